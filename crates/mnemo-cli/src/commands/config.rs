@@ -50,6 +50,7 @@ impl ConfigCommand {
                         "upstream_url": config.proxy.upstream_url,
                         "timeout_secs": config.proxy.timeout_secs,
                         "max_injection_tokens": config.proxy.max_injection_tokens,
+                        "tombstone_budget_share": config.proxy.tombstone_budget_share,
                     },
                     "router": {
                         "strategy": config.router.strategy,
@@ -66,8 +67,8 @@ impl ConfigCommand {
                 println!("{}", serde_json::to_string_pretty(&output)?);
             }
             OutputFormat::Table => {
-                if config_path.is_some() {
-                    println!("Configuration from: {}", config_path.unwrap().display());
+                if let Some(path) = config_path {
+                    println!("Configuration from: {}", path.display());
                 } else {
                     println!("Configuration: (using defaults)");
                 }
@@ -107,6 +108,10 @@ impl ConfigCommand {
                     "max_injection_tokens",
                     &config.proxy.max_injection_tokens.to_string(),
                 ]);
+                proxy_table.add_row([
+                    "tombstone_budget_share",
+                    &config.proxy.tombstone_budget_share.to_string(),
+                ]);
 
                 println!("{proxy_table}\n");
 
@@ -31,6 +31,9 @@ pub enum MemorySubcommand {
 
     #[clap(about = "Manually add a memory")]
     Add(AddArgs),
+
+    #[clap(about = "Restore a compressed memory to its full archived content")]
+    Rehydrate(RehydrateArgs),
 }
 
 #[derive(Parser)]
@@ -59,6 +62,13 @@ pub struct ListArgs {
         conflicts_with = "session"
     )]
     pub global: bool,
+
+    #[clap(
+        long = "tag",
+        value_delimiter = ',',
+        help = "Show only memories with this tag (repeatable; all must match)"
+    )]
+    pub tags: Vec<String>,
 }
 
 #[derive(Parser)]
@@ -79,6 +89,12 @@ pub struct GlobalizeArgs {
     pub id: String,
 }
 
+#[derive(Parser)]
+pub struct RehydrateArgs {
+    #[clap(help = "Memory ID to rehydrate (UUID format)")]
+    pub id: String,
+}
+
 #[derive(Parser)]
 pub struct AddArgs {
     #[clap(help = "Memory content text")]
@@ -90,6 +106,13 @@ pub struct AddArgs {
         help = "Memory type (episodic, semantic, procedural)"
     )]
     pub r#type: String,
+
+    #[clap(
+        long = "tag",
+        value_delimiter = ',',
+        help = "Tag to attach (repeatable or comma-separated)"
+    )]
+    pub tags: Vec<String>,
 }
 
 impl MemoryCommand {
@@ -100,6 +123,7 @@ impl MemoryCommand {
             MemorySubcommand::Delete(args) => Self::delete(store, args, format).await,
             MemorySubcommand::Globalize(args) => Self::globalize(store, args, format).await,
             MemorySubcommand::Add(args) => Self::add(store, args, format).await,
+            MemorySubcommand::Rehydrate(args) => Self::rehydrate(store, args, format).await,
         }
     }
 
@@ -134,7 +158,12 @@ impl MemoryCommand {
             memories.retain(|m| m.conversation_id.is_none());
         }
 
-        memories.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        for tag in &args.tags {
+            let tag = tag.trim();
+            memories.retain(|m| m.tags.iter().any(|t| t == tag));
+        }
+
+        memories.sort_by_key(|m| std::cmp::Reverse(m.created_at));
         memories.truncate(args.limit);
 
         match format {
@@ -148,6 +177,7 @@ impl MemoryCommand {
                             "type": format!("{:?}", m.memory_type),
                             "weight": m.weight,
                             "tier": format!("{:?}", m.tier),
+                            "tags": &m.tags,
                             "created_at": m.created_at.to_rfc3339(),
                         })
                     })
@@ -164,7 +194,7 @@ impl MemoryCommand {
                 table
                     .load_preset(UTF8_FULL_CONDENSED)
                     .set_content_arrangement(ContentArrangement::Dynamic)
-                    .set_header(["ID", "Content", "Type", "Weight", "Tier", "Created"]);
+                    .set_header(["ID", "Content", "Type", "Weight", "Tier", "Tags", "Created"]);
 
                 for memory in &memories {
                     table.add_row([
@@ -173,6 +203,7 @@ impl MemoryCommand {
                         format!("{:?}", memory.memory_type),
                         format!("{:.2}", memory.weight),
                         format!("{:?}", memory.tier),
+                        truncate_string(&memory.tags.join(", "), 30),
                         format_timestamp(&memory.created_at),
                     ]);
                 }
@@ -209,6 +240,8 @@ impl MemoryCommand {
                     "access_count": memory.access_count,
                     "conversation_id": memory.conversation_id,
                     "entities": memory.entities,
+                    "tags": memory.tags,
+                    "metadata": memory.metadata,
                 });
                 println!("{}", serde_json::to_string_pretty(&output)?);
             }
@@ -234,6 +267,11 @@ impl MemoryCommand {
                     memory.conversation_id.as_deref().unwrap_or("-"),
                 ]);
                 table.add_row(["Entities", &memory.entities.join(", ")]);
+                table.add_row(["Tags", &memory.tags.join(", ")]);
+                table.add_row([
+                    "Metadata",
+                    &serde_json::Value::Object(memory.metadata.clone()).to_string(),
+                ]);
                 table.add_row(["Embedding Size", &memory.embedding.len().to_string()]);
 
                 println!("{table}");
@@ -310,6 +348,35 @@ impl MemoryCommand {
         Ok(())
     }
 
+    async fn rehydrate(
+        store: &LanceStore,
+        args: &RehydrateArgs,
+        format: OutputFormat,
+    ) -> CliResult<()> {
+        let id = Uuid::parse_str(&args.id).map_err(|e| format!("Invalid UUID format: {e}"))?;
+
+        let rehydrated = store.rehydrate(id).await?;
+
+        match format {
+            OutputFormat::Json => {
+                let output = serde_json::json!({
+                    "id": args.id,
+                    "rehydrated": rehydrated,
+                });
+                println!("{}", serde_json::to_string_pretty(&output)?);
+            }
+            OutputFormat::Table => {
+                if rehydrated {
+                    println!("Memory {} restored to full content.", args.id);
+                } else {
+                    println!("Memory {} is already full or has no archived content.", args.id);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     async fn add(store: &LanceStore, args: &AddArgs, format: OutputFormat) -> CliResult<()> {
         let memory_type = match args.r#type.as_str() {
             "episodic" => MemoryType::Episodic,
@@ -326,12 +393,13 @@ impl MemoryCommand {
         let embedding_model = mnemo_server::embedding::EmbeddingModel::new()?;
         let embedding = embedding_model.embed(&args.text)?;
 
-        let memory = Memory::new(
+        let mut memory = Memory::new(
             args.text.clone(),
             embedding,
             memory_type,
             MemorySource::Manual,
         );
+        memory.add_tags(args.tags.iter().cloned());
 
         let id = memory.id;
         store.insert(&memory).await?;
@@ -341,6 +409,7 @@ impl MemoryCommand {
                 let output = serde_json::json!({
                     "id": id.to_string(),
                     "created": true,
+                    "tags": memory.tags,
                 });
                 println!("{}", serde_json::to_string_pretty(&output)?);
             }
@@ -83,6 +83,12 @@ async fn run() -> CliResult<()> {
                 store.create_tombstones_table().await?;
             }
 
+            if store.table_exists("archive").await? {
+                store.open_archive_table().await?;
+            } else {
+                store.create_archive_table().await?;
+            }
+
             match &cli.command {
                 Command::Memory(cmd) => cmd.execute(&store, format).await,
                 Command::Stats(cmd) => cmd.execute(&store, format).await,
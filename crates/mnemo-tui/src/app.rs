@@ -432,6 +432,7 @@ General
                 path,
                 provider,
                 timestamp,
+                ..
             } => {
                 use crate::views::requests::RequestEntry;
                 let entry = RequestEntry::from_started(
@@ -448,18 +449,39 @@ General
                 status,
                 latency_ms,
                 ..
+            }
+            | ProxyEvent::RequestFailed {
+                request_id,
+                status,
+                latency_ms,
+                ..
             } => {
                 self.request_log.complete_request(&request_id, status, latency_ms);
             }
             ProxyEvent::Heartbeat { stats, .. } => {
                 self.stats = stats;
             }
-            ProxyEvent::MemoriesInjected { .. } => {
-                // Could update request entry with memory count
+            ProxyEvent::MemoriesInjected {
+                request_id,
+                query,
+                memories,
+                tokens,
+                ..
+            } => {
+                use crate::views::requests::InjectionInfo;
+                let injection = InjectionInfo {
+                    query,
+                    memories,
+                    tokens,
+                };
+                self.request_log.set_injection(&request_id, injection);
             }
             ProxyEvent::MemoryIngested { .. } => {
                 // Could trigger memory browser refresh
             }
+            ProxyEvent::MaintenanceCompleted { .. } | ProxyEvent::MaintenanceFailed { .. } => {
+                // Could surface maintenance activity in the stats view
+            }
         }
     }
 
@@ -467,10 +489,8 @@ General
         loop {
             if event::poll(Duration::from_millis(50)).unwrap_or(false) {
                 match event::read() {
-                    Ok(CrosstermEvent::Key(key)) => {
-                        if tx.send(Event::Key(key)).is_err() {
-                            break;
-                        }
+                    Ok(CrosstermEvent::Key(key)) if tx.send(Event::Key(key)).is_err() => {
+                        break;
                     }
                     Ok(CrosstermEvent::Resize(w, h)) => {
                         let _ = tx.send(Event::Resize(w, h));
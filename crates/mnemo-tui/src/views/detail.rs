@@ -71,6 +71,27 @@ impl RequestDetailView {
                     .unwrap_or_else(|| "...".to_string()),
             );
 
+            let content = match req.injection {
+                Some(ref injection) => {
+                    let mut content = format!(
+                        "{content}\n\n\
+                         Query: {}\n\
+                         Injected: {} memories ({} tokens)",
+                        injection.query,
+                        injection.memories.len(),
+                        injection.tokens,
+                    );
+                    for memory in &injection.memories {
+                        content.push_str(&format!(
+                            "\n  {}  similarity {:.3}  score {:.3}  {} tokens",
+                            memory.id, memory.similarity_score, memory.final_score, memory.tokens,
+                        ));
+                    }
+                    content
+                }
+                None => content,
+            };
+
             let paragraph = Paragraph::new(content)
                 .wrap(Wrap { trim: false })
                 .scroll((self.scroll, 0));
@@ -1,5 +1,6 @@
 //! Request log view
 
+use mnemo_server::admin::InjectedMemory;
 use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
@@ -18,6 +19,15 @@ pub struct RequestEntry {
     pub provider: String,
     pub status: Option<u16>,
     pub latency_ms: Option<u64>,
+    pub injection: Option<InjectionInfo>,
+}
+
+/// Memories injected into a request
+#[derive(Debug, Clone)]
+pub struct InjectionInfo {
+    pub query: String,
+    pub memories: Vec<InjectedMemory>,
+    pub tokens: usize,
 }
 
 impl RequestEntry {
@@ -36,6 +46,7 @@ impl RequestEntry {
             provider,
             status: None,
             latency_ms: None,
+            injection: None,
         }
     }
 
@@ -73,6 +84,12 @@ impl RequestLogView {
         }
     }
 
+    pub fn set_injection(&mut self, request_id: &str, injection: InjectionInfo) {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.request_id == request_id) {
+            entry.injection = Some(injection);
+        }
+    }
+
     pub fn next(&mut self) {
         let i = match self.state.selected() {
             Some(i) => {
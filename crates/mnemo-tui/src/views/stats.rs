@@ -4,6 +4,7 @@ use mnemo_server::admin::DaemonStats;
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Style},
+    text::Line,
     widgets::{Block, Borders, Gauge, Paragraph},
     Frame,
 };
@@ -19,7 +20,7 @@ impl StatsView {
                 Constraint::Length(3), // Title
                 Constraint::Length(3), // Total memories gauge
                 Constraint::Min(6),    // Tier breakdown
-                Constraint::Length(3), // Requests counter
+                Constraint::Length(7), // Activity
             ])
             .split(area);
 
@@ -67,10 +68,33 @@ impl StatsView {
         frame.render_widget(warm_gauge, tier_chunks[1]);
         frame.render_widget(cold_gauge, tier_chunks[2]);
 
-        // Requests counter
-        let requests = Paragraph::new(format!("Total Requests: {}", stats.total_requests))
-            .block(Block::default().borders(Borders::ALL).title("Activity"));
-        frame.render_widget(requests, chunks[3]);
+        // Activity
+        let activity = Paragraph::new(vec![
+            Line::from(format!(
+                "Requests: {}    Active sessions: {}",
+                stats.total_requests, stats.active_sessions
+            )),
+            Line::from(format!(
+                "Injection hit rate: {:.0}% ({}/{})",
+                stats.injection_hit_rate * 100.0,
+                stats.injection_hits,
+                stats.injection_attempts
+            )),
+            Line::from(format!(
+                "Latency p50/p95/p99: {}/{}/{} ms",
+                stats.latency_p50_ms, stats.latency_p95_ms, stats.latency_p99_ms
+            )),
+            Line::from(format!(
+                "Ingested: {} (filtered {}, failed {})",
+                stats.memories_ingested, stats.ingestion_filtered, stats.ingestion_failed
+            )),
+            Line::from(format!(
+                "Curator: {} stored, {} rejected, {} failed",
+                stats.curator_stored, stats.curator_rejected, stats.curator_failed
+            )),
+        ])
+        .block(Block::default().borders(Borders::ALL).title("Activity"));
+        frame.render_widget(activity, chunks[3]);
     }
 
     fn tier_gauge(title: &str, count: u64, total: u64, color: Color) -> Gauge<'static> {
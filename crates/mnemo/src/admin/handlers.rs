@@ -1,6 +1,10 @@
 use axum::{
     extract::{Query, State},
-    response::sse::{Event, KeepAlive, Sse},
+    http::header,
+    response::{
+        IntoResponse,
+        sse::{Event, KeepAlive, Sse},
+    },
     Json,
 };
 use futures::stream::StreamExt;
@@ -13,6 +17,7 @@ use crate::admin::{AdminMemory, DaemonStats};
 use crate::memory::types::StorageTier;
 use crate::proxy::AppState;
 use crate::storage::filter::MemoryFilter;
+use crate::telemetry;
 
 pub async fn events_handler(
     State(state): State<Arc<AppState>>,
@@ -32,22 +37,53 @@ pub async fn events_handler(
 }
 
 pub async fn stats_handler(State(state): State<Arc<AppState>>) -> Json<DaemonStats> {
+    Json(collect_daemon_stats(&state).await)
+}
+
+/// Build a `DaemonStats` snapshot from the store, session buffers and live counters
+pub async fn collect_daemon_stats(state: &AppState) -> DaemonStats {
     let store = state.store.lock().await;
 
     let hot_count = store.count_by_tier(StorageTier::Hot).await.unwrap_or(0) as u64;
     let warm_count = store.count_by_tier(StorageTier::Warm).await.unwrap_or(0) as u64;
     let cold_count = store.count_by_tier(StorageTier::Cold).await.unwrap_or(0) as u64;
+    drop(store);
 
-    let stats = DaemonStats {
+    let mut stats = DaemonStats {
         total_memories: hot_count + warm_count + cold_count,
         hot_count,
         warm_count,
         cold_count,
-        total_requests: 0,
-        active_sessions: 0,
+        active_sessions: state
+            .conversation_buffers
+            .iter()
+            .filter(|buffer| !buffer.is_idle())
+            .count() as u64,
+        ..DaemonStats::default()
     };
+    state.stats.fill(&mut stats);
+
+    stats
+}
+
+/// Prometheus scrape endpoint
+///
+/// Tier counts are read from the store at scrape time; everything else is
+/// recorded as requests are handled.
+pub async fn metrics_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let store = state.store.lock().await;
+    for tier in [StorageTier::Hot, StorageTier::Warm, StorageTier::Cold] {
+        match store.count_by_tier(tier).await {
+            Ok(count) => telemetry::set_tier_count(tier, count),
+            Err(e) => tracing::debug!("Failed to count {:?} memories: {}", tier, e),
+        }
+    }
+    drop(store);
 
-    Json(stats)
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4; charset=utf-8")],
+        telemetry::render(),
+    )
 }
 
 #[derive(Debug, Deserialize, Default)]
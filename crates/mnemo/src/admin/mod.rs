@@ -4,10 +4,16 @@
 //! between the daemon and admin clients.
 
 pub mod handlers;
+pub mod stats;
 
+pub use stats::{CurationOutcome, IngestionOutcome, StatsCollector};
+
+use crate::memory::retrieval::RetrievedMemory;
 use crate::memory::types::Memory;
+use crate::proxy::estimate_tokens;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Events emitted by the proxy for real-time monitoring
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -19,6 +25,9 @@ pub enum ProxyEvent {
         method: String,
         path: String,
         provider: String,
+        /// Azure OpenAI deployment the request targets
+        #[serde(default)]
+        deployment: Option<String>,
         timestamp: DateTime<Utc>,
     },
     /// Memories were injected into a request
@@ -26,6 +35,15 @@ pub enum ProxyEvent {
         request_id: String,
         memory_ids: Vec<String>,
         count: usize,
+        /// The query used for retrieval
+        #[serde(default)]
+        query: String,
+        /// The injected memories with their retrieval scores
+        #[serde(default)]
+        memories: Vec<InjectedMemory>,
+        /// Estimated tokens of the whole injected block, including tombstones
+        #[serde(default)]
+        tokens: usize,
     },
     /// A request has completed
     RequestCompleted {
@@ -34,6 +52,13 @@ pub enum ProxyEvent {
         latency_ms: u64,
         bytes: Option<u64>,
     },
+    /// A streamed response was aborted before the upstream finished
+    RequestFailed {
+        request_id: String,
+        status: u16,
+        latency_ms: u64,
+        error: String,
+    },
     /// A new memory was ingested
     MemoryIngested {
         memory_id: String,
@@ -45,10 +70,24 @@ pub enum ProxyEvent {
         timestamp: DateTime<Utc>,
         stats: DaemonStats,
     },
+    /// A background maintenance task finished
+    MaintenanceCompleted {
+        task: String,
+        affected: u64,
+        duration_ms: u64,
+        timestamp: DateTime<Utc>,
+    },
+    /// A background maintenance task failed
+    MaintenanceFailed {
+        task: String,
+        error: String,
+        timestamp: DateTime<Utc>,
+    },
 }
 
 /// Statistics about the daemon's current state
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
 pub struct DaemonStats {
     /// Total number of memories stored
     pub total_memories: u64,
@@ -62,6 +101,34 @@ pub struct DaemonStats {
     pub total_requests: u64,
     /// Number of active sessions
     pub active_sessions: u64,
+    /// Requests processed, keyed by detected provider
+    pub requests_by_provider: BTreeMap<String, u64>,
+    /// Responses returned to clients, keyed by HTTP status code
+    pub requests_by_status: BTreeMap<u16, u64>,
+    /// Requests where memory injection was attempted
+    pub injection_attempts: u64,
+    /// Injection attempts that injected at least one memory
+    pub injection_hits: u64,
+    /// Fraction of injection attempts that injected at least one memory
+    pub injection_hit_rate: f64,
+    /// Captured responses stored as memories
+    pub memories_ingested: u64,
+    /// Captured responses filtered out by the ingestion pipeline
+    pub ingestion_filtered: u64,
+    /// Ingestions that failed with an error
+    pub ingestion_failed: u64,
+    /// Curator runs that chose to store memories
+    pub curator_stored: u64,
+    /// Curator runs that decided not to store anything
+    pub curator_rejected: u64,
+    /// Curator runs that failed
+    pub curator_failed: u64,
+    /// Median request latency in milliseconds over recent requests
+    pub latency_p50_ms: u64,
+    /// 95th percentile request latency in milliseconds over recent requests
+    pub latency_p95_ms: u64,
+    /// 99th percentile request latency in milliseconds over recent requests
+    pub latency_p99_ms: u64,
 }
 
 /// Subset of Memory fields for admin API responses
@@ -89,6 +156,12 @@ pub struct AdminMemory {
     pub entities: Vec<String>,
     /// Optional conversation ID this memory belongs to
     pub conversation_id: Option<String>,
+    /// Free-form tags
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Arbitrary key/value metadata
+    #[serde(default)]
+    pub metadata: serde_json::Map<String, serde_json::Value>,
 }
 
 impl From<&Memory> for AdminMemory {
@@ -104,6 +177,32 @@ impl From<&Memory> for AdminMemory {
             last_accessed: memory.last_accessed,
             entities: memory.entities.clone(),
             conversation_id: memory.conversation_id.clone(),
+            tags: memory.tags.clone(),
+            metadata: memory.metadata.clone(),
+        }
+    }
+}
+
+/// A memory injected into a request, as reported in `MemoriesInjected` events
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InjectedMemory {
+    /// Unique identifier of the memory
+    pub id: String,
+    /// Cosine similarity to the query
+    pub similarity_score: f32,
+    /// Final ranking score after weighting
+    pub final_score: f32,
+    /// Estimated tokens of the memory content
+    pub tokens: usize,
+}
+
+impl From<&RetrievedMemory> for InjectedMemory {
+    fn from(retrieved: &RetrievedMemory) -> Self {
+        Self {
+            id: retrieved.memory.id.to_string(),
+            similarity_score: retrieved.similarity_score,
+            final_score: retrieved.final_score,
+            tokens: estimate_tokens(&retrieved.memory.content),
         }
     }
 }
@@ -120,12 +219,49 @@ mod tests {
             method: "POST".to_string(),
             path: "/v1/chat/completions".to_string(),
             provider: "openai".to_string(),
+            deployment: Some("gpt-4o".to_string()),
             timestamp: Utc::now(),
         };
 
         let json = serde_json::to_string(&event).expect("Failed to serialize event");
         assert!(json.contains("request_started"));
         assert!(json.contains("req-123"));
+        assert!(json.contains("\"deployment\":\"gpt-4o\""));
+    }
+
+    #[test]
+    fn test_memories_injected_roundtrip() {
+        let event = ProxyEvent::MemoriesInjected {
+            request_id: "req-123".to_string(),
+            memory_ids: vec!["mem-1".to_string()],
+            count: 1,
+            query: "What theme do I like?".to_string(),
+            memories: vec![InjectedMemory {
+                id: "mem-1".to_string(),
+                similarity_score: 0.9,
+                final_score: 0.8,
+                tokens: 12,
+            }],
+            tokens: 40,
+        };
+
+        let json = serde_json::to_string(&event).expect("Failed to serialize event");
+        let deserialized: ProxyEvent = serde_json::from_str(&json).expect("Failed to deserialize");
+
+        match deserialized {
+            ProxyEvent::MemoriesInjected {
+                query,
+                memories,
+                tokens,
+                ..
+            } => {
+                assert_eq!(query, "What theme do I like?");
+                assert_eq!(memories.len(), 1);
+                assert_eq!(memories[0].final_score, 0.8);
+                assert_eq!(tokens, 40);
+            }
+            other => panic!("Unexpected event: {other:?}"),
+        }
     }
 
     #[test]
@@ -137,6 +273,33 @@ mod tests {
         assert_eq!(stats.cold_count, 0);
         assert_eq!(stats.total_requests, 0);
         assert_eq!(stats.active_sessions, 0);
+        assert!(stats.requests_by_provider.is_empty());
+        assert_eq!(stats.injection_hit_rate, 0.0);
+        assert_eq!(stats.latency_p99_ms, 0);
+    }
+
+    #[test]
+    fn test_daemon_stats_deserializes_partial_json() {
+        let json = r#"{"total_memories": 5, "total_requests": 2, "active_sessions": 1}"#;
+        let stats: DaemonStats = serde_json::from_str(json).expect("Failed to deserialize");
+
+        assert_eq!(stats.total_memories, 5);
+        assert_eq!(stats.total_requests, 2);
+        assert_eq!(stats.active_sessions, 1);
+        assert!(stats.requests_by_status.is_empty());
+    }
+
+    #[test]
+    fn test_daemon_stats_status_map_roundtrip() {
+        let mut stats = DaemonStats::default();
+        stats.requests_by_status.insert(200, 3);
+        stats.requests_by_provider.insert("OpenAI".to_string(), 3);
+
+        let json = serde_json::to_string(&stats).expect("Failed to serialize");
+        let deserialized: DaemonStats = serde_json::from_str(&json).expect("Failed to deserialize");
+
+        assert_eq!(deserialized.requests_by_status[&200], 3);
+        assert_eq!(deserialized.requests_by_provider["OpenAI"], 3);
     }
 
     #[test]
@@ -173,6 +336,8 @@ mod tests {
             last_accessed: Utc::now(),
             entities: vec!["Rust".to_string(), "programming".to_string()],
             conversation_id: Some("conv-123".to_string()),
+            tags: vec!["rust".to_string()],
+            metadata: serde_json::Map::new(),
         };
 
         let json = serde_json::to_string(&admin_memory).expect("Failed to serialize");
@@ -0,0 +1,262 @@
+//! Live statistics collected by the proxy
+//!
+//! `StatsCollector` is shared through `AppState` and updated as requests,
+//! injections, ingestions and curator runs happen. Snapshots are folded into
+//! `DaemonStats` for `/admin/stats` and heartbeat events.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use dashmap::DashMap;
+
+use crate::admin::DaemonStats;
+
+/// Number of recent request latencies kept for percentile calculation
+const LATENCY_WINDOW: usize = 1024;
+
+/// Outcome of ingesting captured content
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IngestionOutcome {
+    /// A memory was stored (or merged into an existing one)
+    Ingested,
+    /// Content was filtered out by the pipeline
+    Filtered,
+    /// Ingestion returned an error
+    Failed,
+}
+
+/// Outcome of a curator run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurationOutcome {
+    /// The curator chose to store memories
+    Stored,
+    /// The curator decided the content was not worth storing
+    Rejected,
+    /// The curator returned an error
+    Failed,
+}
+
+/// Thread-safe counters for daemon activity
+#[derive(Debug, Default)]
+pub struct StatsCollector {
+    total_requests: AtomicU64,
+    requests_by_provider: DashMap<String, u64>,
+    requests_by_status: DashMap<u16, u64>,
+    injection_attempts: AtomicU64,
+    injection_hits: AtomicU64,
+    memories_ingested: AtomicU64,
+    ingestion_filtered: AtomicU64,
+    ingestion_failed: AtomicU64,
+    curator_stored: AtomicU64,
+    curator_rejected: AtomicU64,
+    curator_failed: AtomicU64,
+    latencies_ms: Mutex<VecDeque<u64>>,
+}
+
+impl StatsCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an incoming proxied request
+    pub fn record_request(&self, provider: &str) {
+        self.total_requests.fetch_add(1, Ordering::Relaxed);
+        *self
+            .requests_by_provider
+            .entry(provider.to_string())
+            .or_insert(0) += 1;
+    }
+
+    /// Record the status code returned to the client
+    pub fn record_status(&self, status: u16) {
+        *self.requests_by_status.entry(status).or_insert(0) += 1;
+    }
+
+    /// Record the latency of a completed request
+    pub fn record_latency(&self, latency_ms: u64) {
+        let mut latencies = self
+            .latencies_ms
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if latencies.len() == LATENCY_WINDOW {
+            latencies.pop_front();
+        }
+        latencies.push_back(latency_ms);
+    }
+
+    /// Record an injection attempt and how many memories it injected
+    pub fn record_injection(&self, injected: usize) {
+        self.injection_attempts.fetch_add(1, Ordering::Relaxed);
+        if injected > 0 {
+            self.injection_hits.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Record the outcome of an ingestion
+    pub fn record_ingestion(&self, outcome: IngestionOutcome) {
+        let counter = match outcome {
+            IngestionOutcome::Ingested => &self.memories_ingested,
+            IngestionOutcome::Filtered => &self.ingestion_filtered,
+            IngestionOutcome::Failed => &self.ingestion_failed,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record the outcome of a curator run
+    pub fn record_curation(&self, outcome: CurationOutcome) {
+        let counter = match outcome {
+            CurationOutcome::Stored => &self.curator_stored,
+            CurationOutcome::Rejected => &self.curator_rejected,
+            CurationOutcome::Failed => &self.curator_failed,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Latency percentile (0-100) over the recent window, or 0 if no requests completed
+    pub fn latency_percentile(&self, percentile: f64) -> u64 {
+        let mut latencies: Vec<u64> = self
+            .latencies_ms
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .copied()
+            .collect();
+        if latencies.is_empty() {
+            return 0;
+        }
+
+        latencies.sort_unstable();
+        let rank = (percentile.clamp(0.0, 100.0) / 100.0 * (latencies.len() - 1) as f64).round();
+        latencies[rank as usize]
+    }
+
+    /// Fill the activity fields of `stats` from the current counters
+    ///
+    /// Memory counts and active sessions come from the store and session
+    /// buffers and are left to the caller.
+    pub fn fill(&self, stats: &mut DaemonStats) {
+        stats.total_requests = self.total_requests.load(Ordering::Relaxed);
+        stats.requests_by_provider = self
+            .requests_by_provider
+            .iter()
+            .map(|entry| (entry.key().clone(), *entry.value()))
+            .collect::<BTreeMap<_, _>>();
+        stats.requests_by_status = self
+            .requests_by_status
+            .iter()
+            .map(|entry| (*entry.key(), *entry.value()))
+            .collect::<BTreeMap<_, _>>();
+
+        stats.injection_attempts = self.injection_attempts.load(Ordering::Relaxed);
+        stats.injection_hits = self.injection_hits.load(Ordering::Relaxed);
+        stats.injection_hit_rate = if stats.injection_attempts > 0 {
+            stats.injection_hits as f64 / stats.injection_attempts as f64
+        } else {
+            0.0
+        };
+
+        stats.memories_ingested = self.memories_ingested.load(Ordering::Relaxed);
+        stats.ingestion_filtered = self.ingestion_filtered.load(Ordering::Relaxed);
+        stats.ingestion_failed = self.ingestion_failed.load(Ordering::Relaxed);
+
+        stats.curator_stored = self.curator_stored.load(Ordering::Relaxed);
+        stats.curator_rejected = self.curator_rejected.load(Ordering::Relaxed);
+        stats.curator_failed = self.curator_failed.load(Ordering::Relaxed);
+
+        stats.latency_p50_ms = self.latency_percentile(50.0);
+        stats.latency_p95_ms = self.latency_percentile(95.0);
+        stats.latency_p99_ms = self.latency_percentile(99.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_counters() {
+        let stats = StatsCollector::new();
+        stats.record_request("OpenAI");
+        stats.record_request("OpenAI");
+        stats.record_request("Anthropic");
+        stats.record_status(200);
+        stats.record_status(200);
+        stats.record_status(502);
+
+        let mut daemon_stats = DaemonStats::default();
+        stats.fill(&mut daemon_stats);
+
+        assert_eq!(daemon_stats.total_requests, 3);
+        assert_eq!(daemon_stats.requests_by_provider["OpenAI"], 2);
+        assert_eq!(daemon_stats.requests_by_provider["Anthropic"], 1);
+        assert_eq!(daemon_stats.requests_by_status[&200], 2);
+        assert_eq!(daemon_stats.requests_by_status[&502], 1);
+    }
+
+    #[test]
+    fn test_injection_hit_rate() {
+        let stats = StatsCollector::new();
+        stats.record_injection(3);
+        stats.record_injection(0);
+        stats.record_injection(1);
+        stats.record_injection(0);
+
+        let mut daemon_stats = DaemonStats::default();
+        stats.fill(&mut daemon_stats);
+
+        assert_eq!(daemon_stats.injection_attempts, 4);
+        assert_eq!(daemon_stats.injection_hits, 2);
+        assert_eq!(daemon_stats.injection_hit_rate, 0.5);
+    }
+
+    #[test]
+    fn test_ingestion_and_curation_outcomes() {
+        let stats = StatsCollector::new();
+        stats.record_ingestion(IngestionOutcome::Ingested);
+        stats.record_ingestion(IngestionOutcome::Ingested);
+        stats.record_ingestion(IngestionOutcome::Filtered);
+        stats.record_ingestion(IngestionOutcome::Failed);
+        stats.record_curation(CurationOutcome::Stored);
+        stats.record_curation(CurationOutcome::Rejected);
+        stats.record_curation(CurationOutcome::Rejected);
+
+        let mut daemon_stats = DaemonStats::default();
+        stats.fill(&mut daemon_stats);
+
+        assert_eq!(daemon_stats.memories_ingested, 2);
+        assert_eq!(daemon_stats.ingestion_filtered, 1);
+        assert_eq!(daemon_stats.ingestion_failed, 1);
+        assert_eq!(daemon_stats.curator_stored, 1);
+        assert_eq!(daemon_stats.curator_rejected, 2);
+        assert_eq!(daemon_stats.curator_failed, 0);
+    }
+
+    #[test]
+    fn test_latency_percentiles() {
+        let stats = StatsCollector::new();
+        assert_eq!(stats.latency_percentile(50.0), 0);
+
+        for ms in 1..=100 {
+            stats.record_latency(ms);
+        }
+
+        assert_eq!(stats.latency_percentile(0.0), 1);
+        assert_eq!(stats.latency_percentile(50.0), 51);
+        assert_eq!(stats.latency_percentile(99.0), 99);
+        assert_eq!(stats.latency_percentile(100.0), 100);
+    }
+
+    #[test]
+    fn test_latency_window_is_bounded() {
+        let stats = StatsCollector::new();
+        for _ in 0..LATENCY_WINDOW {
+            stats.record_latency(1000);
+        }
+        for _ in 0..LATENCY_WINDOW {
+            stats.record_latency(10);
+        }
+
+        assert_eq!(stats.latency_percentile(100.0), 10);
+    }
+}
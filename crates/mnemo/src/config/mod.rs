@@ -1,6 +1,8 @@
 use serde::Deserialize;
 use std::path::PathBuf;
 
+use crate::proxy::Provider;
+
 /// Main configuration structure for Mnemo
 #[derive(Debug, Clone, Deserialize, Default)]
 pub struct Config {
@@ -19,6 +21,15 @@ pub struct Config {
     /// Memory curator configuration
     #[serde(default)]
     pub curator: CuratorConfig,
+    /// Memory ingestion configuration
+    #[serde(default)]
+    pub ingestion: IngestionConfig,
+    /// Conversation capture configuration
+    #[serde(default)]
+    pub capture: CaptureConfig,
+    /// Background maintenance configuration
+    #[serde(default)]
+    pub maintenance: MaintenanceConfig,
 }
 
 /// Storage tier configuration
@@ -85,6 +96,12 @@ pub struct ProxyConfig {
     /// Maximum tokens to inject into context
     #[serde(default = "default_max_injection_tokens")]
     pub max_injection_tokens: usize,
+    /// Fraction of `max_injection_tokens` reserved for tombstones of evicted memories
+    #[serde(default = "default_tombstone_budget_share")]
+    pub tombstone_budget_share: f32,
+    /// Rules mapping upstream requests to a provider, checked before detection
+    #[serde(default)]
+    pub routes: Vec<RouteRule>,
 }
 
 impl Default for ProxyConfig {
@@ -95,10 +112,50 @@ impl Default for ProxyConfig {
             allowed_hosts: Vec::new(),
             timeout_secs: default_timeout_secs(),
             max_injection_tokens: default_max_injection_tokens(),
+            tombstone_budget_share: default_tombstone_budget_share(),
+            routes: Vec::new(),
         }
     }
 }
 
+/// Rule mapping matching upstream requests to a provider format
+///
+/// A rule matches when all of its conditions do; a rule without conditions
+/// matches every request. Rules are checked in order and the first match is
+/// used. Requests matching no rule fall back to provider detection.
+#[derive(Debug, Clone, Deserialize)]
+pub struct RouteRule {
+    /// Upstream host, exact or a `*.` wildcard (e.g., "*.openai.azure.com")
+    #[serde(default)]
+    pub host: Option<String>,
+    /// Prefix the upstream path must start with
+    #[serde(default)]
+    pub path_prefix: Option<String>,
+    /// Name of a header the request must carry
+    #[serde(default)]
+    pub header: Option<String>,
+    /// Required value of `header` (any value matches when unset)
+    #[serde(default)]
+    pub header_value: Option<String>,
+    /// Provider format of matching requests (detected when unset)
+    #[serde(default)]
+    pub provider: Option<Provider>,
+    /// Inject memories into matching requests
+    #[serde(default = "default_route_inject")]
+    pub inject: bool,
+    /// Capture responses to matching requests as memories
+    #[serde(default = "default_route_capture")]
+    pub capture: bool,
+}
+
+fn default_route_inject() -> bool {
+    true
+}
+
+fn default_route_capture() -> bool {
+    true
+}
+
 fn default_listen_addr() -> String {
     "127.0.0.1:9999".to_string()
 }
@@ -111,21 +168,35 @@ fn default_max_injection_tokens() -> usize {
     2000
 }
 
+fn default_tombstone_budget_share() -> f32 {
+    0.2
+}
+
 /// Request routing and strategy configuration
 #[derive(Debug, Clone, Deserialize)]
 pub struct RouterConfig {
     /// Strategy for selecting relevant memories (placeholder)
     #[serde(default)]
     pub strategy: String,
-    /// Maximum memories to retrieve per request (placeholder)
+    /// Maximum memories to retrieve per request
     #[serde(default = "default_max_memories")]
     pub max_memories: usize,
-    /// Minimum relevance score threshold (placeholder)
+    /// Minimum similarity score for a memory to be injected
     #[serde(default = "default_relevance_threshold")]
     pub relevance_threshold: f32,
     /// Deterministic retrieval settings for improved LLM cache hit rates
     #[serde(default)]
     pub deterministic: DeterministicConfig,
+    /// How the retrieval query is built from the conversation
+    #[serde(default)]
+    pub query: QueryConfig,
+    /// Hybrid BM25 and vector search settings
+    #[serde(default)]
+    pub hybrid: HybridSearchConfig,
+    /// Restore compressed memories to their archived full content once they
+    /// have been retrieved this many times (disabled when unset)
+    #[serde(default)]
+    pub rehydrate_after_accesses: Option<u32>,
 }
 
 impl Default for RouterConfig {
@@ -135,6 +206,9 @@ impl Default for RouterConfig {
             max_memories: default_max_memories(),
             relevance_threshold: default_relevance_threshold(),
             deterministic: DeterministicConfig::default(),
+            query: QueryConfig::default(),
+            hybrid: HybridSearchConfig::default(),
+            rehydrate_after_accesses: None,
         }
     }
 }
@@ -183,6 +257,90 @@ fn default_topic_overlap_weight() -> f32 {
     0.1
 }
 
+/// Strategy for building the retrieval query from a conversation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryStrategy {
+    /// Embed the last user message only
+    #[default]
+    LastMessage,
+    /// Embed the last `turns` turns and average them, newer turns weighted higher
+    RecentTurns,
+    /// Blend each user message into a per-session running embedding
+    Rolling,
+    /// Search with each of the last `turns` turns and fuse the rankings
+    MultiQuery,
+}
+
+/// Retrieval query construction configuration
+#[derive(Debug, Clone, Deserialize)]
+pub struct QueryConfig {
+    /// Query construction strategy
+    #[serde(default)]
+    pub strategy: QueryStrategy,
+    /// Number of recent user and assistant turns used by `recent_turns` and `multi_query`
+    #[serde(default = "default_query_turns")]
+    pub turns: usize,
+    /// Weight multiplier applied per turn of age (newest turn has weight 1.0)
+    #[serde(default = "default_recency_decay")]
+    pub recency_decay: f32,
+    /// Weight of the newest message in the rolling embedding (0.0-1.0)
+    #[serde(default = "default_rolling_alpha")]
+    pub rolling_alpha: f32,
+}
+
+impl Default for QueryConfig {
+    fn default() -> Self {
+        Self {
+            strategy: QueryStrategy::default(),
+            turns: default_query_turns(),
+            recency_decay: default_recency_decay(),
+            rolling_alpha: default_rolling_alpha(),
+        }
+    }
+}
+
+fn default_query_turns() -> usize {
+    4
+}
+
+fn default_recency_decay() -> f32 {
+    0.5
+}
+
+fn default_rolling_alpha() -> f32 {
+    0.5
+}
+
+/// Hybrid search configuration
+///
+/// Fuses BM25 full-text relevance over memory content and entities with
+/// vector similarity, so exact identifiers such as error codes, ticket
+/// numbers and function names are found even when embeddings miss them.
+#[derive(Debug, Clone, Deserialize)]
+pub struct HybridSearchConfig {
+    /// Enable hybrid search
+    #[serde(default)]
+    pub enabled: bool,
+    /// Weight of full-text relevance in the blend (0.0-1.0); vector
+    /// similarity gets the remainder
+    #[serde(default = "default_hybrid_text_weight")]
+    pub text_weight: f32,
+}
+
+impl Default for HybridSearchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            text_weight: default_hybrid_text_weight(),
+        }
+    }
+}
+
+fn default_hybrid_text_weight() -> f32 {
+    0.3
+}
+
 /// Embedding model configuration
 #[derive(Debug, Clone, Deserialize)]
 pub struct EmbeddingConfig {
@@ -356,6 +514,9 @@ pub struct BufferConfig {
     /// Maximum tokens in buffer before triggering curation
     #[serde(default = "default_buffer_max_tokens")]
     pub max_tokens: usize,
+    /// Seconds a session's buffer is kept after its last turn
+    #[serde(default = "default_buffer_session_ttl_secs")]
+    pub session_ttl_secs: u64,
 }
 
 impl Default for BufferConfig {
@@ -363,6 +524,7 @@ impl Default for BufferConfig {
         Self {
             max_turns: default_buffer_max_turns(),
             max_tokens: default_buffer_max_tokens(),
+            session_ttl_secs: default_buffer_session_ttl_secs(),
         }
     }
 }
@@ -375,6 +537,10 @@ fn default_buffer_max_tokens() -> usize {
     8000
 }
 
+fn default_buffer_session_ttl_secs() -> u64 {
+    1800
+}
+
 /// Injection tracking configuration for feedback loop
 #[derive(Debug, Clone, Deserialize)]
 pub struct InjectionTrackingConfig {
@@ -384,9 +550,15 @@ pub struct InjectionTrackingConfig {
     /// Maximum number of tracked injections
     #[serde(default = "default_injection_tracking_max_entries")]
     pub max_entries: usize,
-    /// Penalty factor for unused memories (0.0-1.0)
+    /// Score multiplier for memories already injected in the session (0.0-1.0)
     #[serde(default = "default_injection_tracking_penalty_factor")]
     pub penalty_factor: f32,
+    /// Maximum number of sessions tracked at once
+    #[serde(default = "default_injection_tracking_max_sessions")]
+    pub max_sessions: usize,
+    /// Seconds of inactivity after which a session's tracking is reset
+    #[serde(default = "default_injection_tracking_session_ttl_secs")]
+    pub session_ttl_secs: u64,
 }
 
 impl Default for InjectionTrackingConfig {
@@ -395,6 +567,8 @@ impl Default for InjectionTrackingConfig {
             enabled: default_injection_tracking_enabled(),
             max_entries: default_injection_tracking_max_entries(),
             penalty_factor: default_injection_tracking_penalty_factor(),
+            max_sessions: default_injection_tracking_max_sessions(),
+            session_ttl_secs: default_injection_tracking_session_ttl_secs(),
         }
     }
 }
@@ -411,6 +585,247 @@ fn default_injection_tracking_penalty_factor() -> f32 {
     0.3
 }
 
+fn default_injection_tracking_max_sessions() -> usize {
+    1000
+}
+
+fn default_injection_tracking_session_ttl_secs() -> u64 {
+    3600
+}
+
+/// Memory ingestion configuration
+///
+/// Controls near-duplicate detection: before a new memory is stored, its
+/// embedding is compared against existing memories of the same type. If one
+/// is at least `dedup_threshold` similar, the new content is merged into it
+/// instead of creating a new memory.
+#[derive(Debug, Clone, Deserialize)]
+pub struct IngestionConfig {
+    /// Enable near-duplicate merging
+    #[serde(default = "default_dedup_enabled")]
+    pub dedup_enabled: bool,
+    /// Minimum cosine similarity for two memories to be considered duplicates (0.0 - 1.0)
+    #[serde(default = "default_dedup_threshold")]
+    pub dedup_threshold: f32,
+    /// Weight added to an existing memory each time a duplicate is merged into it
+    #[serde(default = "default_merge_weight_boost")]
+    pub merge_weight_boost: f32,
+}
+
+impl Default for IngestionConfig {
+    fn default() -> Self {
+        Self {
+            dedup_enabled: default_dedup_enabled(),
+            dedup_threshold: default_dedup_threshold(),
+            merge_weight_boost: default_merge_weight_boost(),
+        }
+    }
+}
+
+fn default_dedup_enabled() -> bool {
+    true
+}
+
+fn default_dedup_threshold() -> f32 {
+    0.95
+}
+
+fn default_merge_weight_boost() -> f32 {
+    0.05
+}
+
+/// Conversation capture configuration
+///
+/// Controls which turns of a proxied conversation are recorded in the
+/// curator's conversation buffer, and whether user messages are stored
+/// directly when no curator is running.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CaptureConfig {
+    /// Record new user messages in the conversation buffer
+    #[serde(default = "default_capture_user_turns")]
+    pub user_turns: bool,
+    /// Record tool results sent back by the client in the conversation buffer
+    #[serde(default = "default_capture_tool_turns")]
+    pub tool_turns: bool,
+    /// Ingest user messages as memories when no curator is running
+    #[serde(default = "default_capture_ingest_user_messages")]
+    pub ingest_user_messages: bool,
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        Self {
+            user_turns: default_capture_user_turns(),
+            tool_turns: default_capture_tool_turns(),
+            ingest_user_messages: default_capture_ingest_user_messages(),
+        }
+    }
+}
+
+fn default_capture_user_turns() -> bool {
+    true
+}
+
+fn default_capture_tool_turns() -> bool {
+    false
+}
+
+fn default_capture_ingest_user_messages() -> bool {
+    false
+}
+
+/// Background maintenance configuration for compaction, eviction and tier migration
+///
+/// Each task runs on a fixed interval unless a cron expression is given in
+/// `schedule`, in which case the cron schedule takes precedence. Cron
+/// expressions use six fields: `sec min hour day-of-month month day-of-week`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MaintenanceConfig {
+    /// Enable the background maintenance scheduler
+    #[serde(default = "default_maintenance_enabled")]
+    pub enabled: bool,
+    /// Compaction task configuration
+    #[serde(default)]
+    pub compaction: CompactionScheduleConfig,
+    /// Capacity-driven eviction task configuration
+    #[serde(default)]
+    pub eviction: EvictionScheduleConfig,
+    /// Age/access-based tier migration task configuration
+    #[serde(default)]
+    pub tier_migration: TierMigrationScheduleConfig,
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_maintenance_enabled(),
+            compaction: CompactionScheduleConfig::default(),
+            eviction: EvictionScheduleConfig::default(),
+            tier_migration: TierMigrationScheduleConfig::default(),
+        }
+    }
+}
+
+fn default_maintenance_enabled() -> bool {
+    false
+}
+
+/// Schedule for the compaction task
+#[derive(Debug, Clone, Deserialize)]
+pub struct CompactionScheduleConfig {
+    /// Enable scheduled compaction
+    #[serde(default = "default_maintenance_task_enabled")]
+    pub enabled: bool,
+    /// Interval between runs in seconds
+    #[serde(default = "default_compaction_interval_secs")]
+    pub interval_secs: u64,
+    /// Cron expression (overrides `interval_secs` when set)
+    #[serde(default)]
+    pub schedule: Option<String>,
+}
+
+impl Default for CompactionScheduleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_maintenance_task_enabled(),
+            interval_secs: default_compaction_interval_secs(),
+            schedule: None,
+        }
+    }
+}
+
+/// Schedule and limits for the eviction task
+#[derive(Debug, Clone, Deserialize)]
+pub struct EvictionScheduleConfig {
+    /// Enable scheduled eviction (deletes memories, so it is opt-in)
+    #[serde(default = "default_eviction_enabled")]
+    pub enabled: bool,
+    /// Interval between runs in seconds
+    #[serde(default = "default_eviction_interval_secs")]
+    pub interval_secs: u64,
+    /// Cron expression (overrides `interval_secs` when set)
+    #[serde(default)]
+    pub schedule: Option<String>,
+    /// Maximum number of memories per tier used for capacity calculation
+    #[serde(default = "default_eviction_max_memories_per_tier")]
+    pub max_memories_per_tier: usize,
+}
+
+impl Default for EvictionScheduleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_eviction_enabled(),
+            interval_secs: default_eviction_interval_secs(),
+            schedule: None,
+            max_memories_per_tier: default_eviction_max_memories_per_tier(),
+        }
+    }
+}
+
+/// Schedule and thresholds for the tier migration task
+#[derive(Debug, Clone, Deserialize)]
+pub struct TierMigrationScheduleConfig {
+    /// Enable scheduled tier migration
+    #[serde(default = "default_maintenance_task_enabled")]
+    pub enabled: bool,
+    /// Interval between runs in seconds
+    #[serde(default = "default_tier_migration_interval_secs")]
+    pub interval_secs: u64,
+    /// Cron expression (overrides `interval_secs` when set)
+    #[serde(default)]
+    pub schedule: Option<String>,
+    /// Days without access before a hot memory is demoted to warm
+    #[serde(default = "default_hot_idle_days")]
+    pub hot_idle_days: u32,
+    /// Days without access before a warm memory is demoted to cold
+    #[serde(default = "default_warm_idle_days")]
+    pub warm_idle_days: u32,
+}
+
+impl Default for TierMigrationScheduleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_maintenance_task_enabled(),
+            interval_secs: default_tier_migration_interval_secs(),
+            schedule: None,
+            hot_idle_days: default_hot_idle_days(),
+            warm_idle_days: default_warm_idle_days(),
+        }
+    }
+}
+
+fn default_maintenance_task_enabled() -> bool {
+    true
+}
+
+fn default_eviction_enabled() -> bool {
+    false
+}
+
+fn default_compaction_interval_secs() -> u64 {
+    86400
+}
+
+fn default_eviction_interval_secs() -> u64 {
+    900
+}
+
+fn default_eviction_max_memories_per_tier() -> usize {
+    10000
+}
+
+fn default_tier_migration_interval_secs() -> u64 {
+    3600
+}
+
+fn default_hot_idle_days() -> u32 {
+    7
+}
+
+fn default_warm_idle_days() -> u32 {
+    30
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -426,6 +841,7 @@ mod tests {
         assert!(config.proxy.allowed_hosts.is_empty());
         assert_eq!(config.proxy.timeout_secs, 300);
         assert_eq!(config.proxy.max_injection_tokens, 2000);
+        assert_eq!(config.proxy.tombstone_budget_share, 0.2);
         assert_eq!(config.router.max_memories, 10);
         assert_eq!(config.router.relevance_threshold, 0.7);
         assert_eq!(config.embedding.dimension, 1536);
@@ -446,6 +862,7 @@ listen_addr = "0.0.0.0:8080"
 upstream_url = "https://api.openai.com/v1"
 timeout_secs = 60
 max_injection_tokens = 4000
+tombstone_budget_share = 0.1
 
 [router]
 strategy = "semantic"
@@ -474,6 +891,7 @@ batch_size = 64
         assert!(config.proxy.allowed_hosts.is_empty());
         assert_eq!(config.proxy.timeout_secs, 60);
         assert_eq!(config.proxy.max_injection_tokens, 4000);
+        assert_eq!(config.proxy.tombstone_budget_share, 0.1);
 
         assert_eq!(config.router.strategy, "semantic");
         assert_eq!(config.router.max_memories, 20);
@@ -570,6 +988,48 @@ topic_overlap_weight = 0.2
         assert!((config.router.deterministic.topic_overlap_weight - 0.2).abs() < f32::EPSILON);
     }
 
+    #[test]
+    fn test_query_config_defaults() {
+        let config = Config::default();
+        assert_eq!(config.router.query.strategy, QueryStrategy::LastMessage);
+        assert_eq!(config.router.query.turns, 4);
+        assert!((config.router.query.recency_decay - 0.5).abs() < f32::EPSILON);
+        assert!((config.router.query.rolling_alpha - 0.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_query_config_from_toml() {
+        let toml_str = r#"
+[router.query]
+strategy = "multi_query"
+turns = 6
+recency_decay = 0.7
+"#;
+
+        let config: Config = toml::from_str(toml_str).expect("Failed to parse TOML");
+        assert_eq!(config.router.query.strategy, QueryStrategy::MultiQuery);
+        assert_eq!(config.router.query.turns, 6);
+        assert!((config.router.query.recency_decay - 0.7).abs() < f32::EPSILON);
+        assert!((config.router.query.rolling_alpha - 0.5).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_hybrid_search_config() {
+        let config = Config::default();
+        assert!(!config.router.hybrid.enabled);
+        assert!((config.router.hybrid.text_weight - 0.3).abs() < f32::EPSILON);
+
+        let toml_str = r#"
+[router.hybrid]
+enabled = true
+text_weight = 0.5
+"#;
+
+        let config: Config = toml::from_str(toml_str).expect("Failed to parse TOML");
+        assert!(config.router.hybrid.enabled);
+        assert!((config.router.hybrid.text_weight - 0.5).abs() < f32::EPSILON);
+    }
+
     #[test]
     fn test_curator_config_defaults() {
         let config = Config::default();
@@ -591,11 +1051,14 @@ topic_overlap_weight = 0.2
         // Buffer config defaults
         assert_eq!(config.curator.buffer.max_turns, 10);
         assert_eq!(config.curator.buffer.max_tokens, 8000);
+        assert_eq!(config.curator.buffer.session_ttl_secs, 1800);
 
         // Injection tracking defaults
         assert!(config.curator.injection_tracking.enabled);
         assert_eq!(config.curator.injection_tracking.max_entries, 1000);
         assert!((config.curator.injection_tracking.penalty_factor - 0.3).abs() < f32::EPSILON);
+        assert_eq!(config.curator.injection_tracking.max_sessions, 1000);
+        assert_eq!(config.curator.injection_tracking.session_ttl_secs, 3600);
     }
 
     #[test]
@@ -620,11 +1083,14 @@ timeout_secs = 60
 [curator.buffer]
 max_turns = 20
 max_tokens = 16000
+session_ttl_secs = 300
 
 [curator.injection_tracking]
 enabled = false
 max_entries = 500
 penalty_factor = 0.5
+max_sessions = 50
+session_ttl_secs = 600
 "#;
 
         let config: Config = toml::from_str(toml_str).expect("Failed to parse TOML");
@@ -651,11 +1117,14 @@ penalty_factor = 0.5
         // Buffer config
         assert_eq!(config.curator.buffer.max_turns, 20);
         assert_eq!(config.curator.buffer.max_tokens, 16000);
+        assert_eq!(config.curator.buffer.session_ttl_secs, 300);
 
         // Injection tracking
         assert!(!config.curator.injection_tracking.enabled);
         assert_eq!(config.curator.injection_tracking.max_entries, 500);
         assert!((config.curator.injection_tracking.penalty_factor - 0.5).abs() < f32::EPSILON);
+        assert_eq!(config.curator.injection_tracking.max_sessions, 50);
+        assert_eq!(config.curator.injection_tracking.session_ttl_secs, 600);
     }
 
     #[test]
@@ -688,4 +1157,151 @@ use_gpu = true
         assert_eq!(config.curator.buffer.max_turns, 10);
         assert_eq!(config.curator.buffer.max_tokens, 8000);
     }
+
+    #[test]
+    fn test_maintenance_config_defaults() {
+        let config = Config::default();
+        assert!(!config.maintenance.enabled);
+
+        assert!(config.maintenance.compaction.enabled);
+        assert_eq!(config.maintenance.compaction.interval_secs, 86400);
+        assert!(config.maintenance.compaction.schedule.is_none());
+
+        assert!(!config.maintenance.eviction.enabled);
+        assert_eq!(config.maintenance.eviction.interval_secs, 900);
+        assert_eq!(config.maintenance.eviction.max_memories_per_tier, 10000);
+
+        assert!(config.maintenance.tier_migration.enabled);
+        assert_eq!(config.maintenance.tier_migration.interval_secs, 3600);
+        assert_eq!(config.maintenance.tier_migration.hot_idle_days, 7);
+        assert_eq!(config.maintenance.tier_migration.warm_idle_days, 30);
+    }
+
+    #[test]
+    fn test_maintenance_config_from_toml() {
+        let toml_str = r#"
+[maintenance]
+enabled = true
+
+[maintenance.compaction]
+schedule = "0 0 3 * * *"
+
+[maintenance.eviction]
+enabled = false
+max_memories_per_tier = 500
+
+[maintenance.tier_migration]
+interval_secs = 600
+hot_idle_days = 1
+"#;
+
+        let config: Config = toml::from_str(toml_str).expect("Failed to parse TOML");
+
+        assert!(config.maintenance.enabled);
+        assert_eq!(
+            config.maintenance.compaction.schedule.as_deref(),
+            Some("0 0 3 * * *")
+        );
+        assert_eq!(config.maintenance.compaction.interval_secs, 86400); // default
+
+        assert!(!config.maintenance.eviction.enabled);
+        assert_eq!(config.maintenance.eviction.max_memories_per_tier, 500);
+
+        assert_eq!(config.maintenance.tier_migration.interval_secs, 600);
+        assert_eq!(config.maintenance.tier_migration.hot_idle_days, 1);
+        assert_eq!(config.maintenance.tier_migration.warm_idle_days, 30); // default
+    }
+
+    #[test]
+    fn test_maintenance_config_rejects_negative_idle_days() {
+        let toml_str = r#"
+[maintenance.tier_migration]
+hot_idle_days = -1
+"#;
+
+        assert!(toml::from_str::<Config>(toml_str).is_err());
+    }
+
+    #[test]
+    fn test_ingestion_config_defaults() {
+        let config = Config::default();
+        assert!(config.ingestion.dedup_enabled);
+        assert_eq!(config.ingestion.dedup_threshold, 0.95);
+        assert_eq!(config.ingestion.merge_weight_boost, 0.05);
+    }
+
+    #[test]
+    fn test_ingestion_config_from_toml() {
+        let toml_str = r#"
+[ingestion]
+dedup_threshold = 0.9
+"#;
+
+        let config: Config = toml::from_str(toml_str).expect("Failed to parse TOML");
+
+        assert!(config.ingestion.dedup_enabled); // default
+        assert_eq!(config.ingestion.dedup_threshold, 0.9);
+        assert_eq!(config.ingestion.merge_weight_boost, 0.05); // default
+    }
+
+    #[test]
+    fn test_proxy_routes_from_toml() {
+        let toml_str = r#"
+[[proxy.routes]]
+host = "*.openai.azure.com"
+provider = "openai"
+
+[[proxy.routes]]
+host = "gateway.internal"
+path_prefix = "/anthropic"
+header = "x-gateway-provider"
+header_value = "anthropic"
+provider = "anthropic"
+capture = false
+
+[[proxy.routes]]
+host = "api.groq.com"
+inject = false
+"#;
+
+        let config: Config = toml::from_str(toml_str).expect("Failed to parse TOML");
+        let routes = &config.proxy.routes;
+
+        assert_eq!(routes.len(), 3);
+        assert_eq!(routes[0].host.as_deref(), Some("*.openai.azure.com"));
+        assert_eq!(routes[0].provider, Some(Provider::OpenAI));
+        assert!(routes[0].inject && routes[0].capture); // defaults
+
+        assert_eq!(routes[1].path_prefix.as_deref(), Some("/anthropic"));
+        assert_eq!(routes[1].header.as_deref(), Some("x-gateway-provider"));
+        assert_eq!(routes[1].header_value.as_deref(), Some("anthropic"));
+        assert_eq!(routes[1].provider, Some(Provider::Anthropic));
+        assert!(!routes[1].capture);
+
+        assert!(routes[2].provider.is_none());
+        assert!(!routes[2].inject);
+    }
+
+    #[test]
+    fn test_capture_config_defaults() {
+        let config = Config::default();
+        assert!(config.capture.user_turns);
+        assert!(!config.capture.tool_turns);
+        assert!(!config.capture.ingest_user_messages);
+    }
+
+    #[test]
+    fn test_capture_config_from_toml() {
+        let toml_str = r#"
+[capture]
+tool_turns = true
+ingest_user_messages = true
+"#;
+
+        let config: Config = toml::from_str(toml_str).expect("Failed to parse TOML");
+
+        assert!(config.capture.user_turns); // default
+        assert!(config.capture.tool_turns);
+        assert!(config.capture.ingest_user_messages);
+    }
 }
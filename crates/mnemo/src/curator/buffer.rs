@@ -5,7 +5,7 @@
 //! context window for memory extraction.
 
 use crate::config::BufferConfig;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use std::collections::VecDeque;
 
 /// Role of a conversation participant
@@ -17,6 +17,8 @@ pub enum Role {
     Assistant,
     /// System message
     System,
+    /// Tool result returned by the client
+    Tool,
 }
 
 impl Role {
@@ -26,6 +28,7 @@ impl Role {
             Role::User => "user",
             Role::Assistant => "assistant",
             Role::System => "system",
+            Role::Tool => "tool",
         }
     }
 }
@@ -63,11 +66,14 @@ impl ConversationTurn {
 /// Buffer for managing conversation history with LRU eviction
 ///
 /// Maintains a fixed-size buffer of conversation turns, evicting oldest
-/// turns when either max_turns or max_tokens limits are exceeded.
+/// turns when either max_turns or max_tokens limits are exceeded. A buffer
+/// that has seen no turns for `session_ttl_secs` is idle and can be dropped.
 pub struct ConversationBuffer {
     turns: VecDeque<ConversationTurn>,
     max_turns: usize,
     max_tokens: usize,
+    session_ttl: Duration,
+    last_activity: DateTime<Utc>,
 }
 
 impl ConversationBuffer {
@@ -77,6 +83,11 @@ impl ConversationBuffer {
             turns: VecDeque::new(),
             max_turns: config.max_turns,
             max_tokens: config.max_tokens,
+            session_ttl: i64::try_from(config.session_ttl_secs)
+                .ok()
+                .and_then(Duration::try_seconds)
+                .unwrap_or(Duration::MAX),
+            last_activity: Utc::now(),
         }
     }
 
@@ -84,10 +95,34 @@ impl ConversationBuffer {
     ///
     /// After adding, enforces limits by evicting oldest turns if necessary.
     pub fn push(&mut self, turn: ConversationTurn) {
+        self.last_activity = self.last_activity.max(turn.timestamp);
         self.turns.push_back(turn);
         self.enforce_limits();
     }
 
+    /// Add a turn unless the latest turns of its role already include it
+    ///
+    /// Clients resend the conversation with every request, so the same message
+    /// can be seen more than once, e.g. when a request is retried. Only the
+    /// most recent run of turns with the same role is checked, so a message
+    /// repeated later in the conversation is still recorded. Returns whether
+    /// the turn was added.
+    pub fn push_unique(&mut self, turn: ConversationTurn) -> bool {
+        let seen = self
+            .turns
+            .iter()
+            .rev()
+            .skip_while(|t| t.role != turn.role)
+            .take_while(|t| t.role == turn.role)
+            .any(|t| t.content == turn.content);
+        if seen {
+            return false;
+        }
+
+        self.push(turn);
+        true
+    }
+
     /// Format turns as XML prompt context for LLM consumption
     ///
     /// Returns a string in the format:
@@ -117,6 +152,16 @@ impl ConversationBuffer {
         result
     }
 
+    /// Time of the most recent turn, or of creation for an empty buffer
+    pub fn last_activity(&self) -> DateTime<Utc> {
+        self.last_activity
+    }
+
+    /// Whether no turn has been seen for the session TTL
+    pub fn is_idle(&self) -> bool {
+        Utc::now() - self.last_activity >= self.session_ttl
+    }
+
     /// Clear all turns from the buffer
     pub fn clear(&mut self) {
         self.turns.clear();
@@ -173,6 +218,7 @@ mod tests {
         BufferConfig {
             max_turns: 5,
             max_tokens: 100,
+            session_ttl_secs: 1800,
         }
     }
 
@@ -230,6 +276,7 @@ mod tests {
         let config = BufferConfig {
             max_turns: 3,
             max_tokens: 10000, // High limit so we don't hit it
+            session_ttl_secs: 1800,
         };
         let mut buffer = ConversationBuffer::new(&config);
 
@@ -251,6 +298,7 @@ mod tests {
         let config = BufferConfig {
             max_turns: 100, // High limit so we don't hit it
             max_tokens: 10, // Very low limit (40 chars max)
+            session_ttl_secs: 1800,
         };
         let mut buffer = ConversationBuffer::new(&config);
 
@@ -317,6 +365,22 @@ mod tests {
         assert!(context.contains("&quot;quotes&quot;"));
     }
 
+    #[test]
+    fn test_buffer_idle_after_session_ttl() {
+        let mut buffer = ConversationBuffer::new(&test_config());
+        buffer.push(ConversationTurn::new(Role::User, "Hello".to_string()));
+        assert!(!buffer.is_idle());
+
+        let config = BufferConfig {
+            session_ttl_secs: 0,
+            ..test_config()
+        };
+        let mut buffer = ConversationBuffer::new(&config);
+        buffer.push(ConversationTurn::new(Role::User, "Hello".to_string()));
+        assert!(buffer.is_idle());
+        assert!(buffer.last_activity() <= Utc::now());
+    }
+
     #[test]
     fn test_buffer_clear() {
         let config = test_config();
@@ -331,6 +395,41 @@ mod tests {
         assert!(buffer.is_empty());
     }
 
+    #[test]
+    fn test_buffer_push_unique_skips_seen_turns() {
+        let config = test_config();
+        let mut buffer = ConversationBuffer::new(&config);
+
+        assert!(buffer.push_unique(ConversationTurn::new(Role::User, "Hello".to_string())));
+        assert!(!buffer.push_unique(ConversationTurn::new(Role::User, "Hello".to_string())));
+        assert!(buffer.push_unique(ConversationTurn::new(Role::Assistant, "Hello".to_string())));
+        assert_eq!(buffer.len(), 2);
+    }
+
+    #[test]
+    fn test_buffer_push_unique_checks_only_latest_turns_of_role() {
+        let config = BufferConfig {
+            max_turns: 10,
+            ..test_config()
+        };
+        let mut buffer = ConversationBuffer::new(&config);
+
+        // A retried request resends the same tool results after the response
+        buffer.push(ConversationTurn::new(Role::Tool, "result a".to_string()));
+        buffer.push(ConversationTurn::new(Role::Tool, "result b".to_string()));
+        buffer.push(ConversationTurn::new(Role::Assistant, "Done".to_string()));
+        assert!(!buffer.push_unique(ConversationTurn::new(Role::Tool, "result a".to_string())));
+        assert!(!buffer.push_unique(ConversationTurn::new(Role::Tool, "result b".to_string())));
+
+        // A message repeated in a later turn is new
+        buffer.push(ConversationTurn::new(Role::User, "yes".to_string()));
+        buffer.push(ConversationTurn::new(Role::Assistant, "Sure".to_string()));
+        buffer.push(ConversationTurn::new(Role::User, "no".to_string()));
+        buffer.push(ConversationTurn::new(Role::Assistant, "Okay".to_string()));
+        assert!(buffer.push_unique(ConversationTurn::new(Role::User, "yes".to_string())));
+        assert_eq!(buffer.len(), 8);
+    }
+
     #[test]
     fn test_buffer_iter() {
         let config = test_config();
@@ -350,6 +449,7 @@ mod tests {
         let config = BufferConfig {
             max_turns: 2,
             max_tokens: 100,
+            session_ttl_secs: 1800,
         };
         let mut buffer = ConversationBuffer::new(&config);
 
@@ -371,6 +471,7 @@ mod tests {
         let config = BufferConfig {
             max_turns: 10,
             max_tokens: 5, // Only ~20 chars allowed
+            session_ttl_secs: 1800,
         };
         let mut buffer = ConversationBuffer::new(&config);
 
@@ -37,6 +37,8 @@ struct ExtractedMemoryJson {
     content: String,
     importance: f32,
     entities: Vec<String>,
+    #[serde(default)]
+    supersedes: Option<String>,
 }
 
 impl LocalCurator {
@@ -146,7 +148,12 @@ impl LocalCurator {
                     _ => MemoryType::Semantic,
                 };
 
-                CuratedMemory::new(memory_type, m.content, m.importance, m.entities)
+                let memory =
+                    CuratedMemory::new(memory_type, m.content, m.importance, m.entities);
+                match m.supersedes.filter(|hint| !hint.trim().is_empty()) {
+                    Some(hint) => memory.with_supersedes_hint(hint),
+                    None => memory,
+                }
             })
             .collect();
 
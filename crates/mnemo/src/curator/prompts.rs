@@ -36,17 +36,30 @@ For each memory you identify, provide:
 2. Content: The specific information to remember (be concise but complete)
 3. Importance: Score from 0.0 to 1.0 based on how valuable this is for future interactions
 4. Entities: Key nouns/entities mentioned (people, projects, technologies, etc.)
+5. Supersedes (optional): If this memory replaces an earlier fact that is now outdated (e.g. a changed preference), a short description of that earlier fact; otherwise null
 
 Conversation:
 {conversation}
 
 Respond with a JSON array of memories in this exact format:
+[
+  {
+    "type": "semantic",
+    "content": "User is building a CLI tool in Rust",
+    "importance": 0.8,
+    "entities": ["Rust", "CLI tool"],
+    "supersedes": null
+  }
+]
+
+Only set "supersedes" when the conversation explicitly replaces an earlier fact. For example, if the user says they switched from light mode to dark mode:
 [
   {
     "type": "semantic",
     "content": "User prefers dark mode interfaces",
     "importance": 0.8,
-    "entities": ["dark mode", "UI preferences"]
+    "entities": ["dark mode", "UI preferences"],
+    "supersedes": "User prefers light mode interfaces"
   }
 ]
 
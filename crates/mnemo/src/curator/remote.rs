@@ -67,6 +67,8 @@ struct ExtractedMemoryJson {
     content: String,
     importance: f32,
     entities: Vec<String>,
+    #[serde(default)]
+    supersedes: Option<String>,
 }
 
 impl RemoteCurator {
@@ -230,7 +232,12 @@ impl RemoteCurator {
                     _ => MemoryType::Semantic,
                 };
 
-                CuratedMemory::new(memory_type, m.content, m.importance, m.entities)
+                let memory =
+                    CuratedMemory::new(memory_type, m.content, m.importance, m.entities);
+                match m.supersedes.filter(|hint| !hint.trim().is_empty()) {
+                    Some(hint) => memory.with_supersedes_hint(hint),
+                    None => memory,
+                }
             })
             .collect();
 
@@ -369,13 +376,15 @@ mod tests {
                             "type": "semantic",
                             "content": "User prefers dark mode interfaces",
                             "importance": 0.8,
-                            "entities": ["dark mode", "UI preferences"]
+                            "entities": ["dark mode", "UI preferences"],
+                            "supersedes": "User prefers light mode interfaces"
                         },
                         {
                             "type": "episodic",
                             "content": "User mentioned they are learning Rust",
                             "importance": 0.7,
-                            "entities": ["Rust", "learning"]
+                            "entities": ["Rust", "learning"],
+                            "supersedes": null
                         }
                     ]"#
                 }
@@ -402,7 +411,12 @@ mod tests {
         assert_eq!(memories[0].memory_type, MemoryType::Semantic);
         assert_eq!(memories[0].content, "User prefers dark mode interfaces");
         assert_eq!(memories[0].importance, 0.8);
+        assert_eq!(
+            memories[0].supersedes_hint,
+            Some("User prefers light mode interfaces".to_string())
+        );
         assert_eq!(memories[1].memory_type, MemoryType::Episodic);
+        assert!(memories[1].supersedes_hint.is_none());
     }
 
     #[tokio::test]
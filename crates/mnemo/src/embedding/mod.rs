@@ -1,6 +1,8 @@
 use std::sync::Mutex;
+use std::time::Instant;
 
 use crate::MnemoError;
+use crate::telemetry;
 use fastembed::{EmbeddingModel as FastEmbedModel, InitOptions, TextEmbedding};
 
 pub const EMBEDDING_DIMENSION: usize = 384;
@@ -19,12 +21,15 @@ impl EmbeddingModel {
     }
 
     pub fn embed(&self, text: &str) -> Result<Vec<f32>, MnemoError> {
-        let embeddings = self
+        let mut model = self
             .model
             .lock()
-            .map_err(|e| MnemoError::Embedding(format!("Mutex poisoned: {e}")))?
+            .map_err(|e| MnemoError::Embedding(format!("Mutex poisoned: {e}")))?;
+        let start = Instant::now();
+        let embeddings = model
             .embed(vec![text.to_string()], None)
             .map_err(|e| MnemoError::Embedding(e.to_string()))?;
+        telemetry::record_embedding(start.elapsed());
         embeddings
             .into_iter()
             .next()
@@ -32,11 +37,16 @@ impl EmbeddingModel {
     }
 
     pub fn embed_batch(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, MnemoError> {
-        self.model
+        let mut model = self
+            .model
             .lock()
-            .map_err(|e| MnemoError::Embedding(format!("Mutex poisoned: {e}")))?
+            .map_err(|e| MnemoError::Embedding(format!("Mutex poisoned: {e}")))?;
+        let start = Instant::now();
+        let embeddings = model
             .embed(texts, None)
-            .map_err(|e| MnemoError::Embedding(e.to_string()))
+            .map_err(|e| MnemoError::Embedding(e.to_string()))?;
+        telemetry::record_embedding(start.elapsed());
+        Ok(embeddings)
     }
 }
 
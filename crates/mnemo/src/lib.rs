@@ -9,10 +9,12 @@ pub mod config;
 pub mod curator;
 pub mod embedding;
 pub mod error;
+pub mod maintenance;
 pub mod memory;
 pub mod proxy;
 pub mod router;
 pub mod storage;
+pub mod telemetry;
 pub mod testing;
 
 pub use error::MnemoError;
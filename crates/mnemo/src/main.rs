@@ -123,16 +123,27 @@ async fn serve(config_path: Option<PathBuf>) -> Result<()> {
         ))
     })?;
 
+    tracing::info!("Initializing embedding model (this may take a moment on first run)...");
+    let embedding_model = EmbeddingModel::new()?;
+    tracing::info!("Embedding model initialized");
+
     let mut store = LanceStore::connect(data_dir).await?;
 
     if store.table_exists("memories").await? {
         tracing::debug!("Opening existing memories table");
-        store.open_memories_table().await?;
+        store
+            .open_memories_table_with_model(&embedding_model)
+            .await?;
     } else {
         tracing::info!("Creating memories table");
         store.create_memories_table().await?;
     }
 
+    if config.router.hybrid.enabled {
+        tracing::debug!("Ensuring full-text index for hybrid search");
+        store.create_fts_index().await?;
+    }
+
     if store.table_exists("tombstones").await? {
         tracing::debug!("Opening existing tombstones table");
         store.open_tombstones_table().await?;
@@ -141,9 +152,13 @@ async fn serve(config_path: Option<PathBuf>) -> Result<()> {
         store.create_tombstones_table().await?;
     }
 
-    tracing::info!("Initializing embedding model (this may take a moment on first run)...");
-    let embedding_model = EmbeddingModel::new()?;
-    tracing::info!("Embedding model initialized");
+    if store.table_exists("archive").await? {
+        tracing::debug!("Opening existing archive table");
+        store.open_archive_table().await?;
+    } else {
+        tracing::info!("Creating archive table");
+        store.create_archive_table().await?;
+    }
 
     tracing::info!("Initializing memory router...");
     let router = MemoryRouter::new()?;
@@ -161,7 +176,10 @@ async fn serve(config_path: Option<PathBuf>) -> Result<()> {
         router,
         config.router.clone(),
         Some(config.curator.clone()),
-    );
+    )
+    .with_ingestion(config.ingestion.clone())
+    .with_capture(config.capture.clone())
+    .with_maintenance(config.maintenance.clone());
     tracing::info!("Starting proxy server on {}", config.proxy.listen_addr);
 
     proxy.serve().await?;
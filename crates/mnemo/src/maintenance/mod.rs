@@ -0,0 +1,480 @@
+//! Background maintenance for the Mnemo daemon
+//!
+//! Runs compaction, capacity-driven eviction and age-based tier migration
+//! on configurable schedules so a long-running store does not grow without
+//! limit. Each run is reported on the proxy event channel. Runs check for
+//! shutdown between memories, so stopping never interrupts a store write.
+
+pub mod schedule;
+
+pub use schedule::Schedule;
+
+use std::sync::Arc;
+use std::time::Instant;
+
+use chrono::{Duration, Utc};
+use tokio::sync::{Mutex as TokioMutex, broadcast};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+use crate::admin::ProxyEvent;
+use crate::config::MaintenanceConfig;
+use crate::error::Result;
+use crate::memory::types::StorageTier;
+use crate::storage::{Compactor, EvictionConfig, Evictor, LanceStore, TierManager};
+
+const ALL_TIERS: [StorageTier; 3] = [StorageTier::Hot, StorageTier::Warm, StorageTier::Cold];
+
+/// A maintenance task the scheduler can run
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaintenanceTask {
+    /// Compress old, low-weight memories
+    Compaction,
+    /// Evict low-priority memories from tiers over capacity
+    Eviction,
+    /// Demote memories that have not been accessed recently
+    TierMigration,
+}
+
+impl MaintenanceTask {
+    /// Name used in events and logs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MaintenanceTask::Compaction => "compaction",
+            MaintenanceTask::Eviction => "eviction",
+            MaintenanceTask::TierMigration => "tier_migration",
+        }
+    }
+}
+
+/// Schedules and runs background maintenance tasks against the store
+pub struct MaintenanceScheduler {
+    store: Arc<TokioMutex<LanceStore>>,
+    config: MaintenanceConfig,
+    event_tx: broadcast::Sender<ProxyEvent>,
+    cancel: CancellationToken,
+}
+
+/// Running maintenance loops, returned by [`MaintenanceScheduler::spawn`]
+pub struct MaintenanceHandle {
+    cancel: CancellationToken,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl MaintenanceHandle {
+    /// Stop every maintenance loop
+    ///
+    /// Idle loops stop at once; a run in progress finishes the memory it is
+    /// on. Loops still running after `timeout` are aborted.
+    pub async fn shutdown(self, timeout: std::time::Duration) {
+        self.cancel.cancel();
+
+        let mut tasks = self.tasks;
+        let stopped = tokio::time::timeout(timeout, async {
+            for task in &mut tasks {
+                let _ = task.await;
+            }
+        })
+        .await;
+
+        if stopped.is_err() {
+            tracing::warn!(
+                "Background maintenance did not stop within {:?}, aborting",
+                timeout
+            );
+            for task in &tasks {
+                task.abort();
+            }
+        }
+    }
+}
+
+impl MaintenanceScheduler {
+    pub fn new(
+        store: Arc<TokioMutex<LanceStore>>,
+        config: MaintenanceConfig,
+        event_tx: broadcast::Sender<ProxyEvent>,
+    ) -> Self {
+        Self {
+            store,
+            config,
+            event_tx,
+            cancel: CancellationToken::new(),
+        }
+    }
+
+    /// Spawn a background loop for every enabled task
+    ///
+    /// Schedules are validated up front so a bad cron expression is reported
+    /// at startup rather than silently disabling the task.
+    pub fn spawn(self) -> Result<MaintenanceHandle> {
+        let cancel = self.cancel.clone();
+        if !self.config.enabled {
+            tracing::info!("Background maintenance disabled");
+            return Ok(MaintenanceHandle {
+                cancel,
+                tasks: Vec::new(),
+            });
+        }
+
+        let mut tasks = Vec::new();
+        if self.config.compaction.enabled {
+            let schedule = Schedule::from_config(
+                self.config.compaction.interval_secs,
+                self.config.compaction.schedule.as_deref(),
+            )?;
+            tasks.push((MaintenanceTask::Compaction, schedule));
+        }
+        if self.config.eviction.enabled {
+            let schedule = Schedule::from_config(
+                self.config.eviction.interval_secs,
+                self.config.eviction.schedule.as_deref(),
+            )?;
+            tasks.push((MaintenanceTask::Eviction, schedule));
+        }
+        if self.config.tier_migration.enabled {
+            let schedule = Schedule::from_config(
+                self.config.tier_migration.interval_secs,
+                self.config.tier_migration.schedule.as_deref(),
+            )?;
+            tasks.push((MaintenanceTask::TierMigration, schedule));
+        }
+
+        let scheduler = Arc::new(self);
+        let handles = tasks
+            .into_iter()
+            .map(|(task, schedule)| {
+                tracing::info!("Scheduled background {}: {:?}", task.as_str(), schedule);
+                let scheduler = Arc::clone(&scheduler);
+                tokio::spawn(async move {
+                    while let Some(delay) = schedule.next_delay(Utc::now()) {
+                        tokio::select! {
+                            _ = scheduler.cancel.cancelled() => return,
+                            _ = tokio::time::sleep(delay) => {}
+                        }
+                        scheduler.run_task(task).await;
+                    }
+                    tracing::warn!("No upcoming runs for background {}", task.as_str());
+                })
+            })
+            .collect();
+
+        Ok(MaintenanceHandle {
+            cancel,
+            tasks: handles,
+        })
+    }
+
+    /// Run a single task and report the outcome as a `ProxyEvent`
+    pub async fn run_task(&self, task: MaintenanceTask) {
+        let start_time = Instant::now();
+
+        let result = match task {
+            MaintenanceTask::Compaction => self.run_compaction().await,
+            MaintenanceTask::Eviction => self.run_eviction().await,
+            MaintenanceTask::TierMigration => self.run_tier_migration().await,
+        };
+
+        let event = match result {
+            Ok(affected) => {
+                tracing::debug!(
+                    "Background {} affected {} memories",
+                    task.as_str(),
+                    affected
+                );
+                ProxyEvent::MaintenanceCompleted {
+                    task: task.as_str().to_string(),
+                    affected,
+                    duration_ms: start_time.elapsed().as_millis() as u64,
+                    timestamp: Utc::now(),
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Background {} failed: {}", task.as_str(), e);
+                ProxyEvent::MaintenanceFailed {
+                    task: task.as_str().to_string(),
+                    error: e.to_string(),
+                    timestamp: Utc::now(),
+                }
+            }
+        };
+
+        let _ = self.event_tx.send(event);
+    }
+
+    /// Compact every tier, returning the number of memories compacted
+    ///
+    /// The store lock is taken per memory so proxy traffic is not blocked for
+    /// the whole run; each memory is re-read before it is compacted. A
+    /// shutdown stops the run early.
+    pub async fn run_compaction(&self) -> Result<u64> {
+        let mut compacted = 0u64;
+        for tier in ALL_TIERS {
+            let memories = self.store.lock().await.list_by_tier(tier).await?;
+
+            for memory in memories {
+                if self.cancel.is_cancelled() {
+                    return Ok(compacted);
+                }
+                let store = self.store.lock().await;
+                let compactor = Compactor::new(&store);
+                let Some(target) = compactor.target_compression(&memory) else {
+                    continue;
+                };
+                let Some(current) = store.get(memory.id).await? else {
+                    continue;
+                };
+                if compactor.compact_memory(&current, target).await? {
+                    compacted += 1;
+                }
+            }
+        }
+
+        Ok(compacted)
+    }
+
+    /// Evict from every tier over capacity, returning the number of memories evicted
+    ///
+    /// Candidates are chosen under one short lock, then evicted one per lock so
+    /// proxy traffic can interleave. Memories that were deleted or became
+    /// protected in between are skipped. A shutdown stops the run early.
+    pub async fn run_eviction(&self) -> Result<u64> {
+        let config = EvictionConfig {
+            max_memories_per_tier: self.config.eviction.max_memories_per_tier,
+            ..EvictionConfig::default()
+        };
+
+        let mut evicted = 0u64;
+        for tier in ALL_TIERS {
+            let (candidates, reason) = {
+                let store = self.store.lock().await;
+                Evictor::with_config(&store, config)
+                    .plan_eviction(tier)
+                    .await?
+            };
+
+            for memory in candidates {
+                if self.cancel.is_cancelled() {
+                    return Ok(evicted);
+                }
+                let store = self.store.lock().await;
+                let evictor = Evictor::with_config(&store, config);
+                let Some(current) = store.get(memory.id).await? else {
+                    continue;
+                };
+                if evictor.is_protected(&current) {
+                    continue;
+                }
+                if evictor
+                    .evict_with_tombstone(&current, reason.clone())
+                    .await?
+                {
+                    evicted += 1;
+                }
+            }
+        }
+
+        Ok(evicted)
+    }
+
+    /// Demote idle memories, returning the number of memories demoted
+    ///
+    /// Warm is processed before Hot so a memory moves at most one tier per run.
+    /// The store lock is taken per memory and a shutdown stops the run early,
+    /// as in [`Self::run_compaction`].
+    pub async fn run_tier_migration(&self) -> Result<u64> {
+        let config = &self.config.tier_migration;
+
+        let mut demoted = 0u64;
+        for (tier, idle_days) in [
+            (StorageTier::Warm, config.warm_idle_days),
+            (StorageTier::Hot, config.hot_idle_days),
+        ] {
+            let cutoff = Utc::now() - Duration::days(idle_days.into());
+            let memories = self.store.lock().await.list_by_tier(tier).await?;
+
+            for memory in memories.iter().filter(|m| m.last_accessed < cutoff) {
+                if self.cancel.is_cancelled() {
+                    return Ok(demoted);
+                }
+                let store = self.store.lock().await;
+                if TierManager::new(&store)
+                    .demote_if_idle(memory.id, tier, cutoff)
+                    .await?
+                {
+                    demoted += 1;
+                }
+            }
+        }
+
+        Ok(demoted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::types::{CompressionLevel, Memory, MemorySource, MemoryType};
+
+    async fn create_test_scheduler(
+        config: MaintenanceConfig,
+    ) -> (
+        MaintenanceScheduler,
+        Arc<TokioMutex<LanceStore>>,
+        broadcast::Receiver<ProxyEvent>,
+        tempfile::TempDir,
+    ) {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+        store.create_memories_table().await.unwrap();
+        store.create_tombstones_table().await.unwrap();
+
+        let store = Arc::new(TokioMutex::new(store));
+        let (event_tx, event_rx) = broadcast::channel(16);
+        let scheduler = MaintenanceScheduler::new(store.clone(), config, event_tx);
+
+        (scheduler, store, event_rx, temp_dir)
+    }
+
+    fn create_idle_memory(tier: StorageTier, idle_days: i64) -> Memory {
+        let mut memory = Memory::new(
+            "An idle memory about something".to_string(),
+            vec![0.1; 384],
+            MemoryType::Semantic,
+            MemorySource::Conversation,
+        );
+        memory.tier = tier;
+        memory.weight = 0.2;
+        memory.last_accessed = Utc::now() - Duration::days(idle_days);
+        memory
+    }
+
+    #[tokio::test]
+    async fn test_tier_migration_demotes_one_tier_per_run() {
+        let (scheduler, store, _rx, _dir) =
+            create_test_scheduler(MaintenanceConfig::default()).await;
+
+        let memory = create_idle_memory(StorageTier::Hot, 60);
+        let id = memory.id;
+        store.lock().await.insert(&memory).await.unwrap();
+
+        assert_eq!(scheduler.run_tier_migration().await.unwrap(), 1);
+        let tier = store.lock().await.get(id).await.unwrap().unwrap().tier;
+        assert_eq!(tier, StorageTier::Warm);
+
+        assert_eq!(scheduler.run_tier_migration().await.unwrap(), 1);
+        let tier = store.lock().await.get(id).await.unwrap().unwrap().tier;
+        assert_eq!(tier, StorageTier::Cold);
+    }
+
+    #[tokio::test]
+    async fn test_compaction_compresses_old_memories() {
+        let (scheduler, store, _rx, _dir) =
+            create_test_scheduler(MaintenanceConfig::default()).await;
+
+        let mut old = create_idle_memory(StorageTier::Warm, 1);
+        old.created_at = Utc::now() - Duration::days(40);
+        let recent = create_idle_memory(StorageTier::Hot, 1);
+        {
+            let store = store.lock().await;
+            store.insert(&old).await.unwrap();
+            store.insert(&recent).await.unwrap();
+        }
+
+        assert_eq!(scheduler.run_compaction().await.unwrap(), 1);
+
+        let store = store.lock().await;
+        let compression = store.get(old.id).await.unwrap().unwrap().compression;
+        assert_eq!(compression, CompressionLevel::Summary);
+        let compression = store.get(recent.id).await.unwrap().unwrap().compression;
+        assert_eq!(compression, CompressionLevel::Full);
+    }
+
+    #[tokio::test]
+    async fn test_eviction_respects_configured_capacity() {
+        let mut config = MaintenanceConfig::default();
+        config.eviction.max_memories_per_tier = 2;
+        let (scheduler, store, _rx, _dir) = create_test_scheduler(config).await;
+
+        {
+            let store = store.lock().await;
+            for _ in 0..4 {
+                store
+                    .insert(&create_idle_memory(StorageTier::Hot, 10))
+                    .await
+                    .unwrap();
+            }
+        }
+
+        let evicted = scheduler.run_eviction().await.unwrap();
+        assert!(evicted > 0);
+
+        let store = store.lock().await;
+        assert_eq!(
+            store.count_by_tier(StorageTier::Hot).await.unwrap() as u64,
+            4 - evicted
+        );
+        assert_eq!(
+            store.list_all_tombstones().await.unwrap().len() as u64,
+            evicted
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_task_emits_event() {
+        let (scheduler, _store, mut rx, _dir) =
+            create_test_scheduler(MaintenanceConfig::default()).await;
+
+        scheduler.run_task(MaintenanceTask::Compaction).await;
+
+        match rx.recv().await.unwrap() {
+            ProxyEvent::MaintenanceCompleted { task, affected, .. } => {
+                assert_eq!(task, "compaction");
+                assert_eq!(affected, 0);
+            }
+            other => panic!("Unexpected event: {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_spawn_rejects_invalid_schedule() {
+        let mut config = MaintenanceConfig::default();
+        config.compaction.schedule = Some("not a cron".to_string());
+        let (scheduler, _store, _rx, _dir) = create_test_scheduler(config).await;
+
+        assert!(scheduler.spawn().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_run_stops_between_memories() {
+        let (scheduler, store, _rx, _dir) =
+            create_test_scheduler(MaintenanceConfig::default()).await;
+
+        let memory = create_idle_memory(StorageTier::Hot, 60);
+        let id = memory.id;
+        store.lock().await.insert(&memory).await.unwrap();
+
+        scheduler.cancel.cancel();
+
+        assert_eq!(scheduler.run_tier_migration().await.unwrap(), 0);
+        let tier = store.lock().await.get(id).await.unwrap().unwrap().tier;
+        assert_eq!(tier, StorageTier::Hot);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_stops_idle_loops() {
+        let config = MaintenanceConfig {
+            enabled: true,
+            ..MaintenanceConfig::default()
+        };
+        let (scheduler, _store, _rx, _dir) = create_test_scheduler(config).await;
+
+        let handle = scheduler.spawn().unwrap();
+        assert_eq!(handle.tasks.len(), 3);
+
+        let shutdown = handle.shutdown(std::time::Duration::from_secs(60));
+        tokio::time::timeout(std::time::Duration::from_secs(5), shutdown)
+            .await
+            .expect("idle maintenance loops should stop without waiting for the timeout");
+    }
+}
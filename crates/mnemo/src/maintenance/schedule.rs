@@ -0,0 +1,92 @@
+//! Schedules for background maintenance tasks
+//!
+//! A task runs either on a fixed interval or according to a cron expression.
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use crate::error::{MnemoError, Result};
+
+/// When a maintenance task should run
+#[derive(Debug, Clone)]
+pub enum Schedule {
+    /// Run repeatedly with a fixed delay between runs
+    Interval(Duration),
+    /// Run at the times described by a cron expression
+    Cron(Box<cron::Schedule>),
+}
+
+impl Schedule {
+    /// Build a schedule from task configuration
+    ///
+    /// A cron expression takes precedence over the interval when both are set.
+    pub fn from_config(interval_secs: u64, cron_expr: Option<&str>) -> Result<Self> {
+        if let Some(expr) = cron_expr {
+            let schedule = cron::Schedule::from_str(expr).map_err(|e| {
+                MnemoError::Config(format!("Invalid maintenance schedule '{expr}': {e}"))
+            })?;
+            return Ok(Schedule::Cron(Box::new(schedule)));
+        }
+
+        if interval_secs == 0 {
+            return Err(MnemoError::Config(
+                "Maintenance interval_secs must be greater than zero".to_string(),
+            ));
+        }
+
+        Ok(Schedule::Interval(Duration::from_secs(interval_secs)))
+    }
+
+    /// Time to wait from `now` until the next run
+    ///
+    /// Returns `None` if a cron schedule has no upcoming fire times.
+    pub fn next_delay(&self, now: DateTime<Utc>) -> Option<Duration> {
+        match self {
+            Schedule::Interval(interval) => Some(*interval),
+            Schedule::Cron(schedule) => {
+                let next = schedule.after(&now).next()?;
+                Some((next - now).to_std().unwrap_or_default())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_interval_schedule() {
+        let schedule = Schedule::from_config(60, None).unwrap();
+        assert_eq!(
+            schedule.next_delay(Utc::now()),
+            Some(Duration::from_secs(60))
+        );
+    }
+
+    #[test]
+    fn test_zero_interval_rejected() {
+        assert!(Schedule::from_config(0, None).is_err());
+    }
+
+    #[test]
+    fn test_cron_overrides_interval() {
+        let schedule = Schedule::from_config(60, Some("0 0 3 * * *")).unwrap();
+        assert!(matches!(schedule, Schedule::Cron(_)));
+
+        let now = Utc.with_ymd_and_hms(2024, 1, 1, 2, 0, 0).unwrap();
+        assert_eq!(
+            schedule.next_delay(now),
+            Some(Duration::from_secs(60 * 60))
+        );
+    }
+
+    #[test]
+    fn test_invalid_cron_rejected() {
+        let result = Schedule::from_config(60, Some("not a cron"));
+        assert!(matches!(result, Err(MnemoError::Config(_))));
+    }
+}
@@ -3,19 +3,45 @@
 //! Orchestrates the full ingestion flow: routing, embedding generation,
 //! memory creation, and storage.
 
+use serde_json::{Map, Value};
 use std::sync::Arc;
 use tokio::sync::Mutex as TokioMutex;
+use uuid::Uuid;
 
+use crate::config::IngestionConfig;
 use crate::curator::CuratedMemory;
 use crate::embedding::EmbeddingModel;
 use crate::error::Result;
+use crate::memory::retrieval::cosine_similarity;
+use crate::memory::tombstone::EvictionReason;
 use crate::memory::types::{CompressionLevel, Memory, MemorySource, MemoryType, StorageTier};
+use crate::proxy::{SessionId, ToolCall};
 use crate::router::MemoryRouter;
-use crate::storage::LanceStore;
+use crate::storage::filter::MemoryFilter;
+use crate::storage::{Evictor, LanceStore};
 
 /// Minimum content length for ingestion (in characters)
 const MIN_CONTENT_LENGTH: usize = 10;
 
+/// Minimum similarity between a supersedes hint and an existing memory
+/// for that memory to be considered superseded. Matches must also share an
+/// entity with the new memory, see `shares_entity`.
+const SUPERSEDES_SIMILARITY_THRESHOLD: f32 = 0.85;
+
+/// Number of nearest memories considered when matching a supersedes hint
+const SUPERSEDES_CANDIDATE_LIMIT: usize = 5;
+
+/// Number of nearest memories considered when looking for a near-duplicate
+const DEDUP_CANDIDATE_LIMIT: usize = 5;
+
+/// What a curator supersedes hint refers to
+enum SupersedesTarget {
+    /// ID of the outdated memory
+    Id(Uuid),
+    /// Embedding of a description of the outdated memory
+    Description(Vec<f32>),
+}
+
 /// Pipeline for ingesting content into the memory system.
 ///
 /// The pipeline orchestrates:
@@ -23,11 +49,13 @@ const MIN_CONTENT_LENGTH: usize = 10;
 /// 2. Routing (entity/topic extraction)
 /// 3. Embedding generation
 /// 4. Memory creation with calculated weight and compression level
-/// 5. Storage in LanceDB
+/// 5. Near-duplicate detection (merging into an existing memory)
+/// 6. Storage in LanceDB
 pub struct IngestionPipeline {
     router: Arc<MemoryRouter>,
     embedding_model: Arc<EmbeddingModel>,
     store: Arc<TokioMutex<LanceStore>>,
+    config: IngestionConfig,
 }
 
 impl IngestionPipeline {
@@ -43,9 +71,16 @@ impl IngestionPipeline {
             router,
             embedding_model,
             store,
+            config: IngestionConfig::default(),
         }
     }
 
+    /// Use the given ingestion configuration
+    pub fn with_config(mut self, config: IngestionConfig) -> Self {
+        self.config = config;
+        self
+    }
+
     /// Create a new ingestion pipeline with owned components.
     ///
     /// Initializes its own router (NER model) and embedding model (e5-small).
@@ -55,6 +90,7 @@ impl IngestionPipeline {
             router: Arc::new(MemoryRouter::new()?),
             embedding_model: Arc::new(EmbeddingModel::new()?),
             store: Arc::new(TokioMutex::new(store)),
+            config: IngestionConfig::default(),
         })
     }
 
@@ -76,6 +112,10 @@ impl IngestionPipeline {
     /// # Memory Type Determination
     /// - `Conversation` source -> `Episodic` memory type
     /// - All other sources -> `Semantic` memory type
+    ///
+    /// # Deduplication
+    /// If a near-duplicate already exists, the new content is merged into it
+    /// and the updated existing memory is returned instead.
     pub async fn ingest(
         &mut self,
         text: &str,
@@ -108,9 +148,145 @@ impl IngestionPipeline {
         memory.compression = compression;
         memory.tier = StorageTier::Hot;
 
-        self.store.lock().await.insert(&memory).await?;
+        Ok(Some(self.store_or_merge(memory).await?))
+    }
+
+    /// Ingest a tool invocation as a procedural memory.
+    ///
+    /// The tool name is recorded as an entity ahead of any the router
+    /// extracts, so memories can be found by the tool that produced them.
+    /// Returns `Ok(None)` for calls without a tool name.
+    pub async fn ingest_tool_call(
+        &mut self,
+        call: &ToolCall,
+        conversation_id: Option<String>,
+    ) -> Result<Option<Memory>> {
+        let name = call.name.trim();
+        if name.is_empty() {
+            return Ok(None);
+        }
+
+        let text = call.to_memory_content();
+        let router_output = self.router.route(&text)?;
+        let embedding = self.embedding_model.embed(&text)?;
+
+        let mut entities = vec![name.to_string()];
+        for entity in router_output.entities {
+            if !entities.contains(&entity.text) {
+                entities.push(entity.text);
+            }
+        }
+
+        let mut memory = Memory::new(
+            text,
+            embedding,
+            MemoryType::Procedural,
+            MemorySource::Conversation,
+        );
+        memory.conversation_id = conversation_id;
+        memory.weight = (0.5 + (entities.len() as f32 * 0.1)).min(1.0);
+        memory.entities = entities;
+        memory.compression = Self::determine_compression(memory.content.len());
+        memory.tier = StorageTier::Hot;
+
+        Ok(Some(self.store_or_merge(memory).await?))
+    }
+
+    /// Store a new memory, or merge it into an existing near-duplicate.
+    ///
+    /// Returns the stored memory, or the updated existing memory if merged.
+    async fn store_or_merge(&self, memory: Memory) -> Result<Memory> {
+        let store = self.store.lock().await;
+
+        if let Some(mut existing) = self.find_duplicate(&store, &memory).await? {
+            existing.merge_duplicate(&memory, self.config.merge_weight_boost);
+            store.update_merged(&existing).await?;
+            tracing::debug!("Merged near-duplicate into memory {}", existing.id);
+            return Ok(existing);
+        }
+
+        store.insert(&memory).await?;
+        Ok(memory)
+    }
+
+    /// Add tags and metadata to an ingested memory and persist them.
+    ///
+    /// Tags are merged into the memory's existing tags; metadata keys overwrite
+    /// existing values. Does nothing when there are no labels to apply.
+    pub async fn apply_labels(
+        &self,
+        memory: &mut Memory,
+        tags: &[String],
+        metadata: &Map<String, Value>,
+    ) -> Result<()> {
+        if tags.is_empty() && metadata.is_empty() {
+            return Ok(());
+        }
+
+        memory.add_tags(tags.iter().cloned());
+        for (key, value) in metadata {
+            memory.metadata.insert(key.clone(), value.clone());
+        }
+
+        self.store
+            .lock()
+            .await
+            .update_labels(memory.id, &memory.tags, &memory.metadata)
+            .await
+    }
+
+    /// Find an existing memory similar enough to be a duplicate of `memory`.
+    ///
+    /// Only memories of the same type in the same scope are considered, so
+    /// session memories never merge across sessions or into global memories.
+    async fn find_duplicate(&self, store: &LanceStore, memory: &Memory) -> Result<Option<Memory>> {
+        if !self.config.dedup_enabled {
+            return Ok(None);
+        }
 
-        Ok(Some(memory))
+        let Some(filter) = Self::same_kind_filter(memory) else {
+            return Ok(None);
+        };
+        let candidates = store
+            .search_filtered(&memory.embedding, &filter, DEDUP_CANDIDATE_LIMIT)
+            .await?;
+
+        Ok(candidates
+            .into_iter()
+            .map(|existing| {
+                (
+                    cosine_similarity(&memory.embedding, &existing.embedding),
+                    existing,
+                )
+            })
+            .filter(|(similarity, _)| *similarity >= self.config.dedup_threshold)
+            .max_by(|a, b| a.0.total_cmp(&b.0))
+            .map(|(_, existing)| existing))
+    }
+
+    /// Filter for memories of the same type in the same scope as `memory`
+    ///
+    /// A session memory matches only memories of its own session, and a
+    /// global memory only other global memories. Returns `None` if the
+    /// conversation ID is not a valid session ID and so cannot be safely
+    /// used in a filter.
+    fn same_kind_filter(memory: &Memory) -> Option<MemoryFilter> {
+        let filter = MemoryFilter::new().with_memory_types(vec![memory.memory_type]);
+
+        Some(match memory.conversation_id {
+            Some(ref conversation_id) => {
+                SessionId::try_from(conversation_id.as_str()).ok()?;
+                filter.with_conversation_id(conversation_id.clone())
+            }
+            None => filter.with_session_filter(None),
+        })
+    }
+
+    /// Whether two memories have at least one entity in common (case-insensitive)
+    fn shares_entity(a: &Memory, b: &Memory) -> bool {
+        a.entities
+            .iter()
+            .any(|x| b.entities.iter().any(|y| x.eq_ignore_ascii_case(y)))
     }
 
     fn determine_compression(length: usize) -> CompressionLevel {
@@ -141,10 +317,94 @@ impl IngestionPipeline {
         memory.compression = Self::determine_compression(curated.content.len());
         memory.tier = StorageTier::Hot;
 
-        self.store.lock().await.insert(&memory).await?;
+        // A memory that supersedes another is new information, not a duplicate of it
+        let memory = if curated.supersedes_hint.is_some() {
+            self.store.lock().await.insert(&memory).await?;
+            memory
+        } else {
+            self.store_or_merge(memory).await?
+        };
+
+        if let Some(hint) = curated.supersedes_hint.as_deref() {
+            match self.resolve_supersession(&memory, hint).await {
+                Ok(Some(old_id)) => {
+                    tracing::info!("Memory {} superseded memory {}", memory.id, old_id);
+                }
+                Ok(None) => {
+                    tracing::debug!("No memory matched supersedes hint '{}'", hint);
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to resolve supersedes hint '{}': {}", hint, e);
+                }
+            }
+        }
 
         Ok(memory)
     }
+
+    /// Retire the memory that `memory` supersedes.
+    ///
+    /// The hint is either the ID of the outdated memory or a description of it,
+    /// which is matched semantically. Either way, only a memory of the same
+    /// type, in the same scope (the same session, or both global) and sharing
+    /// an entity with the new memory can be retired, so a session memory never
+    /// retires a global one. The outdated memory is
+    /// evicted with an `EvictionReason::Superseded` tombstone pointing at its
+    /// replacement.
+    ///
+    /// Returns the ID of the retired memory, or `None` if nothing matched.
+    pub async fn resolve_supersession(&self, memory: &Memory, hint: &str) -> Result<Option<Uuid>> {
+        let hint = hint.trim();
+        if hint.is_empty() {
+            return Ok(None);
+        }
+
+        // Embed a descriptive hint before taking the store lock
+        let target = match Uuid::parse_str(hint) {
+            Ok(id) => SupersedesTarget::Id(id),
+            Err(_) => SupersedesTarget::Description(self.embedding_model.embed(hint)?),
+        };
+
+        let store = self.store.lock().await;
+
+        let superseded = match target {
+            // An ID hint gets the same guards as a description, so a
+            // hallucinated ID cannot retire an unrelated memory
+            SupersedesTarget::Id(id) => store.get(id).await?.filter(|old| {
+                old.memory_type == memory.memory_type
+                    && old.conversation_id == memory.conversation_id
+                    && Self::shares_entity(old, memory)
+            }),
+            SupersedesTarget::Description(embedding) => {
+                let Some(filter) = Self::same_kind_filter(memory) else {
+                    return Ok(None);
+                };
+                store
+                    .search_filtered(&embedding, &filter, SUPERSEDES_CANDIDATE_LIMIT)
+                    .await?
+                    .into_iter()
+                    .filter(|old| {
+                        old.id != memory.id
+                            && old.conversation_id == memory.conversation_id
+                            && Self::shares_entity(old, memory)
+                    })
+                    .map(|old| (cosine_similarity(&embedding, &old.embedding), old))
+                    .filter(|(similarity, _)| *similarity >= SUPERSEDES_SIMILARITY_THRESHOLD)
+                    .max_by(|a, b| a.0.total_cmp(&b.0))
+                    .map(|(_, old)| old)
+            }
+        };
+
+        let Some(superseded) = superseded.filter(|old| old.id != memory.id) else {
+            return Ok(None);
+        };
+
+        Evictor::new(&store)
+            .evict_with_tombstone(&superseded, EvictionReason::Superseded { by: memory.id })
+            .await?;
+
+        Ok(Some(superseded.id))
+    }
 }
 
 #[cfg(test)]
@@ -363,4 +623,390 @@ mod tests {
             "This memory should be stored in LanceDB."
         );
     }
+
+    #[tokio::test]
+    async fn test_apply_labels_persists_tags_and_metadata() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+        store.create_memories_table().await.unwrap();
+
+        let mut pipeline = IngestionPipeline::new_owned(store).expect("Failed to create pipeline");
+
+        let mut memory = pipeline
+            .ingest(
+                "This memory is labelled after ingestion.",
+                MemorySource::Manual,
+                None,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+        let mut metadata = Map::new();
+        metadata.insert("repo".to_string(), Value::from("watzon/mnemo"));
+        pipeline
+            .apply_labels(
+                &mut memory,
+                &["rust".to_string(), "rust".to_string()],
+                &metadata,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(memory.tags, vec!["rust".to_string()]);
+
+        let mut store2 = LanceStore::connect(temp_dir.path()).await.unwrap();
+        store2.open_memories_table().await.unwrap();
+
+        let retrieved = store2.get(memory.id).await.unwrap().unwrap();
+        assert_eq!(retrieved.tags, vec!["rust".to_string()]);
+        assert_eq!(retrieved.metadata, metadata);
+    }
+
+    async fn create_test_store_with_tombstones() -> LanceStore {
+        let mut store = create_test_store().await;
+        store.create_tombstones_table().await.unwrap();
+        store
+    }
+
+    #[tokio::test]
+    async fn test_ingest_curated_supersedes_by_id() {
+        let store = create_test_store_with_tombstones().await;
+        let mut pipeline = IngestionPipeline::new_owned(store).expect("Failed to create pipeline");
+
+        let old = pipeline
+            .ingest_curated(
+                CuratedMemory::new(
+                    MemoryType::Semantic,
+                    "User prefers tabs for indentation".to_string(),
+                    0.7,
+                    vec!["indentation".to_string()],
+                ),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let new = pipeline
+            .ingest_curated(
+                CuratedMemory::new(
+                    MemoryType::Semantic,
+                    "User prefers spaces for indentation".to_string(),
+                    0.7,
+                    vec!["indentation".to_string()],
+                )
+                .with_supersedes_hint(old.id.to_string()),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let store = pipeline.store.lock().await;
+        assert!(store.get(old.id).await.unwrap().is_none());
+        assert!(store.get(new.id).await.unwrap().is_some());
+
+        let tombstone = store.get_tombstone(old.id).await.unwrap().unwrap();
+        assert_eq!(tombstone.reason, EvictionReason::Superseded { by: new.id });
+        assert_eq!(tombstone.topics, vec!["indentation".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_ingest_curated_supersedes_by_description() {
+        let store = create_test_store_with_tombstones().await;
+        let mut pipeline = IngestionPipeline::new_owned(store).expect("Failed to create pipeline");
+
+        let old = pipeline
+            .ingest_curated(
+                CuratedMemory::new(
+                    MemoryType::Semantic,
+                    "User prefers tabs for indentation".to_string(),
+                    0.7,
+                    vec!["indentation".to_string()],
+                ),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let new = pipeline
+            .ingest_curated(
+                CuratedMemory::new(
+                    MemoryType::Semantic,
+                    "User prefers spaces for indentation".to_string(),
+                    0.7,
+                    vec!["Indentation".to_string()],
+                )
+                .with_supersedes_hint("User prefers tabs for indentation".to_string()),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let store = pipeline.store.lock().await;
+        assert!(store.get(old.id).await.unwrap().is_none());
+        assert!(store.get(new.id).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_supersedes_by_description_requires_shared_entity() {
+        let store = create_test_store_with_tombstones().await;
+        let mut pipeline = IngestionPipeline::new_owned(store).expect("Failed to create pipeline");
+
+        let old = pipeline
+            .ingest_curated(
+                CuratedMemory::new(
+                    MemoryType::Semantic,
+                    "User prefers tabs for indentation".to_string(),
+                    0.7,
+                    vec!["indentation".to_string()],
+                ),
+                None,
+            )
+            .await
+            .unwrap();
+
+        // The hint describes the old memory exactly, but the new memory is unrelated
+        let new = pipeline
+            .ingest_curated(
+                CuratedMemory::new(
+                    MemoryType::Semantic,
+                    "User's favourite editor is Helix".to_string(),
+                    0.7,
+                    vec!["Helix".to_string()],
+                )
+                .with_supersedes_hint("User prefers tabs for indentation".to_string()),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let store = pipeline.store.lock().await;
+        assert!(store.get(old.id).await.unwrap().is_some());
+        assert!(store.get(new.id).await.unwrap().is_some());
+        assert!(store.get_tombstone(old.id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_supersedes_by_id_ignores_unrelated_memory() {
+        let store = create_test_store_with_tombstones().await;
+        let mut pipeline = IngestionPipeline::new_owned(store).expect("Failed to create pipeline");
+
+        let unrelated = pipeline
+            .ingest_curated(
+                CuratedMemory::new(
+                    MemoryType::Procedural,
+                    "Deploy by tagging a release on the main branch".to_string(),
+                    0.7,
+                    vec!["deployment".to_string()],
+                ),
+                None,
+            )
+            .await
+            .unwrap();
+
+        // The curator points at a memory of another type with no shared entity
+        pipeline
+            .ingest_curated(
+                CuratedMemory::new(
+                    MemoryType::Semantic,
+                    "User prefers spaces for indentation".to_string(),
+                    0.7,
+                    vec!["indentation".to_string()],
+                )
+                .with_supersedes_hint(unrelated.id.to_string()),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let store = pipeline.store.lock().await;
+        assert!(store.get(unrelated.id).await.unwrap().is_some());
+        assert!(store.get_tombstone(unrelated.id).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_session_memory_cannot_supersede_global_memory() {
+        let store = create_test_store_with_tombstones().await;
+        let mut pipeline = IngestionPipeline::new_owned(store).expect("Failed to create pipeline");
+
+        let global = pipeline
+            .ingest_curated(
+                CuratedMemory::new(
+                    MemoryType::Semantic,
+                    "User prefers tabs for indentation".to_string(),
+                    0.7,
+                    vec!["indentation".to_string()],
+                ),
+                None,
+            )
+            .await
+            .unwrap();
+
+        pipeline
+            .ingest_curated(
+                CuratedMemory::new(
+                    MemoryType::Semantic,
+                    "User prefers spaces for indentation".to_string(),
+                    0.7,
+                    vec!["indentation".to_string()],
+                )
+                .with_supersedes_hint(global.id.to_string()),
+                Some("session-a".to_string()),
+            )
+            .await
+            .unwrap();
+
+        let store = pipeline.store.lock().await;
+        assert!(store.get(global.id).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_supersession_ignores_unmatched_hint() {
+        let store = create_test_store_with_tombstones().await;
+        let mut pipeline = IngestionPipeline::new_owned(store).expect("Failed to create pipeline");
+
+        let memory = pipeline
+            .ingest_curated(
+                CuratedMemory::new(
+                    MemoryType::Semantic,
+                    "User prefers spaces for indentation".to_string(),
+                    0.7,
+                    vec![],
+                ),
+                None,
+            )
+            .await
+            .unwrap();
+
+        let resolved = pipeline
+            .resolve_supersession(&memory, &Uuid::new_v4().to_string())
+            .await
+            .unwrap();
+        assert!(resolved.is_none());
+
+        let resolved = pipeline
+            .resolve_supersession(&memory, &memory.id.to_string())
+            .await
+            .unwrap();
+        assert!(resolved.is_none());
+        assert!(
+            pipeline
+                .store
+                .lock()
+                .await
+                .get(memory.id)
+                .await
+                .unwrap()
+                .is_some()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ingest_merges_near_duplicate() {
+        let store = create_test_store().await;
+        let mut pipeline = IngestionPipeline::new_owned(store).expect("Failed to create pipeline");
+
+        let first = pipeline
+            .ingest(
+                "The user prefers dark mode in all of their editors.",
+                MemorySource::Manual,
+                None,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+        let second = pipeline
+            .ingest(
+                "The user prefers dark mode in all of their editors.",
+                MemorySource::Manual,
+                None,
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(second.id, first.id);
+        assert_eq!(second.access_count, first.access_count + 1);
+        assert!(second.weight >= first.weight);
+
+        let store = pipeline.store.lock().await;
+        assert_eq!(store.total_count().await.unwrap(), 1);
+        let stored = store.get(first.id).await.unwrap().unwrap();
+        assert_eq!(stored.access_count, second.access_count);
+    }
+
+    #[tokio::test]
+    async fn test_ingest_dedup_disabled_keeps_duplicates() {
+        let store = create_test_store().await;
+        let mut pipeline = IngestionPipeline::new_owned(store)
+            .expect("Failed to create pipeline")
+            .with_config(IngestionConfig {
+                dedup_enabled: false,
+                ..IngestionConfig::default()
+            });
+
+        for _ in 0..2 {
+            pipeline
+                .ingest(
+                    "The user prefers dark mode in all of their editors.",
+                    MemorySource::Manual,
+                    None,
+                )
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(pipeline.store.lock().await.total_count().await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_ingest_does_not_merge_across_sessions() {
+        let store = create_test_store().await;
+        let mut pipeline = IngestionPipeline::new_owned(store).expect("Failed to create pipeline");
+
+        for session in ["session-a", "session-b"] {
+            pipeline
+                .ingest(
+                    "User said they are migrating the billing service to Rust.",
+                    MemorySource::Conversation,
+                    Some(session.to_string()),
+                )
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(pipeline.store.lock().await.total_count().await.unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_session_memory_does_not_merge_into_global_memory() {
+        let store = create_test_store().await;
+        let mut pipeline = IngestionPipeline::new_owned(store).expect("Failed to create pipeline");
+
+        let content = "User said they are migrating the billing service to Rust.";
+        let global = pipeline
+            .ingest(content, MemorySource::Conversation, None)
+            .await
+            .unwrap()
+            .unwrap();
+        let session = pipeline
+            .ingest(
+                content,
+                MemorySource::Conversation,
+                Some("session-a".to_string()),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_ne!(session.id, global.id);
+        assert_eq!(session.conversation_id, Some("session-a".to_string()));
+
+        let store = pipeline.store.lock().await;
+        assert_eq!(store.total_count().await.unwrap(), 2);
+        let stored = store.get(global.id).await.unwrap().unwrap();
+        assert_eq!(stored.access_count, global.access_count);
+        assert!(stored.conversation_id.is_none());
+    }
 }
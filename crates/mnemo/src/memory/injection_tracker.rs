@@ -2,12 +2,17 @@
 //!
 //! Tracks which memories have been injected into the current session
 //! to prevent duplicate injections. Uses an LRU cache with configurable
-//! capacity for session-scoped deduplication.
+//! capacity for session-scoped deduplication. `SessionInjectionTrackers`
+//! keeps one tracker per session, expiring idle sessions.
 
 use lru::LruCache;
 use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
+use crate::config::InjectionTrackingConfig;
+
 /// Default capacity for the injection tracker cache
 pub const DEFAULT_TRACKER_CAPACITY: usize = 1000;
 
@@ -79,6 +84,92 @@ impl Default for InjectionTracker {
     }
 }
 
+struct SessionEntry {
+    tracker: Arc<Mutex<InjectionTracker>>,
+    last_used: Instant,
+}
+
+/// Injection trackers keyed by session ID
+///
+/// Holds at most `max_sessions` trackers, evicting the least recently used
+/// session when full. A session idle for longer than the TTL starts over with
+/// an empty tracker.
+pub struct SessionInjectionTrackers {
+    config: InjectionTrackingConfig,
+    ttl: Duration,
+    sessions: Mutex<LruCache<String, SessionEntry>>,
+}
+
+impl SessionInjectionTrackers {
+    pub fn new(config: InjectionTrackingConfig) -> Self {
+        let cap = NonZeroUsize::new(config.max_sessions)
+            .unwrap_or_else(|| NonZeroUsize::new(DEFAULT_TRACKER_CAPACITY).unwrap());
+        Self {
+            ttl: Duration::from_secs(config.session_ttl_secs),
+            config,
+            sessions: Mutex::new(LruCache::new(cap)),
+        }
+    }
+
+    /// Whether injection tracking is enabled
+    pub fn enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Score multiplier for memories already injected in a session
+    pub fn penalty_factor(&self) -> f32 {
+        self.config.penalty_factor
+    }
+
+    /// Get the tracker for a session, creating it if needed
+    ///
+    /// Returns `None` when injection tracking is disabled.
+    pub fn tracker_for(&self, session_id: &str) -> Option<Arc<Mutex<InjectionTracker>>> {
+        self.tracker_at(session_id, Instant::now())
+    }
+
+    fn tracker_at(&self, session_id: &str, now: Instant) -> Option<Arc<Mutex<InjectionTracker>>> {
+        if !self.config.enabled {
+            return None;
+        }
+
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+
+        // Drop idle sessions from the cold end of the LRU
+        while let Some((_, entry)) = sessions.peek_lru() {
+            if now.saturating_duration_since(entry.last_used) <= self.ttl {
+                break;
+            }
+            sessions.pop_lru();
+        }
+
+        if let Some(entry) = sessions.get_mut(session_id) {
+            entry.last_used = now;
+            return Some(Arc::clone(&entry.tracker));
+        }
+
+        let tracker = Arc::new(Mutex::new(InjectionTracker::new(self.config.max_entries)));
+        sessions.put(
+            session_id.to_string(),
+            SessionEntry {
+                tracker: Arc::clone(&tracker),
+                last_used: now,
+            },
+        );
+        Some(tracker)
+    }
+
+    /// Number of sessions currently tracked
+    pub fn len(&self) -> usize {
+        self.sessions.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    /// Returns true if no sessions are tracked
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -202,4 +293,69 @@ mod tests {
         let tracker = InjectionTracker::new(0);
         assert_eq!(tracker.capacity(), DEFAULT_TRACKER_CAPACITY);
     }
+
+    fn session_config(max_sessions: usize, session_ttl_secs: u64) -> InjectionTrackingConfig {
+        InjectionTrackingConfig {
+            max_sessions,
+            session_ttl_secs,
+            ..InjectionTrackingConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_session_trackers_are_isolated() {
+        let trackers = SessionInjectionTrackers::new(session_config(10, 3600));
+        let id = Uuid::new_v4();
+
+        trackers.tracker_for("a").unwrap().lock().unwrap().mark_injected(id);
+
+        assert!(trackers.tracker_for("a").unwrap().lock().unwrap().was_injected(&id));
+        assert!(!trackers.tracker_for("b").unwrap().lock().unwrap().was_injected(&id));
+        assert_eq!(trackers.len(), 2);
+    }
+
+    #[test]
+    fn test_session_trackers_evict_least_recently_used() {
+        let trackers = SessionInjectionTrackers::new(session_config(2, 3600));
+        let id = Uuid::new_v4();
+
+        trackers.tracker_for("a").unwrap().lock().unwrap().mark_injected(id);
+        trackers.tracker_for("b");
+        trackers.tracker_for("c");
+
+        assert_eq!(trackers.len(), 2);
+        assert!(!trackers.tracker_for("a").unwrap().lock().unwrap().was_injected(&id));
+    }
+
+    #[test]
+    fn test_session_trackers_expire_after_ttl() {
+        let trackers = SessionInjectionTrackers::new(session_config(10, 60));
+        let id = Uuid::new_v4();
+        let start = Instant::now();
+
+        trackers
+            .tracker_at("a", start)
+            .unwrap()
+            .lock()
+            .unwrap()
+            .mark_injected(id);
+
+        let within_ttl = trackers.tracker_at("a", start + Duration::from_secs(30)).unwrap();
+        assert!(within_ttl.lock().unwrap().was_injected(&id));
+
+        let after_ttl = trackers.tracker_at("a", start + Duration::from_secs(120)).unwrap();
+        assert!(!after_ttl.lock().unwrap().was_injected(&id));
+    }
+
+    #[test]
+    fn test_session_trackers_disabled() {
+        let config = InjectionTrackingConfig {
+            enabled: false,
+            ..InjectionTrackingConfig::default()
+        };
+        let trackers = SessionInjectionTrackers::new(config);
+
+        assert!(trackers.tracker_for("a").is_none());
+        assert!(trackers.is_empty());
+    }
 }
@@ -5,13 +5,15 @@
 
 pub mod ingestion;
 pub mod injection_tracker;
+pub mod query;
 pub mod retrieval;
 pub mod tombstone;
 pub mod types;
 pub mod weight;
 
 pub use ingestion::IngestionPipeline;
-pub use injection_tracker::{InjectionTracker, DEFAULT_TRACKER_CAPACITY};
+pub use injection_tracker::{InjectionTracker, SessionInjectionTrackers, DEFAULT_TRACKER_CAPACITY};
+pub use query::{QueryBuilder, RetrievalQuery, RollingQueryEmbeddings};
 pub use retrieval::{RetrievalConfig, RetrievalPipeline, RetrievedMemory};
 pub use tombstone::{EvictionReason, Tombstone};
 pub use types::{CompressionLevel, Memory, MemorySource, MemoryType, StorageTier};
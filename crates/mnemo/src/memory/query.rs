@@ -0,0 +1,237 @@
+//! Retrieval query construction from conversation context
+//!
+//! Follow-up messages such as "what about the second one?" carry little
+//! meaning on their own. Depending on the configured [`QueryStrategy`], the
+//! retrieval query is built from the last user message, a recency-weighted
+//! blend of recent turns, a per-session rolling embedding, or several recent
+//! turns searched separately and fused.
+
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+
+use crate::config::{QueryConfig, QueryStrategy};
+use crate::embedding::EmbeddingModel;
+use crate::error::Result;
+
+/// Default number of sessions whose rolling query embedding is kept
+pub const DEFAULT_ROLLING_SESSIONS: usize = 1000;
+
+/// What the retrieval pipeline searches with
+#[derive(Debug, Clone, PartialEq)]
+pub enum RetrievalQuery {
+    /// Embed the text and search with it
+    Text(String),
+    /// Search with a pre-computed embedding
+    Embedding(Vec<f32>),
+    /// Search with each weighted embedding and fuse the rankings
+    Fused(Vec<(Vec<f32>, f32)>),
+}
+
+/// Builds retrieval queries according to a [`QueryConfig`]
+pub struct QueryBuilder<'a> {
+    config: &'a QueryConfig,
+    embedding_model: &'a EmbeddingModel,
+    rolling: &'a RollingQueryEmbeddings,
+}
+
+impl<'a> QueryBuilder<'a> {
+    pub fn new(
+        config: &'a QueryConfig,
+        embedding_model: &'a EmbeddingModel,
+        rolling: &'a RollingQueryEmbeddings,
+    ) -> Self {
+        Self {
+            config,
+            embedding_model,
+            rolling,
+        }
+    }
+
+    /// Whether the strategy needs recent conversation turns
+    pub fn needs_turns(&self) -> bool {
+        matches!(
+            self.config.strategy,
+            QueryStrategy::RecentTurns | QueryStrategy::MultiQuery
+        )
+    }
+
+    /// Build the query for a request
+    ///
+    /// `query` is the last user message and `turns` the recent user and
+    /// assistant turns, oldest first. Strategies that need turns fall back to
+    /// `query` when there are none; the rolling strategy falls back to the
+    /// plain query without a session.
+    pub fn build(
+        &self,
+        query: &str,
+        turns: &[String],
+        session_id: Option<&str>,
+    ) -> Result<RetrievalQuery> {
+        let turns: Vec<String> = if turns.is_empty() {
+            vec![query.to_string()]
+        } else {
+            let skip = turns.len().saturating_sub(self.config.turns.max(1));
+            turns[skip..].to_vec()
+        };
+
+        match self.config.strategy {
+            QueryStrategy::LastMessage => Ok(RetrievalQuery::Text(query.to_string())),
+            QueryStrategy::RecentTurns => {
+                let embeddings = self.embedding_model.embed_batch(&turns)?;
+                let weights = recency_weights(embeddings.len(), self.config.recency_decay);
+                Ok(RetrievalQuery::Embedding(weighted_mean(
+                    &embeddings,
+                    &weights,
+                )))
+            }
+            QueryStrategy::Rolling => {
+                let embedding = self.embedding_model.embed(query)?;
+                Ok(RetrievalQuery::Embedding(match session_id {
+                    Some(id) => self.rolling.update(id, embedding),
+                    None => embedding,
+                }))
+            }
+            QueryStrategy::MultiQuery => {
+                let embeddings = self.embedding_model.embed_batch(&turns)?;
+                let weights = recency_weights(embeddings.len(), self.config.recency_decay);
+                Ok(RetrievalQuery::Fused(
+                    embeddings.into_iter().zip(weights).collect(),
+                ))
+            }
+        }
+    }
+}
+
+/// Per-session running embedding of user messages
+///
+/// Each new message embedding is blended in as
+/// `alpha * new + (1 - alpha) * previous`, so earlier topics fade out over a
+/// few turns instead of vanishing at once. Idle sessions are evicted LRU.
+pub struct RollingQueryEmbeddings {
+    alpha: f32,
+    sessions: Mutex<LruCache<String, Vec<f32>>>,
+}
+
+impl RollingQueryEmbeddings {
+    pub fn new(alpha: f32, capacity: usize) -> Self {
+        let cap = NonZeroUsize::new(capacity)
+            .unwrap_or_else(|| NonZeroUsize::new(DEFAULT_ROLLING_SESSIONS).unwrap());
+        Self {
+            alpha: alpha.clamp(0.0, 1.0),
+            sessions: Mutex::new(LruCache::new(cap)),
+        }
+    }
+
+    /// Blend a message embedding into a session's rolling embedding
+    ///
+    /// Returns the updated rolling embedding.
+    pub fn update(&self, session_id: &str, embedding: Vec<f32>) -> Vec<f32> {
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+
+        let rolling = match sessions.get(session_id) {
+            Some(previous) if previous.len() == embedding.len() => weighted_mean(
+                &[previous.clone(), embedding],
+                &[1.0 - self.alpha, self.alpha],
+            ),
+            _ => embedding,
+        };
+
+        sessions.put(session_id.to_string(), rolling.clone());
+        rolling
+    }
+}
+
+impl Default for RollingQueryEmbeddings {
+    fn default() -> Self {
+        Self::new(0.5, DEFAULT_ROLLING_SESSIONS)
+    }
+}
+
+/// Weights for `count` turns, oldest first; the newest turn has weight 1.0
+/// and each older turn is multiplied by `decay`
+pub fn recency_weights(count: usize, decay: f32) -> Vec<f32> {
+    (0..count)
+        .map(|i| decay.powi((count - 1 - i) as i32))
+        .collect()
+}
+
+/// Weighted mean of embeddings, normalized to unit length
+pub fn weighted_mean(embeddings: &[Vec<f32>], weights: &[f32]) -> Vec<f32> {
+    let dimension = embeddings.first().map_or(0, |e| e.len());
+    let mut mean = vec![0.0f32; dimension];
+
+    for (embedding, weight) in embeddings.iter().zip(weights) {
+        for (value, component) in mean.iter_mut().zip(embedding) {
+            *value += component * weight;
+        }
+    }
+
+    let norm = mean.iter().map(|v| v * v).sum::<f32>().sqrt();
+    if norm > 0.0 {
+        for value in &mut mean {
+            *value /= norm;
+        }
+    }
+
+    mean
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recency_weights() {
+        assert_eq!(recency_weights(3, 0.5), vec![0.25, 0.5, 1.0]);
+        assert_eq!(recency_weights(1, 0.5), vec![1.0]);
+        assert!(recency_weights(0, 0.5).is_empty());
+    }
+
+    #[test]
+    fn test_weighted_mean_normalizes() {
+        let mean = weighted_mean(&[vec![1.0, 0.0], vec![0.0, 1.0]], &[1.0, 1.0]);
+
+        let expected = 1.0 / 2.0f32.sqrt();
+        assert!((mean[0] - expected).abs() < 1e-6);
+        assert!((mean[1] - expected).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_weighted_mean_favors_heavier_weight() {
+        let mean = weighted_mean(&[vec![1.0, 0.0], vec![0.0, 1.0]], &[0.25, 1.0]);
+
+        assert!(mean[1] > mean[0]);
+    }
+
+    #[test]
+    fn test_rolling_embedding_first_message_is_unchanged() {
+        let rolling = RollingQueryEmbeddings::new(0.5, 10);
+
+        assert_eq!(rolling.update("session", vec![1.0, 0.0]), vec![1.0, 0.0]);
+    }
+
+    #[test]
+    fn test_rolling_embedding_blends_messages() {
+        let rolling = RollingQueryEmbeddings::new(0.5, 10);
+        rolling.update("session", vec![1.0, 0.0]);
+
+        let blended = rolling.update("session", vec![0.0, 1.0]);
+        assert!((blended[0] - blended[1]).abs() < 1e-6);
+        assert!(blended[0] > 0.0);
+
+        // Other sessions are unaffected
+        assert_eq!(rolling.update("other", vec![0.0, 1.0]), vec![0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_rolling_embedding_resets_on_dimension_change() {
+        let rolling = RollingQueryEmbeddings::new(0.5, 10);
+        rolling.update("session", vec![1.0, 0.0]);
+
+        assert_eq!(
+            rolling.update("session", vec![0.0, 0.0, 1.0]),
+            vec![0.0, 0.0, 1.0]
+        );
+    }
+}
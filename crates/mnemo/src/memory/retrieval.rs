@@ -4,17 +4,20 @@
 //! 1. Vector search for candidate memories based on semantic similarity
 //! 2. Reranking based on effective weight (combining base weight, recency, access patterns)
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 
-use crate::config::DeterministicConfig;
+use uuid::Uuid;
+
+use crate::config::{DeterministicConfig, RouterConfig};
 use crate::embedding::EmbeddingModel;
 use crate::error::Result;
 use crate::memory::injection_tracker::InjectionTracker;
-use crate::memory::types::Memory;
+use crate::memory::types::{CompressionLevel, Memory};
 use crate::memory::weight::{WeightConfig, calculate_effective_weight};
 use crate::storage::LanceStore;
 use crate::storage::filter::MemoryFilter;
+use crate::storage::fusion::fuse_rankings;
 
 /// A retrieved memory with scoring information
 #[derive(Debug, Clone)]
@@ -63,11 +66,19 @@ pub struct RetrievalConfig {
     pub rerank_weight: f32,
     /// Deterministic retrieval settings (optional)
     pub deterministic_config: Option<DeterministicConfig>,
+    /// Minimum similarity score for a candidate to be returned (optional)
+    pub relevance_threshold: Option<f32>,
     /// Enable injection tracking
     pub injection_tracking_enabled: bool,
     /// Penalty factor for already-injected memories (0.0-1.0)
     /// A value of 0.3 means injected memories get 30% of their original score
     pub injection_penalty_factor: f32,
+    /// Rehydrate compressed memories from the archive once they have been
+    /// retrieved this many times (optional)
+    pub rehydrate_after_accesses: Option<u32>,
+    /// Weight of BM25 full-text relevance for hybrid search (0.0-1.0);
+    /// pure vector search when unset
+    pub text_weight: Option<f32>,
 }
 
 impl Default for RetrievalConfig {
@@ -78,21 +89,39 @@ impl Default for RetrievalConfig {
             similarity_weight: 0.7,
             rerank_weight: 0.3,
             deterministic_config: None,
+            relevance_threshold: None,
             injection_tracking_enabled: true,
             injection_penalty_factor: 0.3,
+            rehydrate_after_accesses: None,
+            text_weight: None,
+        }
+    }
+}
+
+impl RetrievalConfig {
+    /// Build a retrieval configuration from the `[router]` config section
+    pub fn from_router_config(router: &RouterConfig) -> Self {
+        Self {
+            deterministic_config: Some(router.deterministic.clone()),
+            relevance_threshold: Some(router.relevance_threshold),
+            rehydrate_after_accesses: router.rehydrate_after_accesses,
+            text_weight: router.hybrid.enabled.then_some(router.hybrid.text_weight),
+            ..Self::default()
         }
     }
 }
 
 /// Two-stage retrieval pipeline with weight-based reranking
 ///
-/// Stage 1: Vector search for semantic similarity (retrieves 3x candidates)
+/// Stage 1: Vector search for semantic similarity (retrieves 3x candidates),
+/// fused with BM25 full-text search when hybrid search is enabled
 /// Stage 2: Rerank by effective weight (combines similarity + weight factors)
 pub struct RetrievalPipeline<'a> {
     store: &'a LanceStore,
     embedding_model: &'a EmbeddingModel,
     config: RetrievalConfig,
     injection_tracker: Option<Arc<Mutex<InjectionTracker>>>,
+    text_query: Option<String>,
 }
 
 impl<'a> RetrievalPipeline<'a> {
@@ -107,6 +136,7 @@ impl<'a> RetrievalPipeline<'a> {
             embedding_model,
             config,
             injection_tracker: None,
+            text_query: None,
         }
     }
 
@@ -115,7 +145,8 @@ impl<'a> RetrievalPipeline<'a> {
         Self::new(store, embedding_model, RetrievalConfig::default())
     }
 
-    /// Create a pipeline with an injection tracker for deduplication
+    /// Create a pipeline that penalizes memories already marked as injected
+    /// in `tracker`
     pub fn with_injection_tracker(
         store: &'a LanceStore,
         embedding_model: &'a EmbeddingModel,
@@ -127,9 +158,19 @@ impl<'a> RetrievalPipeline<'a> {
             embedding_model,
             config,
             injection_tracker: Some(tracker),
+            text_query: None,
         }
     }
 
+    /// Set the text matched by full-text search when retrieving by embedding
+    ///
+    /// Text retrieval uses its query text; embedding-based retrieval only
+    /// takes part in hybrid search when this is set.
+    pub fn with_text_query(mut self, text: impl Into<String>) -> Self {
+        self.text_query = Some(text.into());
+        self
+    }
+
     /// Retrieve memories matching a query text
     ///
     /// Performs two-stage retrieval:
@@ -165,98 +206,11 @@ impl<'a> RetrievalPipeline<'a> {
         }
 
         let query_embedding = self.embedding_model.embed(query)?;
-
-        let candidate_limit = limit * self.config.candidate_multiplier;
-        let candidates = self
-            .store
-            .search_filtered(&query_embedding, filter, candidate_limit)
+        let text = self.text_query.clone().unwrap_or_else(|| query.to_string());
+        let results = self
+            .rank_candidates(&query_embedding, Some(&text), filter, limit, query_entities)
             .await?;
-
-        if candidates.is_empty() {
-            return Ok(Vec::new());
-        }
-
-        let deterministic = self
-            .config
-            .deterministic_config
-            .as_ref()
-            .map(|c| c.enabled)
-            .unwrap_or(false);
-
-        let injection_enabled = self.config.injection_tracking_enabled;
-        let penalty_factor = self.config.injection_penalty_factor;
-
-        let mut results: Vec<RetrievedMemory> = candidates
-            .into_iter()
-            .map(|memory| {
-                let similarity_score = cosine_similarity(&query_embedding, &memory.embedding);
-                let mut retrieved = RetrievedMemory::new(
-                    memory,
-                    similarity_score,
-                    &self.config.weight_config,
-                    self.config.similarity_weight,
-                    self.config.rerank_weight,
-                );
-
-                if let (true, Some(det_config), Some(q_entities)) = (
-                    deterministic,
-                    &self.config.deterministic_config,
-                    query_entities,
-                ) {
-                    let topic_score = topic_overlap_score(q_entities, &retrieved.memory.entities);
-                    let topic_boost = topic_score * det_config.topic_overlap_weight;
-                    retrieved.final_score = quantize_score(
-                        retrieved.final_score + topic_boost,
-                        det_config.decimal_places,
-                    );
-                }
-
-                // Apply injection penalty if tracking is enabled and memory was injected
-                if injection_enabled {
-                    if let Some(ref tracker) = self.injection_tracker {
-                        if let Ok(mut guard) = tracker.lock() {
-                            if guard.was_injected(&retrieved.memory.id) {
-                                retrieved.final_score *= penalty_factor;
-                            }
-                        }
-                    }
-                }
-
-                retrieved
-            })
-            .collect();
-
-        if deterministic {
-            results.sort_by(|a, b| {
-                b.final_score
-                    .total_cmp(&a.final_score)
-                    .then_with(|| a.memory.created_at.cmp(&b.memory.created_at))
-                    .then_with(|| a.memory.id.cmp(&b.memory.id))
-            });
-        } else {
-            results.sort_by(|a, b| {
-                b.final_score
-                    .partial_cmp(&a.final_score)
-                    .unwrap_or(std::cmp::Ordering::Equal)
-            });
-        }
-
-        results.truncate(limit);
-
-        for result in &results {
-            self.store.update_access(result.memory.id).await?;
-        }
-
-        // Mark retrieved memories as injected if tracking is enabled
-        if injection_enabled {
-            if let Some(ref tracker) = self.injection_tracker {
-                if let Ok(mut guard) = tracker.lock() {
-                    for result in &results {
-                        guard.mark_injected(result.memory.id);
-                    }
-                }
-            }
-        }
+        self.record_retrieval(&results).await?;
 
         Ok(results)
     }
@@ -294,11 +248,86 @@ impl<'a> RetrievalPipeline<'a> {
             return Ok(Vec::new());
         }
 
-        let candidate_limit = limit * self.config.candidate_multiplier;
-        let candidates = self
-            .store
-            .search_filtered(embedding, filter, candidate_limit)
+        let text = self.text_query.clone();
+        let results = self
+            .rank_candidates(embedding, text.as_deref(), filter, limit, query_entities)
             .await?;
+        self.record_retrieval(&results).await?;
+
+        Ok(results)
+    }
+
+    /// Retrieve memories for several weighted query embeddings and fuse the rankings
+    ///
+    /// Each embedding is searched separately, then memories are ordered by
+    /// weighted reciprocal rank fusion, so memories ranked well by several
+    /// queries, or by heavily weighted ones, come first.
+    pub async fn retrieve_fused(
+        &mut self,
+        queries: &[(Vec<f32>, f32)],
+        filter: &MemoryFilter,
+        limit: usize,
+        query_entities: Option<&[String]>,
+    ) -> Result<Vec<RetrievedMemory>> {
+        if limit == 0 || queries.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let text = self.text_query.clone();
+        let mut rankings = Vec::with_capacity(queries.len());
+        for (embedding, weight) in queries {
+            let ranking = self
+                .rank_candidates(embedding, text.as_deref(), filter, limit, query_entities)
+                .await?;
+            rankings.push((ranking, *weight));
+        }
+
+        let results = fuse_retrieved(rankings, limit);
+        self.record_retrieval(&results).await?;
+
+        Ok(results)
+    }
+
+    /// Search and score candidates for an embedding, best first
+    ///
+    /// With hybrid search enabled and `text` given, candidates come from
+    /// fused vector and full-text search, and each memory's normalized BM25
+    /// score is blended into its final score.
+    ///
+    /// Has no side effects; callers record the results they keep with
+    /// [`record_retrieval`](Self::record_retrieval).
+    async fn rank_candidates(
+        &self,
+        embedding: &[f32],
+        text: Option<&str>,
+        filter: &MemoryFilter,
+        limit: usize,
+        query_entities: Option<&[String]>,
+    ) -> Result<Vec<RetrievedMemory>> {
+        let candidate_limit = limit * self.config.candidate_multiplier;
+        let text_weight = self.config.text_weight.unwrap_or(0.0).clamp(0.0, 1.0);
+
+        let mut text_scores: HashMap<Uuid, f32> = HashMap::new();
+        let candidates = match text {
+            Some(text) if text_weight > 0.0 => {
+                let matches = self
+                    .store
+                    .search_hybrid(embedding, text, filter, candidate_limit, text_weight)
+                    .await?;
+                matches
+                    .into_iter()
+                    .map(|(memory, text_score)| {
+                        text_scores.insert(memory.id, text_score);
+                        memory
+                    })
+                    .collect()
+            }
+            _ => {
+                self.store
+                    .search_filtered(embedding, filter, candidate_limit)
+                    .await?
+            }
+        };
 
         if candidates.is_empty() {
             return Ok(Vec::new());
@@ -326,6 +355,11 @@ impl<'a> RetrievalPipeline<'a> {
                     self.config.rerank_weight,
                 );
 
+                if let Some(text_score) = text_scores.get(&retrieved.memory.id) {
+                    retrieved.final_score =
+                        retrieved.final_score * (1.0 - text_weight) + text_score * text_weight;
+                }
+
                 if let (true, Some(det_config), Some(q_entities)) = (
                     deterministic,
                     &self.config.deterministic_config,
@@ -354,6 +388,21 @@ impl<'a> RetrievalPipeline<'a> {
             })
             .collect();
 
+        // BM25 scores are normalized against the best text match, so the top
+        // hit always scores 1.0; the threshold applies to its blend with
+        // similarity, and a text match can only raise a memory's relevance
+        if let Some(threshold) = self.config.relevance_threshold {
+            results.retain(|r| {
+                let relevance = match text_scores.get(&r.memory.id) {
+                    Some(text_score) => r
+                        .similarity_score
+                        .max(r.similarity_score * (1.0 - text_weight) + text_score * text_weight),
+                    None => r.similarity_score,
+                };
+                relevance >= threshold
+            });
+        }
+
         if deterministic {
             results.sort_by(|a, b| {
                 b.final_score
@@ -371,26 +420,77 @@ impl<'a> RetrievalPipeline<'a> {
 
         results.truncate(limit);
 
-        for result in &results {
+        Ok(results)
+    }
+
+    /// Update access stats for returned memories
+    ///
+    /// Memories are not marked as injected here: callers mark only the
+    /// memories that actually fit into the injected block.
+    async fn record_retrieval(&mut self, results: &[RetrievedMemory]) -> Result<()> {
+        for result in results {
             self.store.update_access(result.memory.id).await?;
+            self.maybe_rehydrate(&result.memory).await;
         }
 
-        // Mark retrieved memories as injected if tracking is enabled
-        if injection_enabled {
-            if let Some(ref tracker) = self.injection_tracker {
-                if let Ok(mut guard) = tracker.lock() {
-                    for result in &results {
-                        guard.mark_injected(result.memory.id);
-                    }
-                }
-            }
+        Ok(())
+    }
+
+    /// Restore a frequently retrieved compressed memory to full content
+    ///
+    /// `memory` holds the access count from before this retrieval. The
+    /// restored content is used from the next retrieval on.
+    async fn maybe_rehydrate(&self, memory: &Memory) {
+        let Some(threshold) = self.config.rehydrate_after_accesses else {
+            return;
+        };
+
+        if memory.compression == CompressionLevel::Full
+            || memory.access_count + 1 < threshold
+            || self.store.archive_table().is_none()
+        {
+            return;
         }
 
-        Ok(results)
+        match self.store.rehydrate(memory.id).await {
+            Ok(true) => tracing::debug!("Rehydrated memory {}", memory.id),
+            Ok(false) => {}
+            Err(e) => tracing::warn!("Failed to rehydrate memory {}: {e}", memory.id),
+        }
     }
 }
 
-fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+/// Fuse weighted rankings of retrieved memories, best first
+///
+/// Each memory keeps the scores from its best-scoring ranking.
+fn fuse_retrieved(
+    rankings: Vec<(Vec<RetrievedMemory>, f32)>,
+    limit: usize,
+) -> Vec<RetrievedMemory> {
+    let mut results = fuse_rankings(
+        rankings,
+        |retrieved| retrieved.memory.id,
+        |best, retrieved| {
+            if retrieved.final_score > best.final_score {
+                *best = retrieved;
+            }
+        },
+    );
+    results.sort_by(|(a, a_score), (b, b_score)| {
+        b_score
+            .total_cmp(a_score)
+            .then_with(|| b.final_score.total_cmp(&a.final_score))
+            .then_with(|| a.memory.id.cmp(&b.memory.id))
+    });
+    results.truncate(limit);
+
+    results
+        .into_iter()
+        .map(|(retrieved, _)| retrieved)
+        .collect()
+}
+
+pub(crate) fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
     if a.len() != b.len() || a.is_empty() {
         return 0.0;
     }
@@ -530,6 +630,83 @@ mod tests {
         assert_eq!(config.candidate_multiplier, 3);
         assert_eq!(config.similarity_weight, 0.7);
         assert_eq!(config.rerank_weight, 0.3);
+        assert!(config.relevance_threshold.is_none());
+    }
+
+    #[test]
+    fn test_retrieval_config_from_router_config() {
+        let router = RouterConfig {
+            relevance_threshold: 0.8,
+            deterministic: DeterministicConfig {
+                enabled: true,
+                decimal_places: 3,
+                ..DeterministicConfig::default()
+            },
+            ..RouterConfig::default()
+        };
+
+        let config = RetrievalConfig::from_router_config(&router);
+
+        assert_eq!(config.relevance_threshold, Some(0.8));
+        let deterministic = config.deterministic_config.expect("deterministic config");
+        assert!(deterministic.enabled);
+        assert_eq!(deterministic.decimal_places, 3);
+        assert_eq!(config.candidate_multiplier, 3);
+        assert!(config.text_weight.is_none());
+    }
+
+    #[test]
+    fn test_retrieval_config_hybrid_text_weight() {
+        let mut router = RouterConfig::default();
+        router.hybrid.enabled = true;
+        router.hybrid.text_weight = 0.4;
+
+        let config = RetrievalConfig::from_router_config(&router);
+
+        assert_eq!(config.text_weight, Some(0.4));
+    }
+
+    fn create_retrieved(content: &str, final_score: f32) -> RetrievedMemory {
+        RetrievedMemory {
+            memory: create_test_memory(content, 0.5, 0),
+            similarity_score: final_score,
+            effective_weight: 0.5,
+            final_score,
+        }
+    }
+
+    #[test]
+    fn test_fuse_retrieved_rewards_agreement() {
+        let shared = create_retrieved("Shared", 0.6);
+        let only_first = create_retrieved("Only first", 0.9);
+        let only_second = create_retrieved("Only second", 0.9);
+
+        let mut shared_again = shared.clone();
+        shared_again.final_score = 0.7;
+
+        let fused = fuse_retrieved(
+            vec![
+                (vec![only_first.clone(), shared.clone()], 1.0),
+                (vec![only_second.clone(), shared_again], 1.0),
+            ],
+            10,
+        );
+
+        assert_eq!(fused.len(), 3);
+        assert_eq!(fused[0].memory.id, shared.memory.id);
+        // The best-scoring copy of a memory is kept
+        assert!((fused[0].final_score - 0.7).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_fuse_retrieved_respects_weights_and_limit() {
+        let older = create_retrieved("From an older turn", 0.9);
+        let newer = create_retrieved("From the newest turn", 0.9);
+
+        let fused = fuse_retrieved(vec![(vec![older], 0.25), (vec![newer.clone()], 1.0)], 1);
+
+        assert_eq!(fused.len(), 1);
+        assert_eq!(fused[0].memory.id, newer.memory.id);
     }
 
     #[test]
@@ -604,8 +781,8 @@ mod tests {
             store.insert(&high_weight).await.unwrap();
             store.insert(&medium_weight).await.unwrap();
 
-            let mut embedding_model = EmbeddingModel::new().unwrap();
-            let mut pipeline = RetrievalPipeline::with_defaults(&store, &mut embedding_model);
+            let embedding_model = EmbeddingModel::new().unwrap();
+            let mut pipeline = RetrievalPipeline::with_defaults(&store, &embedding_model);
 
             let results = pipeline
                 .retrieve_by_embedding(&base_embedding, 10)
@@ -643,8 +820,8 @@ mod tests {
 
             store.insert(&memory).await.unwrap();
 
-            let mut embedding_model = EmbeddingModel::new().unwrap();
-            let mut pipeline = RetrievalPipeline::with_defaults(&store, &mut embedding_model);
+            let embedding_model = EmbeddingModel::new().unwrap();
+            let mut pipeline = RetrievalPipeline::with_defaults(&store, &embedding_model);
 
             let results = pipeline
                 .retrieve_by_embedding(&base_embedding, 10)
@@ -660,6 +837,96 @@ mod tests {
             );
         }
 
+        #[tokio::test]
+        async fn test_hybrid_retrieval_keeps_exact_identifier_matches() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+            store.create_memories_table().await.unwrap();
+
+            // Cosine similarity 0.5 to the query: below the threshold alone,
+            // above it once blended with the best text match
+            let base_embedding: Vec<f32> = vec![0.5; 384];
+            let keyword = create_memory_with_embedding(
+                "Ticket INC-20931 tracks the login outage",
+                (0..384).map(|i| if i < 96 { 1.0 } else { 0.0 }).collect(),
+            );
+            // A text match cannot carry a dissimilar memory over the threshold
+            let dissimilar = create_memory_with_embedding(
+                "Someone mentioned INC-20931 once in a long thread about lunch plans and desks",
+                vec![-0.5; 384],
+            );
+            store
+                .insert_batch(&[keyword.clone(), dissimilar])
+                .await
+                .unwrap();
+            store.create_fts_index().await.unwrap();
+
+            let embedding_model = EmbeddingModel::new().unwrap();
+
+            let vector_only = RetrievalConfig {
+                relevance_threshold: Some(0.7),
+                ..RetrievalConfig::default()
+            };
+            let mut pipeline = RetrievalPipeline::new(&store, &embedding_model, vector_only)
+                .with_text_query("INC-20931");
+            let results = pipeline
+                .retrieve_by_embedding(&base_embedding, 10)
+                .await
+                .unwrap();
+            assert!(results.is_empty());
+
+            let hybrid = RetrievalConfig {
+                relevance_threshold: Some(0.7),
+                text_weight: Some(0.5),
+                ..RetrievalConfig::default()
+            };
+            let mut pipeline = RetrievalPipeline::new(&store, &embedding_model, hybrid)
+                .with_text_query("INC-20931");
+            let results = pipeline
+                .retrieve_by_embedding(&base_embedding, 10)
+                .await
+                .unwrap();
+            assert_eq!(results.len(), 1);
+            assert_eq!(results[0].memory.id, keyword.id);
+        }
+
+        #[tokio::test]
+        async fn test_retrieval_rehydrates_frequently_accessed_memory() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+            store.create_memories_table().await.unwrap();
+            store.create_archive_table().await.unwrap();
+
+            let base_embedding: Vec<f32> = vec![0.5; 384];
+            let mut memory =
+                create_memory_with_embedding("Original detailed content.", base_embedding.clone());
+            memory.access_count = 2;
+            let id = memory.id;
+
+            store.insert(&memory).await.unwrap();
+            store.archive_content(id, &memory.content).await.unwrap();
+            store
+                .update_compression(id, "detailed content", CompressionLevel::Keywords)
+                .await
+                .unwrap();
+
+            let embedding_model = EmbeddingModel::new().unwrap();
+            let config = RetrievalConfig {
+                rehydrate_after_accesses: Some(3),
+                ..RetrievalConfig::default()
+            };
+            let mut pipeline = RetrievalPipeline::new(&store, &embedding_model, config);
+
+            pipeline
+                .retrieve_by_embedding(&base_embedding, 10)
+                .await
+                .unwrap();
+
+            let updated = store.get(id).await.unwrap().unwrap();
+            assert_eq!(updated.compression, CompressionLevel::Full);
+            assert_eq!(updated.content, "Original detailed content.");
+        }
+
         #[tokio::test]
         async fn test_retrieval_respects_limit() {
             let temp_dir = tempfile::tempdir().unwrap();
@@ -674,8 +941,8 @@ mod tests {
                 store.insert(&memory).await.unwrap();
             }
 
-            let mut embedding_model = EmbeddingModel::new().unwrap();
-            let mut pipeline = RetrievalPipeline::with_defaults(&store, &mut embedding_model);
+            let embedding_model = EmbeddingModel::new().unwrap();
+            let mut pipeline = RetrievalPipeline::with_defaults(&store, &embedding_model);
 
             let results = pipeline
                 .retrieve_by_embedding(&base_embedding, 3)
@@ -688,14 +955,56 @@ mod tests {
             );
         }
 
+        #[tokio::test]
+        async fn test_retrieval_drops_candidates_below_threshold() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+            store.create_memories_table().await.unwrap();
+
+            let mut relevant_embedding = vec![0.0; 384];
+            relevant_embedding[0] = 1.0;
+            let mut unrelated_embedding = vec![0.0; 384];
+            unrelated_embedding[1] = 1.0;
+
+            store
+                .insert(&create_memory_with_embedding(
+                    "Relevant",
+                    relevant_embedding.clone(),
+                ))
+                .await
+                .unwrap();
+            store
+                .insert(&create_memory_with_embedding(
+                    "Unrelated",
+                    unrelated_embedding,
+                ))
+                .await
+                .unwrap();
+
+            let embedding_model = EmbeddingModel::new().unwrap();
+            let config = RetrievalConfig {
+                relevance_threshold: Some(0.5),
+                ..RetrievalConfig::default()
+            };
+            let mut pipeline = RetrievalPipeline::new(&store, &embedding_model, config);
+
+            let results = pipeline
+                .retrieve_by_embedding(&relevant_embedding, 10)
+                .await
+                .unwrap();
+
+            assert_eq!(results.len(), 1);
+            assert_eq!(results[0].memory.content, "Relevant");
+        }
+
         #[tokio::test]
         async fn test_retrieval_empty_results() {
             let temp_dir = tempfile::tempdir().unwrap();
             let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
             store.create_memories_table().await.unwrap();
 
-            let mut embedding_model = EmbeddingModel::new().unwrap();
-            let mut pipeline = RetrievalPipeline::with_defaults(&store, &mut embedding_model);
+            let embedding_model = EmbeddingModel::new().unwrap();
+            let mut pipeline = RetrievalPipeline::with_defaults(&store, &embedding_model);
 
             let results = pipeline.retrieve("test query", 10).await.unwrap();
             assert!(
@@ -714,8 +1023,8 @@ mod tests {
             let memory = create_memory_with_embedding("Test", base_embedding.clone());
             store.insert(&memory).await.unwrap();
 
-            let mut embedding_model = EmbeddingModel::new().unwrap();
-            let mut pipeline = RetrievalPipeline::with_defaults(&store, &mut embedding_model);
+            let embedding_model = EmbeddingModel::new().unwrap();
+            let mut pipeline = RetrievalPipeline::with_defaults(&store, &embedding_model);
 
             let results = pipeline
                 .retrieve_by_embedding(&base_embedding, 0)
@@ -743,8 +1052,8 @@ mod tests {
             store.insert(&low_weight_memory).await.unwrap();
             store.insert(&high_weight_memory).await.unwrap();
 
-            let mut embedding_model = EmbeddingModel::new().unwrap();
-            let mut pipeline = RetrievalPipeline::with_defaults(&store, &mut embedding_model);
+            let embedding_model = EmbeddingModel::new().unwrap();
+            let mut pipeline = RetrievalPipeline::with_defaults(&store, &embedding_model);
 
             let results = pipeline
                 .retrieve_by_embedding(&embedding, 10)
@@ -781,24 +1090,29 @@ mod tests {
             store.create_memories_table().await.unwrap();
 
             let base_embedding: Vec<f32> = vec![0.5; 384];
-            let memory = create_memory_with_embedding("Test memory for injection tracking", base_embedding.clone());
+            let memory = create_memory_with_embedding(
+                "Test memory for injection tracking",
+                base_embedding.clone(),
+            );
             let id = memory.id;
 
             store.insert(&memory).await.unwrap();
 
-            let mut embedding_model = EmbeddingModel::new().unwrap();
+            let embedding_model = EmbeddingModel::new().unwrap();
             let tracker = Arc::new(Mutex::new(InjectionTracker::new(100)));
 
-            let mut config = RetrievalConfig::default();
-            config.injection_tracking_enabled = true;
-            config.injection_penalty_factor = 0.3;
-            // Set rerank weight to 0 so weight changes don't affect the score
-            config.rerank_weight = 0.0;
-            config.similarity_weight = 1.0;
+            let config = RetrievalConfig {
+                injection_tracking_enabled: true,
+                injection_penalty_factor: 0.3,
+                // Set rerank weight to 0 so weight changes don't affect the score
+                rerank_weight: 0.0,
+                similarity_weight: 1.0,
+                ..Default::default()
+            };
 
             let mut pipeline = RetrievalPipeline::with_injection_tracker(
                 &store,
-                &mut embedding_model,
+                &embedding_model,
                 config,
                 tracker.clone(),
             );
@@ -811,6 +1125,11 @@ mod tests {
             assert_eq!(results1.len(), 1);
             let first_score = results1[0].final_score;
 
+            // Retrieval alone does not mark memories; the proxy marks only
+            // the memories that fit into the injected block
+            assert!(!tracker.lock().unwrap().was_injected(&id));
+            tracker.lock().unwrap().mark_injected(id);
+
             // Second retrieval - should get penalized score
             let results2 = pipeline
                 .retrieve_by_embedding(&base_embedding, 10)
@@ -828,10 +1147,6 @@ mod tests {
                 first_score,
                 second_score
             );
-
-            // Verify the memory was marked as injected
-            let mut guard = tracker.lock().unwrap();
-            assert!(guard.was_injected(&id), "Memory should be marked as injected");
         }
 
         #[tokio::test]
@@ -849,19 +1164,21 @@ mod tests {
 
             store.insert(&memory).await.unwrap();
 
-            let mut embedding_model = EmbeddingModel::new().unwrap();
+            let embedding_model = EmbeddingModel::new().unwrap();
             let tracker = Arc::new(Mutex::new(InjectionTracker::new(100)));
 
-            let mut config = RetrievalConfig::default();
-            config.injection_tracking_enabled = false; // Disabled
-            config.injection_penalty_factor = 0.3;
-            // Set rerank weight to 0 so weight changes don't affect the score
-            config.rerank_weight = 0.0;
-            config.similarity_weight = 1.0;
+            let config = RetrievalConfig {
+                injection_tracking_enabled: false, // Disabled
+                injection_penalty_factor: 0.3,
+                // Set rerank weight to 0 so weight changes don't affect the score
+                rerank_weight: 0.0,
+                similarity_weight: 1.0,
+                ..Default::default()
+            };
 
             let mut pipeline = RetrievalPipeline::with_injection_tracker(
                 &store,
-                &mut embedding_model,
+                &embedding_model,
                 config,
                 tracker.clone(),
             );
@@ -873,6 +1190,7 @@ mod tests {
                 .unwrap();
             assert_eq!(results1.len(), 1);
             let first_score = results1[0].final_score;
+            tracker.lock().unwrap().mark_injected(id);
 
             // Second retrieval - should NOT be penalized since tracking is disabled
             let results2 = pipeline
@@ -889,10 +1207,6 @@ mod tests {
                 first_score,
                 second_score
             );
-
-            // Verify the memory was NOT marked as injected (tracking is disabled)
-            let mut guard = tracker.lock().unwrap();
-            assert!(!guard.was_injected(&id), "Memory should NOT be marked as injected when tracking is disabled");
         }
     }
 }
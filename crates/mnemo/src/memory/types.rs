@@ -5,6 +5,7 @@
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use uuid::Uuid;
 
 /// A single memory unit stored in the Mnemo system.
@@ -36,6 +37,16 @@ pub struct Memory {
     pub tier: StorageTier,
     /// Level of content compression
     pub compression: CompressionLevel,
+    /// Free-form tags such as project, repo, author, language or client app
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Arbitrary key/value metadata
+    #[serde(default)]
+    pub metadata: Map<String, Value>,
+    /// Whether the full content was restored from the archive after
+    /// compression; compaction leaves rehydrated memories alone
+    #[serde(default)]
+    pub rehydrated: bool,
 }
 
 impl Memory {
@@ -61,6 +72,9 @@ impl Memory {
             source,
             tier: StorageTier::Hot,
             compression: CompressionLevel::Full,
+            tags: Vec::new(),
+            metadata: Map::new(),
+            rehydrated: false,
         }
     }
 
@@ -74,6 +88,42 @@ impl Memory {
     pub fn set_weight(&mut self, weight: f32) {
         self.weight = weight.clamp(0.0, 1.0);
     }
+
+    /// Merge a near-duplicate memory into this one
+    ///
+    /// Keeps the higher of the two weights plus `weight_boost`, unions the
+    /// entities and tags, adds metadata keys this memory lacks and counts the
+    /// duplicate as an access.
+    pub fn merge_duplicate(&mut self, duplicate: &Memory, weight_boost: f32) {
+        self.set_weight(self.weight.max(duplicate.weight) + weight_boost);
+        for entity in &duplicate.entities {
+            if !self.entities.contains(entity) {
+                self.entities.push(entity.clone());
+            }
+        }
+        self.add_tags(duplicate.tags.iter().cloned());
+        for (key, value) in &duplicate.metadata {
+            self.metadata
+                .entry(key.clone())
+                .or_insert_with(|| value.clone());
+        }
+        self.mark_accessed();
+    }
+
+    /// Add tags, skipping duplicates and tags that are empty or contain commas
+    ///
+    /// Tags are trimmed of surrounding whitespace.
+    pub fn add_tags(&mut self, tags: impl IntoIterator<Item = String>) {
+        for tag in tags {
+            let tag = tag.trim();
+            if tag.is_empty() || tag.contains(',') {
+                continue;
+            }
+            if !self.tags.iter().any(|t| t == tag) {
+                self.tags.push(tag.to_string());
+            }
+        }
+    }
 }
 
 /// Classification of memory types based on cognitive psychology
@@ -163,6 +213,8 @@ mod tests {
         assert!(memory.entities.is_empty());
         assert_eq!(memory.tier, StorageTier::Hot);
         assert_eq!(memory.compression, CompressionLevel::Full);
+        assert!(memory.tags.is_empty());
+        assert!(memory.metadata.is_empty());
     }
 
     #[test]
@@ -181,6 +233,102 @@ mod tests {
         assert!(memory.last_accessed >= before_access);
     }
 
+    #[test]
+    fn test_memory_merge_duplicate() {
+        let mut memory = Memory::new(
+            "User prefers dark mode".to_string(),
+            vec![0.1; 10],
+            MemoryType::Semantic,
+            MemorySource::Conversation,
+        );
+        memory.weight = 0.5;
+        memory.entities = vec!["dark mode".to_string()];
+
+        let mut duplicate = memory.clone();
+        duplicate.weight = 0.6;
+        duplicate.entities = vec!["dark mode".to_string(), "UI".to_string()];
+
+        memory.merge_duplicate(&duplicate, 0.1);
+
+        assert!((memory.weight - 0.7).abs() < 1e-6);
+        assert_eq!(
+            memory.entities,
+            vec!["dark mode".to_string(), "UI".to_string()]
+        );
+        assert_eq!(memory.access_count, 1);
+
+        memory.weight = 0.95;
+        memory.merge_duplicate(&duplicate, 0.1);
+        assert_eq!(memory.weight, 1.0);
+    }
+
+    #[test]
+    fn test_memory_add_tags() {
+        let mut memory = Memory::new(
+            "Test".to_string(),
+            vec![0.1; 10],
+            MemoryType::Semantic,
+            MemorySource::Manual,
+        );
+
+        memory.add_tags(vec![
+            " rust ".to_string(),
+            "rust".to_string(),
+            "".to_string(),
+            "a,b".to_string(),
+            "mnemo".to_string(),
+        ]);
+
+        assert_eq!(memory.tags, vec!["rust".to_string(), "mnemo".to_string()]);
+    }
+
+    #[test]
+    fn test_memory_merge_duplicate_labels() {
+        let mut memory = Memory::new(
+            "Test".to_string(),
+            vec![0.1; 10],
+            MemoryType::Semantic,
+            MemorySource::Manual,
+        );
+        memory.tags = vec!["rust".to_string()];
+        memory
+            .metadata
+            .insert("repo".to_string(), Value::from("mnemo"));
+
+        let mut duplicate = memory.clone();
+        duplicate.tags = vec!["rust".to_string(), "cli".to_string()];
+        duplicate
+            .metadata
+            .insert("repo".to_string(), Value::from("other"));
+        duplicate
+            .metadata
+            .insert("author".to_string(), Value::from("alice"));
+
+        memory.merge_duplicate(&duplicate, 0.0);
+
+        assert_eq!(memory.tags, vec!["rust".to_string(), "cli".to_string()]);
+        assert_eq!(memory.metadata["repo"], Value::from("mnemo"));
+        assert_eq!(memory.metadata["author"], Value::from("alice"));
+    }
+
+    #[test]
+    fn test_memory_deserialize_without_labels() {
+        let memory = Memory::new(
+            "Test".to_string(),
+            vec![0.1; 4],
+            MemoryType::Semantic,
+            MemorySource::Manual,
+        );
+        let mut json = serde_json::to_value(&memory).expect("Failed to serialize");
+        let object = json.as_object_mut().unwrap();
+        object.remove("tags");
+        object.remove("metadata");
+
+        let deserialized: Memory = serde_json::from_value(json).expect("Failed to deserialize");
+        assert!(deserialized.tags.is_empty());
+        assert!(deserialized.metadata.is_empty());
+    }
+
     #[test]
     fn test_memory_set_weight() {
         let mut memory = Memory::new(
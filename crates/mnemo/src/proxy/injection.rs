@@ -2,11 +2,49 @@
 //!
 //! This module handles injecting retrieved memories into LLM request payloads.
 //! It formats memories as XML blocks and injects them into the system message.
+//! Tombstones for evicted memories that match the query are injected alongside
+//! so the model knows it once had knowledge about those topics.
 
 use crate::error::{MnemoError, Result};
 use crate::memory::retrieval::RetrievedMemory;
+use crate::memory::tombstone::Tombstone;
+use crate::proxy::content::{content_text, content_with_placeholders};
 use serde_json::Value;
 
+/// Default fraction of the injection budget reserved for tombstones
+pub const DEFAULT_TOMBSTONE_SHARE: f32 = 0.2;
+
+/// Token budget for injected context.
+///
+/// When tombstones are present, up to `tombstone_share` of `max_tokens` is
+/// reserved for them. Whatever the tombstones do not use goes to memories.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct InjectionBudget {
+    /// Maximum tokens for all injected content
+    pub max_tokens: usize,
+    /// Fraction of `max_tokens` available to tombstones (0.0 - 1.0)
+    pub tombstone_share: f32,
+}
+
+impl InjectionBudget {
+    pub fn new(max_tokens: usize) -> Self {
+        Self {
+            max_tokens,
+            tombstone_share: DEFAULT_TOMBSTONE_SHARE,
+        }
+    }
+
+    pub fn with_tombstone_share(mut self, share: f32) -> Self {
+        self.tombstone_share = share;
+        self
+    }
+
+    /// Tokens available to tombstones
+    pub fn tombstone_tokens(&self) -> usize {
+        (self.max_tokens as f32 * self.tombstone_share.clamp(0.0, 1.0)) as usize
+    }
+}
+
 /// Format memories as an XML block for injection into system prompts.
 ///
 /// Each memory is tagged with its timestamp and type for context.
@@ -37,6 +75,17 @@ pub fn format_memory_block(memories: &[RetrievedMemory]) -> String {
     block
 }
 
+/// Format tombstones as `<mnemo-tombstone>` blocks for injection into system prompts.
+///
+/// See [`Tombstone::to_xml`] for the format of each block.
+pub fn format_tombstone_block(tombstones: &[Tombstone]) -> String {
+    tombstones
+        .iter()
+        .map(Tombstone::to_xml)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 /// Estimate token count using character approximation.
 ///
 /// Uses chars/4 as a rough approximation for token count.
@@ -82,37 +131,144 @@ pub fn truncate_to_budget(memories: &[RetrievedMemory], max_tokens: usize) -> Ve
     result
 }
 
+/// Truncate tombstones to fit within token budget.
+///
+/// Keeps tombstones in order until the budget is exhausted.
+pub fn truncate_tombstones_to_budget(
+    tombstones: &[Tombstone],
+    max_tokens: usize,
+) -> Vec<Tombstone> {
+    let mut used_tokens = 0;
+    let mut result = Vec::new();
+
+    for tombstone in tombstones {
+        let tokens = estimate_tokens(&tombstone.to_xml());
+        if used_tokens + tokens > max_tokens {
+            break;
+        }
+        result.push(tombstone.clone());
+        used_tokens += tokens;
+    }
+
+    result
+}
+
+/// The memories and tombstones that fit an injection budget, and the block built from them
+#[derive(Debug, Clone, Default)]
+pub struct InjectionPlan {
+    /// Memories that fit the budget, in relevance order
+    pub memories: Vec<RetrievedMemory>,
+    /// Tombstones that fit their share of the budget
+    pub tombstones: Vec<Tombstone>,
+    /// The formatted block to inject (empty if nothing fits)
+    pub block: String,
+}
+
+impl InjectionPlan {
+    /// Estimated token cost of the injected block
+    pub fn tokens(&self) -> usize {
+        estimate_tokens(&self.block)
+    }
+}
+
+/// Decide which memories and tombstones fit the budget.
+///
+/// Tombstones are fitted into their share of the budget first; memories then
+/// use the remaining tokens.
+pub fn plan_injection(
+    memories: &[RetrievedMemory],
+    tombstones: &[Tombstone],
+    budget: InjectionBudget,
+) -> InjectionPlan {
+    let tombstones = truncate_tombstones_to_budget(tombstones, budget.tombstone_tokens());
+    let tombstone_block = format_tombstone_block(&tombstones);
+
+    let memory_tokens = budget
+        .max_tokens
+        .saturating_sub(estimate_tokens(&tombstone_block));
+    let memories = truncate_to_budget(memories, memory_tokens);
+    let memory_block = format_memory_block(&memories);
+
+    let block = match (memory_block.is_empty(), tombstone_block.is_empty()) {
+        (true, _) => tombstone_block,
+        (false, true) => memory_block,
+        (false, false) => format!("{memory_block}\n{tombstone_block}"),
+    };
+
+    InjectionPlan {
+        memories,
+        tombstones,
+        block,
+    }
+}
+
+/// Build the full injection block for memories and tombstones.
+///
+/// See [`plan_injection`] for how the budget is split. Returns an empty
+/// string if nothing fits.
+pub fn build_injection_block(
+    memories: &[RetrievedMemory],
+    tombstones: &[Tombstone],
+    budget: InjectionBudget,
+) -> String {
+    plan_injection(memories, tombstones, budget).block
+}
+
 /// Inject memories into an OpenAI-format request body.
 ///
+/// Equivalent to [`inject_context`] with no tombstones.
+pub fn inject_memories(
+    request_body: &mut Value,
+    memories: &[RetrievedMemory],
+    max_tokens: usize,
+) -> Result<()> {
+    inject_context(
+        request_body,
+        memories,
+        &[],
+        InjectionBudget::new(max_tokens),
+    )
+}
+
+/// Inject memories and tombstones into an OpenAI-format request body.
+///
 /// This function:
 /// 1. Parses the messages array from the request
 /// 2. Finds or creates a system message
-/// 3. Appends the memory block to the system message
+/// 3. Appends the memory and tombstone blocks to the system message
 /// 4. Respects the token budget by truncating if necessary
 ///
 /// # Arguments
 /// * `request_body` - Mutable reference to the JSON request body
 /// * `memories` - Slice of retrieved memories (should be sorted by relevance)
-/// * `max_tokens` - Maximum tokens to use for injected memories
+/// * `tombstones` - Tombstones for evicted memories matching the query
+/// * `budget` - Token budget for the injected content
 ///
 /// # Errors
 /// Returns `MnemoError::Proxy` if the request format is invalid.
-pub fn inject_memories(
+pub fn inject_context(
     request_body: &mut Value,
     memories: &[RetrievedMemory],
-    max_tokens: usize,
+    tombstones: &[Tombstone],
+    budget: InjectionBudget,
 ) -> Result<()> {
-    if memories.is_empty() {
-        return Ok(());
-    }
+    // Truncate memories and tombstones to fit budget
+    let memory_block = build_injection_block(memories, tombstones, budget);
+    inject_block(request_body, &memory_block)
+}
 
-    // Truncate memories to fit budget
-    let truncated_memories = truncate_to_budget(memories, max_tokens);
-    if truncated_memories.is_empty() {
+/// Inject a prebuilt memory block into an OpenAI-format request body.
+///
+/// The block is appended to the system message, which is created if the
+/// request has none. Does nothing if the block is empty.
+///
+/// # Errors
+/// Returns `MnemoError::Proxy` if the request format is invalid.
+pub fn inject_block(request_body: &mut Value, memory_block: &str) -> Result<()> {
+    if memory_block.is_empty() {
         return Ok(());
     }
-
-    let memory_block = format_memory_block(&truncated_memories);
+    let memory_block = memory_block.to_string();
 
     // Get or create messages array
     let messages = request_body
@@ -157,24 +313,63 @@ pub fn inject_memories(
 
 /// Extract the user message content from an OpenAI-format request.
 ///
-/// Returns the content of the last user message, which is typically
-/// the query to use for memory retrieval.
+/// Returns the text of the last user message, which is typically
+/// the query to use for memory retrieval. All text parts of multimodal
+/// content are concatenated.
 pub fn extract_user_query(request_body: &Value) -> Option<String> {
-    let messages = request_body.get("messages")?.as_array()?;
+    last_user_message(request_body)?
+        .get("content")
+        .and_then(content_text)
+}
+
+/// Extract the last user message of an OpenAI-format request for capture
+///
+/// Like [`extract_user_query`], but images, files, and audio parts are kept
+/// as placeholders such as `[image: image/png]`.
+pub fn extract_user_message(request_body: &Value) -> Option<String> {
+    last_user_message(request_body)?
+        .get("content")
+        .and_then(content_with_placeholders)
+}
+
+/// Extract the text of the last `max_turns` user and assistant messages
+///
+/// Messages are returned oldest first. Messages without text, such as tool
+/// calls and results, are skipped.
+pub fn extract_recent_turns(request_body: &Value, max_turns: usize) -> Vec<String> {
+    let Some(messages) = request_body.get("messages").and_then(|m| m.as_array()) else {
+        return Vec::new();
+    };
 
-    // Find the last user message
-    messages
+    let mut turns: Vec<String> = messages
+        .iter()
+        .rev()
+        .filter(|m| {
+            matches!(
+                m.get("role").and_then(|r| r.as_str()),
+                Some("user") | Some("assistant")
+            )
+        })
+        .filter_map(|m| m.get("content").and_then(content_text))
+        .take(max_turns)
+        .collect();
+    turns.reverse();
+    turns
+}
+
+fn last_user_message(request_body: &Value) -> Option<&Value> {
+    request_body
+        .get("messages")?
+        .as_array()?
         .iter()
         .rev()
         .find(|m| m.get("role").and_then(|r| r.as_str()) == Some("user"))
-        .and_then(|m| m.get("content"))
-        .and_then(|c| c.as_str())
-        .map(|s| s.to_string())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::memory::tombstone::EvictionReason;
     use crate::memory::types::{Memory, MemorySource, MemoryType};
     use chrono::{TimeZone, Utc};
 
@@ -421,6 +616,54 @@ mod tests {
         assert!(query.is_none());
     }
 
+    #[test]
+    fn test_extract_user_query_multimodal_content() {
+        let request = serde_json::json!({
+            "messages": [{
+                "role": "user",
+                "content": [
+                    {"type": "text", "text": "What breed is this dog?"},
+                    {"type": "image_url", "image_url": {"url": "data:image/jpeg;base64,/9j/4AAQ"}},
+                    {"type": "text", "text": "And how old does it look?"}
+                ]
+            }]
+        });
+
+        assert_eq!(
+            extract_user_query(&request),
+            Some("What breed is this dog?\nAnd how old does it look?".to_string())
+        );
+        assert_eq!(
+            extract_user_message(&request),
+            Some(
+                "What breed is this dog?\n[image: image/jpeg]\nAnd how old does it look?"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_extract_recent_turns() {
+        let request = serde_json::json!({
+            "messages": [
+                {"role": "system", "content": "You are helpful."},
+                {"role": "user", "content": "List three Rust web frameworks."},
+                {"role": "assistant", "content": "Axum, Actix Web, and Rocket."},
+                {"role": "user", "content": "What about the second one?"}
+            ]
+        });
+
+        assert_eq!(
+            extract_recent_turns(&request, 2),
+            vec![
+                "Axum, Actix Web, and Rocket.".to_string(),
+                "What about the second one?".to_string()
+            ]
+        );
+        assert_eq!(extract_recent_turns(&request, 10).len(), 3);
+        assert!(extract_recent_turns(&serde_json::json!({}), 4).is_empty());
+    }
+
     #[test]
     fn test_inject_into_empty_system_message() {
         let mut request = serde_json::json!({
@@ -445,6 +688,111 @@ mod tests {
         assert!(system_content.starts_with("<mnemo-memories>"));
     }
 
+    fn create_test_tombstone(topic: &str) -> Tombstone {
+        Tombstone::new(
+            uuid::Uuid::new_v4(),
+            vec![topic.to_string()],
+            vec!["Alice".to_string()],
+            Utc.with_ymd_and_hms(2024, 1, 15, 10, 30, 0).unwrap(),
+            EvictionReason::StoragePressure,
+        )
+    }
+
+    #[test]
+    fn test_inject_context_with_tombstones() {
+        let mut request = serde_json::json!({
+            "model": "gpt-4",
+            "messages": [
+                {"role": "user", "content": "What about project-x?"}
+            ]
+        });
+
+        let memories = vec![create_retrieved_memory(
+            "User prefers dark mode",
+            MemoryType::Episodic,
+        )];
+        let tombstones = vec![create_test_tombstone("project-x")];
+
+        inject_context(
+            &mut request,
+            &memories,
+            &tombstones,
+            InjectionBudget::new(2000),
+        )
+        .unwrap();
+
+        let system_content = request["messages"][0]["content"].as_str().unwrap();
+        assert!(system_content.contains("<mnemo-memories>"));
+        assert!(system_content.contains("<mnemo-tombstone"));
+        assert!(system_content.contains("topics=\"project-x\""));
+    }
+
+    #[test]
+    fn test_inject_context_tombstones_only() {
+        let mut request = serde_json::json!({
+            "model": "gpt-4",
+            "messages": [
+                {"role": "user", "content": "What about project-x?"}
+            ]
+        });
+
+        let tombstones = vec![create_test_tombstone("project-x")];
+
+        inject_context(&mut request, &[], &tombstones, InjectionBudget::new(2000)).unwrap();
+
+        let system_content = request["messages"][0]["content"].as_str().unwrap();
+        assert!(system_content.starts_with("<mnemo-tombstone"));
+        assert!(!system_content.contains("<mnemo-memories>"));
+    }
+
+    #[test]
+    fn test_tombstones_limited_to_budget_share() {
+        let tombstones: Vec<Tombstone> = (0..20)
+            .map(|i| create_test_tombstone(&format!("topic-{i}")))
+            .collect();
+        let budget = InjectionBudget::new(500).with_tombstone_share(0.2);
+
+        let block = build_injection_block(&[], &tombstones, budget);
+
+        assert!(!block.is_empty());
+        assert!(estimate_tokens(&block) <= budget.tombstone_tokens());
+        assert!(block.matches("<mnemo-tombstone").count() < tombstones.len());
+    }
+
+    #[test]
+    fn test_zero_tombstone_share_disables_tombstones() {
+        let memories = vec![create_retrieved_memory("Memory 1", MemoryType::Semantic)];
+        let tombstones = vec![create_test_tombstone("project-x")];
+        let budget = InjectionBudget::new(2000).with_tombstone_share(0.0);
+
+        let block = build_injection_block(&memories, &tombstones, budget);
+
+        assert!(block.contains("<mnemo-memories>"));
+        assert!(!block.contains("<mnemo-tombstone"));
+    }
+
+    #[test]
+    fn test_plan_injection_reports_what_fits() {
+        let memories: Vec<RetrievedMemory> = (0..10)
+            .map(|i| {
+                let content = format!("Memory {i} {}", "x".repeat(200));
+                create_retrieved_memory(&content, MemoryType::Semantic)
+            })
+            .collect();
+        let tombstones = vec![create_test_tombstone("project-x")];
+
+        let plan = plan_injection(&memories, &tombstones, InjectionBudget::new(300));
+
+        assert!(!plan.memories.is_empty());
+        assert!(plan.memories.len() < memories.len());
+        assert_eq!(plan.tombstones.len(), 1);
+        assert_eq!(plan.tokens(), estimate_tokens(&plan.block));
+        assert_eq!(
+            plan.block,
+            build_injection_block(&memories, &tombstones, InjectionBudget::new(300))
+        );
+    }
+
     #[test]
     fn test_memory_block_xml_is_valid() {
         let memories = vec![
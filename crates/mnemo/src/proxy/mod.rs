@@ -1,4 +1,5 @@
 mod capture;
+mod content;
 mod error;
 mod injection;
 mod passthrough;
@@ -9,16 +10,22 @@ mod session;
 mod streaming;
 
 pub use capture::ResponseCapture;
+pub use content::{attachment_placeholder, content_text, content_with_placeholders};
 pub use error::{
     PassthroughDecision, ProxyError, handle_ingestion_error, handle_network_error,
     handle_proxy_error, handle_request_error, handle_retrieval_error, handle_router_error,
     handle_upstream_error, with_error_handling,
 };
 pub use injection::{
-    estimate_tokens, extract_user_query, format_memory_block, inject_memories, truncate_to_budget,
+    DEFAULT_TOMBSTONE_SHARE, InjectionBudget, InjectionPlan, build_injection_block,
+    estimate_tokens, extract_recent_turns, extract_user_message, extract_user_query,
+    format_memory_block, format_tombstone_block, inject_block, inject_context, inject_memories,
+    plan_injection, truncate_to_budget, truncate_tombstones_to_budget,
 };
 pub use passthrough::UpstreamTarget;
-pub use provider::Provider;
+pub use provider::{Provider, Route};
 pub use server::{AppState, ProxyServer, create_router};
 pub use session::{SessionId, SessionIdError};
-pub use streaming::{BufferHandle, ExtractedContent, SseEvent, StreamingProxy, TeeResult};
+pub use streaming::{
+    BufferHandle, ExtractedContent, SseEvent, StreamAbort, StreamingProxy, TeeResult, ToolCall,
+};
@@ -90,10 +90,15 @@ impl UpstreamTarget {
                 .map_err(|_| MnemoError::Config("Failed to strip password from URL".to_string()))?;
         }
 
-        // Append query string if provided
+        // Append query string if provided, keeping any query that was encoded
+        // into the path (e.g., Azure's `api-version`)
         if let Some(q) = query {
             if !q.is_empty() {
-                url.set_query(Some(q));
+                let merged = match url.query() {
+                    Some(existing) if !existing.is_empty() => format!("{existing}&{q}"),
+                    _ => q.to_string(),
+                };
+                url.set_query(Some(&merged));
             }
         }
 
@@ -134,7 +139,7 @@ impl UpstreamTarget {
     /// Check if a host matches a pattern
     ///
     /// Supports exact matching and wildcard subdomain patterns.
-    fn host_matches_pattern(host: &str, pattern: &str) -> bool {
+    pub(crate) fn host_matches_pattern(host: &str, pattern: &str) -> bool {
         // Handle wildcard subdomain pattern: *.example.com
         if let Some(suffix) = pattern.strip_prefix("*.") {
             if host == suffix {
@@ -215,6 +220,8 @@ mod tests {
             allowed_hosts,
             timeout_secs: 300,
             max_injection_tokens: 2000,
+            tombstone_budget_share: 0.2,
+            routes: Vec::new(),
         }
     }
 
@@ -264,6 +271,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_from_path_preserves_azure_api_version() {
+        let target = UpstreamTarget::from_path(
+            "/p/https://my-resource.openai.azure.com/openai/deployments/gpt-4o/chat/completions",
+            Some("api-version=2024-10-21"),
+        )
+        .unwrap();
+
+        assert_eq!(target.host, "my-resource.openai.azure.com");
+        assert_eq!(target.url.query(), Some("api-version=2024-10-21"));
+    }
+
+    #[test]
+    fn test_from_path_merges_encoded_query() {
+        let target = UpstreamTarget::from_path(
+            "/p/https%3A%2F%2Fmy-resource.openai.azure.com%2Fopenai%2Fdeployments%2Fgpt-4o%2Fchat%2Fcompletions%3Fapi-version%3D2024-10-21",
+            Some("stream=true"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            target.url.query(),
+            Some("api-version=2024-10-21&stream=true")
+        );
+    }
+
     #[test]
     fn test_from_path_strips_fragment() {
         let target =
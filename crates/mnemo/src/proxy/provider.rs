@@ -1,11 +1,19 @@
 use axum::http::HeaderMap;
+use serde::Deserialize;
 use serde_json::Value;
 use url::Url;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use crate::config::RouteRule;
+use crate::proxy::UpstreamTarget;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Provider {
     OpenAI,
     Anthropic,
+    Gemini,
+    Ollama,
+    LlamaCpp,
     Unknown,
 }
 
@@ -34,10 +42,37 @@ impl Provider {
             return Some(Provider::OpenAI);
         }
 
+        // Azure OpenAI resources, addressed by deployment rather than model
+        if host_lower.ends_with(".openai.azure.com")
+            || url.path().starts_with("/openai/deployments/")
+        {
+            return Some(Provider::OpenAI);
+        }
+
         if host_lower.ends_with("anthropic.com") || host_lower == "anthropic.com" {
             return Some(Provider::Anthropic);
         }
 
+        if host_lower == "generativelanguage.googleapis.com" {
+            return Some(Provider::Gemini);
+        }
+
+        // Vertex AI and other Gemini-compatible hosts use the same method suffixes
+        let path = url.path();
+        if path.ends_with(":generateContent") || path.ends_with(":streamGenerateContent") {
+            return Some(Provider::Gemini);
+        }
+
+        // Local servers are recognised by their native endpoints
+        let path = path.trim_end_matches('/');
+        if path.ends_with("/api/chat") || path.ends_with("/api/generate") {
+            return Some(Provider::Ollama);
+        }
+
+        if path.ends_with("/completion") {
+            return Some(Provider::LlamaCpp);
+        }
+
         None
     }
 
@@ -48,6 +83,15 @@ impl Provider {
             if name_lower == "x-api-key" {
                 return Some(Provider::Anthropic);
             }
+
+            if name_lower == "x-goog-api-key" {
+                return Some(Provider::Gemini);
+            }
+
+            // Azure OpenAI authenticates with a bare `api-key` header
+            if name_lower == "api-key" {
+                return Some(Provider::OpenAI);
+            }
         }
 
         if let Some(auth) = headers.get("authorization") {
@@ -62,6 +106,15 @@ impl Provider {
     }
 
     fn detect_from_body(body: &Value) -> Option<Self> {
+        if body.get("contents").is_some_and(|c| c.is_array()) {
+            return Some(Provider::Gemini);
+        }
+
+        // The OpenAI Responses API takes `input` in place of `messages`
+        if body.get("input").is_some() && body.get("messages").is_none() {
+            return Some(Provider::OpenAI);
+        }
+
         if body.get("system").is_some() {
             return Some(Provider::Anthropic);
         }
@@ -93,3 +146,84 @@ impl Provider {
         None
     }
 }
+
+/// How the proxy handles a request: its provider format and whether memories
+/// are injected and the response captured
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Route {
+    pub provider: Provider,
+    pub inject: bool,
+    pub capture: bool,
+}
+
+impl Route {
+    /// Resolve the route for a request
+    ///
+    /// The first rule matching the request decides the policies, and the
+    /// provider when the rule names one. Otherwise the provider is detected
+    /// from the request and memories are injected and captured.
+    ///
+    /// `body` is `None` when the request body is not JSON. Rules still apply
+    /// and the provider is detected from the URL and headers, but nothing
+    /// can be injected into the body.
+    pub fn resolve(
+        rules: &[RouteRule],
+        url: &Url,
+        headers: &HeaderMap,
+        body: Option<&Value>,
+    ) -> Self {
+        let route = Self::resolve_json(rules, url, headers, body.unwrap_or(&Value::Null));
+        Route {
+            inject: route.inject && body.is_some(),
+            ..route
+        }
+    }
+
+    fn resolve_json(rules: &[RouteRule], url: &Url, headers: &HeaderMap, body: &Value) -> Self {
+        match rules.iter().find(|rule| rule.matches(url, headers)) {
+            Some(rule) => Route {
+                provider: rule
+                    .provider
+                    .unwrap_or_else(|| Provider::detect(url, headers, body)),
+                inject: rule.inject,
+                capture: rule.capture,
+            },
+            None => Route {
+                provider: Provider::detect(url, headers, body),
+                inject: true,
+                capture: true,
+            },
+        }
+    }
+}
+
+impl RouteRule {
+    /// Whether every condition of this rule matches the request
+    pub fn matches(&self, url: &Url, headers: &HeaderMap) -> bool {
+        if let Some(ref pattern) = self.host {
+            let host = url.host_str().unwrap_or_default().to_lowercase();
+            if !UpstreamTarget::host_matches_pattern(&host, &pattern.to_lowercase()) {
+                return false;
+            }
+        }
+
+        if let Some(ref prefix) = self.path_prefix {
+            if !url.path().starts_with(prefix.as_str()) {
+                return false;
+            }
+        }
+
+        if let Some(ref name) = self.header {
+            let Some(value) = headers.get(name.as_str()) else {
+                return false;
+            };
+            if let Some(ref expected) = self.header_value {
+                if value.to_str().ok() != Some(expected.as_str()) {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+}
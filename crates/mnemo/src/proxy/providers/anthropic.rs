@@ -1,8 +1,7 @@
 use crate::error::{MnemoError, Result};
-use crate::memory::retrieval::RetrievedMemory;
 use crate::proxy::providers::LLMProvider;
-use crate::proxy::streaming::ExtractedContent;
-use crate::proxy::{format_memory_block, truncate_to_budget};
+use crate::proxy::streaming::{ExtractedContent, ToolCall};
+use crate::proxy::{InjectionPlan, content_text, content_with_placeholders, extract_recent_turns};
 use serde_json::Value;
 
 pub struct AnthropicProvider;
@@ -20,28 +19,26 @@ impl Default for AnthropicProvider {
 }
 
 impl LLMProvider for AnthropicProvider {
-    fn inject_memories(
-        &self,
-        request_body: &mut Value,
-        memories: &[RetrievedMemory],
-        max_tokens: usize,
-    ) -> Result<()> {
-        if memories.is_empty() {
+    fn inject_plan(&self, request_body: &mut Value, plan: &InjectionPlan) -> Result<()> {
+        if plan.block.is_empty() {
             return Ok(());
         }
-
-        let truncated = truncate_to_budget(memories, max_tokens);
-        if truncated.is_empty() {
-            return Ok(());
-        }
-
-        let memory_block = format_memory_block(&truncated);
+        let memory_block = plan.block.clone();
 
         let obj = request_body
             .as_object_mut()
             .ok_or_else(|| MnemoError::Proxy("Request body is not an object".into()))?;
 
         match obj.get_mut("system") {
+            // Structured system prompts get the memories as a trailing text
+            // block, leaving the prefix covered by any `cache_control`
+            // breakpoints unchanged so cached prompts stay valid
+            Some(Value::Array(blocks)) => {
+                blocks.push(serde_json::json!({
+                    "type": "text",
+                    "text": memory_block
+                }));
+            }
             Some(system_value) => {
                 if let Some(system_str) = system_value.as_str() {
                     let new_content = if system_str.is_empty() {
@@ -63,31 +60,15 @@ impl LLMProvider for AnthropicProvider {
     }
 
     fn extract_user_query(&self, request_body: &Value) -> Option<String> {
-        let messages = request_body.get("messages")?.as_array()?;
-
-        messages
-            .iter()
-            .rev()
-            .find(|m| m.get("role").and_then(|r| r.as_str()) == Some("user"))
-            .and_then(|m| {
-                let content = m.get("content")?;
-
-                if let Some(s) = content.as_str() {
-                    return Some(s.to_string());
-                }
+        last_user_content(request_body).and_then(content_text)
+    }
 
-                if let Some(arr) = content.as_array() {
-                    for block in arr {
-                        if block.get("type").and_then(|t| t.as_str()) == Some("text") {
-                            if let Some(text) = block.get("text").and_then(|t| t.as_str()) {
-                                return Some(text.to_string());
-                            }
-                        }
-                    }
-                }
+    fn extract_recent_turns(&self, request_body: &Value, max_turns: usize) -> Vec<String> {
+        extract_recent_turns(request_body, max_turns)
+    }
 
-                None
-            })
+    fn extract_user_message(&self, request_body: &Value) -> Option<String> {
+        last_user_content(request_body).and_then(content_with_placeholders)
     }
 
     fn parse_sse_content(&self, raw_sse: &str) -> ExtractedContent {
@@ -112,10 +93,105 @@ impl LLMProvider for AnthropicProvider {
             Some(result)
         }
     }
+
+    fn parse_response_tool_calls(&self, response_body: &Value) -> Vec<ToolCall> {
+        response_body
+            .get("content")
+            .and_then(|c| c.as_array())
+            .map(|blocks| blocks.iter().filter_map(tool_use_call).collect())
+            .unwrap_or_default()
+    }
+
+    fn extract_tool_results(&self, request_body: &Value) -> Vec<ToolCall> {
+        let Some(messages) = request_body.get("messages").and_then(|m| m.as_array()) else {
+            return Vec::new();
+        };
+
+        // Results are sent back as `tool_result` blocks in the final user turn
+        let Some(last) = messages
+            .last()
+            .filter(|m| m.get("role").and_then(|r| r.as_str()) == Some("user"))
+            .and_then(|m| m.get("content"))
+            .and_then(|c| c.as_array())
+        else {
+            return Vec::new();
+        };
+
+        let calls: Vec<ToolCall> = messages
+            .iter()
+            .filter(|m| m.get("role").and_then(|r| r.as_str()) == Some("assistant"))
+            .filter_map(|m| m.get("content").and_then(|c| c.as_array()))
+            .flatten()
+            .filter_map(tool_use_call)
+            .collect();
+
+        last.iter()
+            .filter(|block| block.get("type").and_then(|t| t.as_str()) == Some("tool_result"))
+            .filter_map(|block| {
+                let id = block.get("tool_use_id")?.as_str()?;
+                let call = calls.iter().find(|c| c.id.as_deref() == Some(id))?;
+                Some(ToolCall {
+                    result: block.get("content").map(tool_result_text),
+                    ..call.clone()
+                })
+            })
+            .collect()
+    }
+}
+
+/// Content of the last user message
+fn last_user_content(request_body: &Value) -> Option<&Value> {
+    request_body
+        .get("messages")?
+        .as_array()?
+        .iter()
+        .rev()
+        .find(|m| m.get("role").and_then(|r| r.as_str()) == Some("user"))?
+        .get("content")
+}
+
+/// Parse a `tool_use` content block
+fn tool_use_call(block: &Value) -> Option<ToolCall> {
+    if block.get("type").and_then(|t| t.as_str()) != Some("tool_use") {
+        return None;
+    }
+
+    Some(ToolCall {
+        id: block
+            .get("id")
+            .and_then(|i| i.as_str())
+            .map(|s| s.to_string()),
+        name: block.get("name")?.as_str()?.to_string(),
+        arguments: block
+            .get("input")
+            .map(|i| i.to_string())
+            .unwrap_or_default(),
+        result: None,
+    })
+}
+
+/// Text of a `tool_result` block, whose content is a string or text blocks
+fn tool_result_text(content: &Value) -> String {
+    if let Some(s) = content.as_str() {
+        return s.to_string();
+    }
+
+    content
+        .as_array()
+        .map(|blocks| {
+            blocks
+                .iter()
+                .filter_map(|b| b.get("text").and_then(|t| t.as_str()))
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+        .unwrap_or_default()
 }
 
 pub fn parse_anthropic_sse(raw: &str) -> ExtractedContent {
     let mut content = String::new();
+    // Tool calls keyed by content block index, as their input streams in pieces
+    let mut tool_calls: Vec<(u64, ToolCall)> = Vec::new();
     let mut is_complete = false;
     let mut event_count = 0;
 
@@ -134,6 +210,19 @@ pub fn parse_anthropic_sse(raw: &str) -> ExtractedContent {
                         "message_stop" => {
                             is_complete = true;
                         }
+                        "content_block_start" => {
+                            if let Ok(json) = serde_json::from_str::<Value>(data) {
+                                let index = json.get("index").and_then(|i| i.as_u64());
+                                let call = json.get("content_block").and_then(tool_use_call);
+
+                                if let (Some(index), Some(mut call)) = (index, call) {
+                                    // The start event carries an empty input
+                                    // object; the real input follows as deltas
+                                    call.arguments.clear();
+                                    tool_calls.push((index, call));
+                                }
+                            }
+                        }
                         "content_block_delta" => {
                             if let Ok(json) = serde_json::from_str::<Value>(data) {
                                 if let Some(delta) = json.get("delta") {
@@ -145,6 +234,16 @@ pub fn parse_anthropic_sse(raw: &str) -> ExtractedContent {
                                         {
                                             content.push_str(text);
                                         }
+                                    } else if delta_type == Some("input_json_delta") {
+                                        let index = json.get("index").and_then(|i| i.as_u64());
+                                        let partial =
+                                            delta.get("partial_json").and_then(|p| p.as_str());
+
+                                        if let Some((_, call)) =
+                                            tool_calls.iter_mut().find(|(i, _)| Some(*i) == index)
+                                        {
+                                            call.arguments.push_str(partial.unwrap_or_default());
+                                        }
                                     }
                                 }
                             }
@@ -158,6 +257,7 @@ pub fn parse_anthropic_sse(raw: &str) -> ExtractedContent {
 
     ExtractedContent {
         content,
+        tool_calls: tool_calls.into_iter().map(|(_, call)| call).collect(),
         is_complete,
         event_count,
     }
@@ -166,6 +266,8 @@ pub fn parse_anthropic_sse(raw: &str) -> ExtractedContent {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::memory::tombstone::Tombstone;
+    use crate::proxy::InjectionBudget;
 
     #[test]
     fn test_parse_anthropic_text_delta() {
@@ -227,6 +329,82 @@ data: {"type":"content_block_delta","index":1,"delta":{"type":"text_delta","text
         assert_eq!(result.content, "Here's the result");
     }
 
+    #[test]
+    fn test_anthropic_stream_reconstructs_tool_use() {
+        let raw = r#"event: content_block_start
+data: {"type":"content_block_start","index":0,"content_block":{"type":"text","text":""}}
+
+event: content_block_delta
+data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Checking"}}
+
+event: content_block_start
+data: {"type":"content_block_start","index":1,"content_block":{"type":"tool_use","id":"toolu_1","name":"get_weather","input":{}}}
+
+event: content_block_delta
+data: {"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":"{\"city\":"}}
+
+event: content_block_delta
+data: {"type":"content_block_delta","index":1,"delta":{"type":"input_json_delta","partial_json":" \"Paris\"}"}}
+
+event: content_block_stop
+data: {"type":"content_block_stop","index":1}
+
+event: message_stop
+data: {"type":"message_stop"}
+
+"#;
+        let result = parse_anthropic_sse(raw);
+
+        assert_eq!(result.content, "Checking");
+        assert_eq!(result.tool_calls.len(), 1);
+        assert_eq!(result.tool_calls[0].id.as_deref(), Some("toolu_1"));
+        assert_eq!(result.tool_calls[0].name, "get_weather");
+        assert_eq!(result.tool_calls[0].arguments, r#"{"city": "Paris"}"#);
+    }
+
+    #[test]
+    fn test_anthropic_parse_response_tool_calls() {
+        let provider = AnthropicProvider::new();
+        let response = serde_json::json!({
+            "content": [
+                {"type": "text", "text": "Let me check."},
+                {"type": "tool_use", "id": "toolu_1", "name": "get_weather",
+                 "input": {"city": "Paris"}}
+            ]
+        });
+
+        let calls = provider.parse_response_tool_calls(&response);
+
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].name, "get_weather");
+        assert_eq!(calls[0].arguments, r#"{"city":"Paris"}"#);
+    }
+
+    #[test]
+    fn test_anthropic_extract_tool_results() {
+        let provider = AnthropicProvider::new();
+        let request = serde_json::json!({
+            "messages": [
+                {"role": "user", "content": "Weather in Paris?"},
+                {"role": "assistant", "content": [
+                    {"type": "tool_use", "id": "toolu_1", "name": "get_weather",
+                     "input": {"city": "Paris"}}
+                ]},
+                {"role": "user", "content": [
+                    {"type": "tool_result", "tool_use_id": "toolu_1",
+                     "content": [{"type": "text", "text": "18C and sunny"}]}
+                ]}
+            ]
+        });
+
+        let results = provider.extract_tool_results(&request);
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "get_weather");
+        assert_eq!(results[0].arguments, r#"{"city":"Paris"}"#);
+        assert_eq!(results[0].result.as_deref(), Some("18C and sunny"));
+    }
+
     #[test]
     fn test_anthropic_handles_ping() {
         let raw = r#"event: ping
@@ -313,7 +491,9 @@ data: {"type":"ping"}
             final_score: 0.85,
         };
 
-        provider.inject_memories(&mut request, &[rm], 2000).unwrap();
+        provider
+            .inject_memories(&mut request, &[rm], &[], InjectionBudget::new(2000))
+            .unwrap();
 
         let system = request["system"].as_str().unwrap();
         assert!(system.starts_with("You are helpful."));
@@ -343,13 +523,93 @@ data: {"type":"ping"}
             final_score: 0.85,
         };
 
-        provider.inject_memories(&mut request, &[rm], 2000).unwrap();
+        provider
+            .inject_memories(&mut request, &[rm], &[], InjectionBudget::new(2000))
+            .unwrap();
 
         assert!(request.get("system").is_some());
         let system = request["system"].as_str().unwrap();
         assert!(system.contains("<mnemo-memories>"));
     }
 
+    #[test]
+    fn test_anthropic_inject_into_system_blocks() {
+        let provider = AnthropicProvider::new();
+        let mut request = serde_json::json!({
+            "system": [
+                {"type": "text", "text": "You are helpful."},
+                {
+                    "type": "text",
+                    "text": "Long reference document",
+                    "cache_control": {"type": "ephemeral"}
+                }
+            ],
+            "messages": [{"role": "user", "content": "Hello"}]
+        });
+
+        use crate::memory::retrieval::RetrievedMemory;
+        use crate::memory::types::{Memory, MemorySource, MemoryType};
+
+        let memory = Memory::new(
+            "Test memory".to_string(),
+            vec![0.0; 384],
+            MemoryType::Semantic,
+            MemorySource::Manual,
+        );
+        let rm = RetrievedMemory {
+            memory,
+            similarity_score: 0.9,
+            effective_weight: 0.8,
+            final_score: 0.85,
+        };
+
+        provider
+            .inject_memories(&mut request, &[rm], &[], InjectionBudget::new(2000))
+            .unwrap();
+
+        let blocks = request["system"].as_array().unwrap();
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0]["text"], "You are helpful.");
+        assert_eq!(blocks[1]["text"], "Long reference document");
+        assert_eq!(blocks[1]["cache_control"]["type"], "ephemeral");
+        assert_eq!(blocks[2]["type"], "text");
+        assert!(
+            blocks[2]["text"]
+                .as_str()
+                .unwrap()
+                .contains("<mnemo-memories>")
+        );
+        assert!(blocks[2].get("cache_control").is_none());
+    }
+
+    #[test]
+    fn test_anthropic_inject_tombstones() {
+        let provider = AnthropicProvider::new();
+        let mut request = serde_json::json!({
+            "system": "You are helpful.",
+            "messages": [{"role": "user", "content": "What about project-x?"}]
+        });
+
+        use crate::memory::tombstone::EvictionReason;
+
+        let tombstone = Tombstone::new(
+            uuid::Uuid::new_v4(),
+            vec!["project-x".to_string()],
+            vec![],
+            chrono::Utc::now(),
+            EvictionReason::LowWeight,
+        );
+
+        provider
+            .inject_memories(&mut request, &[], &[tombstone], InjectionBudget::new(2000))
+            .unwrap();
+
+        let system = request["system"].as_str().unwrap();
+        assert!(system.starts_with("You are helpful."));
+        assert!(system.contains("<mnemo-tombstone"));
+        assert!(!system.contains("<mnemo-memories>"));
+    }
+
     #[test]
     fn test_anthropic_extract_query_string_content() {
         let provider = AnthropicProvider::new();
@@ -395,6 +655,36 @@ data: {"type":"ping"}
         );
     }
 
+    #[test]
+    fn test_anthropic_extract_query_joins_text_blocks() {
+        let provider = AnthropicProvider::new();
+        let request = serde_json::json!({
+            "messages": [{
+                "role": "user",
+                "content": [
+                    {"type": "text", "text": "Here is the contract."},
+                    {
+                        "type": "document",
+                        "source": {"type": "base64", "media_type": "application/pdf", "data": "..."}
+                    },
+                    {"type": "text", "text": "Summarize the termination clause."}
+                ]
+            }]
+        });
+
+        assert_eq!(
+            provider.extract_user_query(&request),
+            Some("Here is the contract.\nSummarize the termination clause.".to_string())
+        );
+        assert_eq!(
+            provider.extract_user_message(&request),
+            Some(
+                "Here is the contract.\n[document: application/pdf]\nSummarize the termination clause."
+                    .to_string()
+            )
+        );
+    }
+
     #[test]
     fn test_anthropic_inject_empty_memories_noop() {
         let provider = AnthropicProvider::new();
@@ -403,7 +693,9 @@ data: {"type":"ping"}
         });
         let mut request = original.clone();
 
-        provider.inject_memories(&mut request, &[], 2000).unwrap();
+        provider
+            .inject_memories(&mut request, &[], &[], InjectionBudget::new(2000))
+            .unwrap();
 
         assert_eq!(request, original);
     }
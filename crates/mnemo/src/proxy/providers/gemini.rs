@@ -0,0 +1,366 @@
+use crate::error::{MnemoError, Result};
+use crate::proxy::providers::LLMProvider;
+use crate::proxy::streaming::{ExtractedContent, SseEvent, StreamingProxy};
+use crate::proxy::{InjectionPlan, content_with_placeholders};
+use serde_json::Value;
+
+pub struct GeminiProvider;
+
+impl GeminiProvider {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for GeminiProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LLMProvider for GeminiProvider {
+    fn inject_plan(&self, request_body: &mut Value, plan: &InjectionPlan) -> Result<()> {
+        if plan.block.is_empty() {
+            return Ok(());
+        }
+        let memory_block = plan.block.clone();
+
+        let obj = request_body
+            .as_object_mut()
+            .ok_or_else(|| MnemoError::Proxy("Request body is not an object".into()))?;
+
+        // The API accepts both spellings; keep whichever the client used
+        let key = if obj.contains_key("system_instruction") {
+            "system_instruction"
+        } else {
+            "systemInstruction"
+        };
+
+        let memory_part = serde_json::json!({ "text": memory_block });
+        match obj.get_mut(key) {
+            Some(instruction) => {
+                let parts = instruction
+                    .get_mut("parts")
+                    .and_then(|p| p.as_array_mut())
+                    .ok_or_else(|| {
+                        MnemoError::Proxy("systemInstruction has no parts array".into())
+                    })?;
+                parts.push(memory_part);
+            }
+            None => {
+                obj.insert(
+                    key.to_string(),
+                    serde_json::json!({ "parts": [memory_part] }),
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn extract_user_query(&self, request_body: &Value) -> Option<String> {
+        last_user_parts(request_body).and_then(join_text_parts)
+    }
+
+    fn extract_recent_turns(&self, request_body: &Value, max_turns: usize) -> Vec<String> {
+        let Some(contents) = request_body.get("contents").and_then(|c| c.as_array()) else {
+            return Vec::new();
+        };
+
+        let mut turns: Vec<String> = contents
+            .iter()
+            .rev()
+            .filter_map(|c| join_text_parts(c.get("parts")?))
+            .take(max_turns)
+            .collect();
+        turns.reverse();
+        turns
+    }
+
+    fn extract_user_message(&self, request_body: &Value) -> Option<String> {
+        last_user_parts(request_body).and_then(content_with_placeholders)
+    }
+
+    fn parse_sse_content(&self, raw_sse: &str) -> ExtractedContent {
+        parse_gemini_sse(raw_sse)
+    }
+
+    fn parse_response_content(&self, response_body: &Value) -> Option<String> {
+        // streamGenerateContent without `alt=sse` returns a JSON array of chunks
+        let content = match response_body.as_array() {
+            Some(chunks) => chunks.iter().filter_map(candidate_text).collect(),
+            None => candidate_text(response_body)?,
+        };
+
+        if content.is_empty() {
+            None
+        } else {
+            Some(content)
+        }
+    }
+}
+
+/// Parts of the last user turn
+fn last_user_parts(request_body: &Value) -> Option<&Value> {
+    // Role is optional on single-turn requests and defaults to "user"
+    request_body
+        .get("contents")?
+        .as_array()?
+        .iter()
+        .rev()
+        .find(|c| c.get("role").and_then(|r| r.as_str()).unwrap_or("user") == "user")?
+        .get("parts")
+}
+
+/// Concatenate the text parts of a Gemini `Content`, skipping thoughts
+fn join_text_parts(parts: &Value) -> Option<String> {
+    let text: String = parts
+        .as_array()?
+        .iter()
+        .filter(|part| {
+            !part
+                .get("thought")
+                .and_then(|t| t.as_bool())
+                .unwrap_or(false)
+        })
+        .filter_map(|part| part.get("text").and_then(|t| t.as_str()))
+        .collect();
+
+    if text.is_empty() { None } else { Some(text) }
+}
+
+/// Text of the first candidate in a `GenerateContentResponse`
+fn candidate_text(response: &Value) -> Option<String> {
+    let parts = response
+        .get("candidates")?
+        .get(0)?
+        .get("content")?
+        .get("parts")?;
+    join_text_parts(parts)
+}
+
+/// Parse a `streamGenerateContent?alt=sse` stream
+///
+/// Each data event is a full `GenerateContentResponse` carrying the next
+/// chunk of text. The stream has no `[DONE]` marker; it is complete once a
+/// candidate reports a `finishReason`.
+pub fn parse_gemini_sse(raw: &str) -> ExtractedContent {
+    let events = StreamingProxy::parse_sse_events(raw);
+    let mut content = String::new();
+    let mut is_complete = false;
+
+    for event in &events {
+        let SseEvent::Data(data) = event else {
+            continue;
+        };
+        let Ok(json) = serde_json::from_str::<Value>(data) else {
+            continue;
+        };
+
+        if let Some(text) = candidate_text(&json) {
+            content.push_str(&text);
+        }
+
+        if json
+            .get("candidates")
+            .and_then(|c| c.get(0))
+            .and_then(|c| c.get("finishReason"))
+            .is_some()
+        {
+            is_complete = true;
+        }
+    }
+
+    ExtractedContent {
+        content,
+        tool_calls: Vec::new(),
+        is_complete,
+        event_count: events.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proxy::InjectionBudget;
+    use crate::proxy::providers::test_fixtures::create_retrieved_memory;
+
+    #[test]
+    fn test_gemini_inject_creates_system_instruction() {
+        let provider = GeminiProvider::new();
+        let mut request = serde_json::json!({
+            "contents": [{"role": "user", "parts": [{"text": "Hello"}]}]
+        });
+
+        provider
+            .inject_memories(
+                &mut request,
+                &[create_retrieved_memory()],
+                &[],
+                InjectionBudget::new(2000),
+            )
+            .unwrap();
+
+        let text = request["systemInstruction"]["parts"][0]["text"]
+            .as_str()
+            .unwrap();
+        assert!(text.contains("<mnemo-memories>"));
+    }
+
+    #[test]
+    fn test_gemini_inject_appends_to_existing_instruction() {
+        let provider = GeminiProvider::new();
+        let mut request = serde_json::json!({
+            "system_instruction": {"parts": [{"text": "You are helpful."}]},
+            "contents": [{"role": "user", "parts": [{"text": "Hello"}]}]
+        });
+
+        provider
+            .inject_memories(
+                &mut request,
+                &[create_retrieved_memory()],
+                &[],
+                InjectionBudget::new(2000),
+            )
+            .unwrap();
+
+        let parts = request["system_instruction"]["parts"].as_array().unwrap();
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0]["text"], "You are helpful.");
+        assert!(
+            parts[1]["text"]
+                .as_str()
+                .unwrap()
+                .contains("<mnemo-memories>")
+        );
+        assert!(request.get("systemInstruction").is_none());
+    }
+
+    #[test]
+    fn test_gemini_inject_empty_memories_noop() {
+        let provider = GeminiProvider::new();
+        let original = serde_json::json!({
+            "contents": [{"role": "user", "parts": [{"text": "Hello"}]}]
+        });
+        let mut request = original.clone();
+
+        provider
+            .inject_memories(&mut request, &[], &[], InjectionBudget::new(2000))
+            .unwrap();
+
+        assert_eq!(request, original);
+    }
+
+    #[test]
+    fn test_gemini_extract_query_last_user_turn() {
+        let provider = GeminiProvider::new();
+        let request = serde_json::json!({
+            "contents": [
+                {"role": "user", "parts": [{"text": "First question"}]},
+                {"role": "model", "parts": [{"text": "First answer"}]},
+                {"role": "user", "parts": [
+                    {"inlineData": {"mimeType": "image/png", "data": "..."}},
+                    {"text": "What is this?"}
+                ]}
+            ]
+        });
+
+        assert_eq!(
+            provider.extract_user_query(&request),
+            Some("What is this?".to_string())
+        );
+        assert_eq!(
+            provider.extract_user_message(&request),
+            Some("[image: image/png]\nWhat is this?".to_string())
+        );
+    }
+
+    #[test]
+    fn test_gemini_extract_recent_turns() {
+        let provider = GeminiProvider::new();
+        let request = serde_json::json!({
+            "contents": [
+                {"role": "user", "parts": [{"text": "First question"}]},
+                {"role": "model", "parts": [{"text": "First answer"}]},
+                {"role": "user", "parts": [{"text": "Second question"}]}
+            ]
+        });
+
+        assert_eq!(
+            provider.extract_recent_turns(&request, 2),
+            vec!["First answer".to_string(), "Second question".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_gemini_extract_query_without_role() {
+        let provider = GeminiProvider::new();
+        let request = serde_json::json!({
+            "contents": [{"parts": [{"text": "Hello"}]}]
+        });
+
+        assert_eq!(
+            provider.extract_user_query(&request),
+            Some("Hello".to_string())
+        );
+    }
+
+    #[test]
+    fn test_gemini_parse_response_content() {
+        let provider = GeminiProvider::new();
+        let response = serde_json::json!({
+            "candidates": [{
+                "content": {
+                    "role": "model",
+                    "parts": [
+                        {"text": "Thinking it over", "thought": true},
+                        {"text": "Hello"},
+                        {"text": " World"}
+                    ]
+                },
+                "finishReason": "STOP"
+            }]
+        });
+
+        assert_eq!(
+            provider.parse_response_content(&response),
+            Some("Hello World".to_string())
+        );
+    }
+
+    #[test]
+    fn test_gemini_parse_response_array() {
+        let provider = GeminiProvider::new();
+        let response = serde_json::json!([
+            {"candidates": [{"content": {"parts": [{"text": "Hello"}]}}]},
+            {"candidates": [{"content": {"parts": [{"text": " World"}]}, "finishReason": "STOP"}]}
+        ]);
+
+        assert_eq!(
+            provider.parse_response_content(&response),
+            Some("Hello World".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_gemini_sse() {
+        let raw = "data: {\"candidates\": [{\"content\": {\"parts\": [{\"text\": \"Hello\"}],\"role\": \"model\"}}]}\r\n\r\n\
+                   data: {\"candidates\": [{\"content\": {\"parts\": [{\"text\": \" World\"}],\"role\": \"model\"},\"finishReason\": \"STOP\"}]}\r\n\r\n";
+
+        let result = parse_gemini_sse(raw);
+
+        assert_eq!(result.content, "Hello World");
+        assert!(result.is_complete);
+        assert_eq!(result.event_count, 2);
+    }
+
+    #[test]
+    fn test_parse_gemini_sse_incomplete() {
+        let raw = "data: {\"candidates\": [{\"content\": {\"parts\": [{\"text\": \"Hel\"}]}}]}\n\n";
+
+        let result = parse_gemini_sse(raw);
+
+        assert_eq!(result.content, "Hel");
+        assert!(!result.is_complete);
+    }
+}
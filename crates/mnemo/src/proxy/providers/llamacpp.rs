@@ -0,0 +1,160 @@
+use crate::error::{MnemoError, Result};
+use crate::proxy::InjectionPlan;
+use crate::proxy::providers::LLMProvider;
+use crate::proxy::streaming::{ExtractedContent, SseEvent, StreamingProxy};
+use serde_json::Value;
+
+/// llama.cpp server's native `/completion` endpoint
+///
+/// The request is a single raw `prompt` with no separate system field, so the
+/// memory block is prepended to the prompt.
+pub struct LlamaCppProvider;
+
+impl LlamaCppProvider {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for LlamaCppProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LLMProvider for LlamaCppProvider {
+    fn inject_plan(&self, request_body: &mut Value, plan: &InjectionPlan) -> Result<()> {
+        if plan.block.is_empty() {
+            return Ok(());
+        }
+        let memory_block = plan.block.clone();
+
+        // Token-array prompts can't be extended without the model's tokenizer
+        match request_body.get_mut("prompt") {
+            Some(Value::String(prompt)) => {
+                *prompt = format!("{memory_block}\n\n{prompt}");
+                Ok(())
+            }
+            _ => Err(MnemoError::Proxy("Prompt field is not a string".into())),
+        }
+    }
+
+    fn extract_user_query(&self, request_body: &Value) -> Option<String> {
+        request_body
+            .get("prompt")?
+            .as_str()
+            .filter(|p| !p.is_empty())
+            .map(|p| p.to_string())
+    }
+
+    fn parse_sse_content(&self, raw_sse: &str) -> ExtractedContent {
+        parse_llamacpp_sse(raw_sse)
+    }
+
+    fn parse_response_content(&self, response_body: &Value) -> Option<String> {
+        response_body
+            .get("content")?
+            .as_str()
+            .filter(|c| !c.is_empty())
+            .map(|c| c.to_string())
+    }
+}
+
+/// Parse a llama.cpp `/completion` event stream
+///
+/// Each data event carries the next chunk in `content`; the final event has
+/// `"stop": true`.
+pub fn parse_llamacpp_sse(raw: &str) -> ExtractedContent {
+    let events = StreamingProxy::parse_sse_events(raw);
+    let mut content = String::new();
+    let mut is_complete = false;
+
+    for event in &events {
+        let SseEvent::Data(data) = event else {
+            continue;
+        };
+        let Ok(json) = serde_json::from_str::<Value>(data) else {
+            continue;
+        };
+
+        if let Some(text) = json.get("content").and_then(|c| c.as_str()) {
+            content.push_str(text);
+        }
+
+        if json.get("stop").and_then(|s| s.as_bool()).unwrap_or(false) {
+            is_complete = true;
+        }
+    }
+
+    ExtractedContent {
+        content,
+        tool_calls: Vec::new(),
+        is_complete,
+        event_count: events.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proxy::InjectionBudget;
+    use crate::proxy::providers::test_fixtures::create_retrieved_memory;
+
+    #[test]
+    fn test_llamacpp_inject_prepends_to_prompt() {
+        let provider = LlamaCppProvider::new();
+        let mut request = serde_json::json!({"prompt": "Hello", "n_predict": 128});
+
+        provider
+            .inject_memories(
+                &mut request,
+                &[create_retrieved_memory()],
+                &[],
+                InjectionBudget::new(2000),
+            )
+            .unwrap();
+
+        let prompt = request["prompt"].as_str().unwrap();
+        assert!(prompt.starts_with("<mnemo-memories>"));
+        assert!(prompt.ends_with("\n\nHello"));
+    }
+
+    #[test]
+    fn test_llamacpp_inject_token_prompt_errors() {
+        let provider = LlamaCppProvider::new();
+        let mut request = serde_json::json!({"prompt": [1, 15043]});
+
+        let result = provider.inject_memories(
+            &mut request,
+            &[create_retrieved_memory()],
+            &[],
+            InjectionBudget::new(2000),
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_llamacpp_parse_response_content() {
+        let provider = LlamaCppProvider::new();
+        let response = serde_json::json!({"content": "Hello World", "stop": true});
+
+        assert_eq!(
+            provider.parse_response_content(&response),
+            Some("Hello World".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_llamacpp_sse() {
+        let raw = "data: {\"content\":\"Hello\",\"stop\":false}\n\n\
+                   data: {\"content\":\" World\",\"stop\":false}\n\n\
+                   data: {\"content\":\"\",\"stop\":true,\"stop_type\":\"eos\"}\n\n";
+
+        let result = parse_llamacpp_sse(raw);
+
+        assert_eq!(result.content, "Hello World");
+        assert!(result.is_complete);
+        assert_eq!(result.event_count, 3);
+    }
+}
@@ -1,32 +1,79 @@
 //! LLM Provider abstraction for multi-provider support
 
 mod anthropic;
+mod gemini;
+mod llamacpp;
+mod ollama;
 mod openai;
 
 pub use anthropic::{AnthropicProvider, parse_anthropic_sse};
-pub use openai::OpenAiProvider;
+pub use gemini::{GeminiProvider, parse_gemini_sse};
+pub use llamacpp::{LlamaCppProvider, parse_llamacpp_sse};
+pub use ollama::{OllamaProvider, parse_ollama_ndjson};
+pub use openai::{OpenAiApi, OpenAiProvider, azure_deployment, parse_responses_sse};
 
 use crate::error::Result;
 use crate::memory::retrieval::RetrievedMemory;
-use crate::proxy::streaming::ExtractedContent;
+use crate::memory::tombstone::Tombstone;
+use crate::proxy::streaming::{ExtractedContent, ToolCall};
+use crate::proxy::{InjectionBudget, InjectionPlan, Provider, plan_injection};
 use serde_json::Value;
+use url::Url;
+
+/// Get the `LLMProvider` implementation for a detected provider
+///
+/// The target URL and request body select between API variants of the same
+/// provider, such as OpenAI Chat Completions and Responses. Returns `None` for
+/// `Provider::Unknown`, whose requests are passed through without memory
+/// injection or capture.
+pub fn llm_provider_for(
+    provider: Provider,
+    url: &Url,
+    body: &Value,
+) -> Option<Box<dyn LLMProvider + Send + Sync>> {
+    match provider {
+        Provider::OpenAI => Some(Box::new(OpenAiProvider::with_api(OpenAiApi::detect(
+            url, body,
+        )))),
+        Provider::Anthropic => Some(Box::new(AnthropicProvider::new())),
+        Provider::Gemini => Some(Box::new(GeminiProvider::new())),
+        Provider::Ollama => Some(Box::new(OllamaProvider::new())),
+        Provider::LlamaCpp => Some(Box::new(LlamaCppProvider::new())),
+        Provider::Unknown => None,
+    }
+}
 
 /// Trait for LLM provider-specific operations
 ///
 /// Implementations handle the differences between providers like OpenAI and Anthropic
 /// in terms of request format, response parsing, and memory injection.
 pub trait LLMProvider {
-    /// Inject memories into the request body
+    /// Inject a planned memory block into the request body
     ///
-    /// Modifies the request body in-place to include the memory context.
-    /// For OpenAI: appends to system message in messages array
+    /// Modifies the request body in-place to include the plan's block of
+    /// memories and tombstones. Does nothing if the block is empty.
+    /// For OpenAI: appends to system message in messages array, or to
+    /// `instructions` for the Responses API
     /// For Anthropic: appends to top-level system field
+    /// For Gemini: appends a part to systemInstruction
+    /// For Ollama: appends to the system message or `system` field
+    /// For llama.cpp: prepends to the raw prompt
+    fn inject_plan(&self, request_body: &mut Value, plan: &InjectionPlan) -> Result<()>;
+
+    /// Inject memories into the request body
+    ///
+    /// Plans what fits the budget with [`plan_injection`] and injects it with
+    /// [`inject_plan`](Self::inject_plan). Callers that also need the plan
+    /// should build it once and call `inject_plan` directly.
     fn inject_memories(
         &self,
         request_body: &mut Value,
         memories: &[RetrievedMemory],
-        max_tokens: usize,
-    ) -> Result<()>;
+        tombstones: &[Tombstone],
+        budget: InjectionBudget,
+    ) -> Result<()> {
+        self.inject_plan(request_body, &plan_injection(memories, tombstones, budget))
+    }
 
     /// Extract user query from request for memory retrieval
     ///
@@ -34,13 +81,70 @@ pub trait LLMProvider {
     /// to search for relevant memories.
     fn extract_user_query(&self, request_body: &Value) -> Option<String>;
 
+    /// Extract the text of the last `max_turns` user and assistant turns
+    ///
+    /// Turns are returned oldest first and feed context-aware retrieval
+    /// queries. Providers without a turn history return only the user query.
+    fn extract_recent_turns(&self, request_body: &Value, _max_turns: usize) -> Vec<String> {
+        self.extract_user_query(request_body).into_iter().collect()
+    }
+
+    /// Extract the last user message for capture into memory
+    ///
+    /// Unlike [`extract_user_query`](Self::extract_user_query), non-text parts
+    /// such as images and files are kept as placeholders, so a captured turn
+    /// records what was attached.
+    fn extract_user_message(&self, request_body: &Value) -> Option<String> {
+        self.extract_user_query(request_body)
+    }
+
     /// Parse SSE stream events and extract text content
     ///
     /// Parses provider-specific SSE format and extracts text content.
+    /// Providers that stream NDJSON, like Ollama, parse that here instead.
     fn parse_sse_content(&self, raw_sse: &str) -> ExtractedContent;
 
     /// Parse non-streaming response and extract text content
     ///
     /// Extracts the assistant's text response from a non-streaming JSON response.
     fn parse_response_content(&self, response_body: &Value) -> Option<String>;
+
+    /// Parse non-streaming response and extract tool calls
+    ///
+    /// Providers without tool support return no calls.
+    fn parse_response_tool_calls(&self, _response_body: &Value) -> Vec<ToolCall> {
+        Vec::new()
+    }
+
+    /// Extract the tool results the client is sending back in this request
+    ///
+    /// Only results after the last assistant turn are returned, each paired
+    /// with the call that produced it earlier in the conversation, so a result
+    /// is captured once rather than on every later request.
+    fn extract_tool_results(&self, _request_body: &Value) -> Vec<ToolCall> {
+        Vec::new()
+    }
+}
+
+/// Fixtures shared by the provider tests
+#[cfg(test)]
+pub(crate) mod test_fixtures {
+    use crate::memory::retrieval::RetrievedMemory;
+    use crate::memory::types::{Memory, MemorySource, MemoryType};
+
+    /// A semantic memory with fixed retrieval scores
+    pub fn create_retrieved_memory() -> RetrievedMemory {
+        let memory = Memory::new(
+            "Test memory".to_string(),
+            vec![0.0; 384],
+            MemoryType::Semantic,
+            MemorySource::Manual,
+        );
+        RetrievedMemory {
+            memory,
+            similarity_score: 0.9,
+            effective_weight: 0.8,
+            final_score: 0.85,
+        }
+    }
 }
@@ -0,0 +1,345 @@
+use crate::error::{MnemoError, Result};
+use crate::proxy::providers::LLMProvider;
+use crate::proxy::streaming::ExtractedContent;
+use crate::proxy::{
+    InjectionPlan, extract_recent_turns, extract_user_query as do_extract_query, inject_block,
+};
+use serde_json::Value;
+
+/// Ollama's native `/api/chat` and `/api/generate` endpoints
+///
+/// Chat requests carry a `messages` array like OpenAI; generate requests carry
+/// a single `prompt` with an optional `system` field. Both stream NDJSON.
+pub struct OllamaProvider;
+
+impl OllamaProvider {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for OllamaProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LLMProvider for OllamaProvider {
+    fn inject_plan(&self, request_body: &mut Value, plan: &InjectionPlan) -> Result<()> {
+        if request_body.get("messages").is_some() {
+            return inject_block(request_body, &plan.block);
+        }
+
+        if plan.block.is_empty() {
+            return Ok(());
+        }
+        let memory_block = plan.block.clone();
+
+        let obj = request_body
+            .as_object_mut()
+            .ok_or_else(|| MnemoError::Proxy("Request body is not an object".into()))?;
+
+        // Raw prompts bypass the model template, so `system` would be ignored
+        let raw = obj.get("raw").and_then(|r| r.as_bool()).unwrap_or(false);
+        if raw {
+            let prompt = obj
+                .get_mut("prompt")
+                .and_then(|p| match p {
+                    Value::String(s) => Some(s),
+                    _ => None,
+                })
+                .ok_or_else(|| MnemoError::Proxy("Prompt field is not a string".into()))?;
+            *prompt = format!("{memory_block}\n\n{prompt}");
+            return Ok(());
+        }
+
+        match obj.get_mut("system") {
+            Some(Value::String(system)) if !system.is_empty() => {
+                system.push_str("\n\n");
+                system.push_str(&memory_block);
+            }
+            Some(Value::String(_)) | Some(Value::Null) | None => {
+                obj.insert("system".to_string(), Value::String(memory_block));
+            }
+            Some(_) => {
+                return Err(MnemoError::Proxy("System field is not a string".into()));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn extract_user_query(&self, request_body: &Value) -> Option<String> {
+        if request_body.get("messages").is_some() {
+            return do_extract_query(request_body);
+        }
+
+        request_body
+            .get("prompt")?
+            .as_str()
+            .filter(|p| !p.is_empty())
+            .map(|p| p.to_string())
+    }
+
+    fn extract_recent_turns(&self, request_body: &Value, max_turns: usize) -> Vec<String> {
+        if request_body.get("messages").is_some() {
+            return extract_recent_turns(request_body, max_turns);
+        }
+
+        self.extract_user_query(request_body).into_iter().collect()
+    }
+
+    fn extract_user_message(&self, request_body: &Value) -> Option<String> {
+        // Images travel base64-encoded beside the text rather than as parts
+        let (text, images) = match request_body.get("messages") {
+            Some(messages) => {
+                let message = messages
+                    .as_array()?
+                    .iter()
+                    .rev()
+                    .find(|m| m.get("role").and_then(|r| r.as_str()) == Some("user"))?;
+                (message.get("content"), message.get("images"))
+            }
+            None => (request_body.get("prompt"), request_body.get("images")),
+        };
+
+        let image_count = images.and_then(|i| i.as_array()).map_or(0, |i| i.len());
+        let pieces: Vec<String> = text
+            .and_then(|t| t.as_str())
+            .filter(|t| !t.is_empty())
+            .map(|t| t.to_string())
+            .into_iter()
+            .chain(std::iter::repeat_n("[image]".to_string(), image_count))
+            .collect();
+
+        if pieces.is_empty() {
+            None
+        } else {
+            Some(pieces.join("\n"))
+        }
+    }
+
+    fn parse_sse_content(&self, raw_sse: &str) -> ExtractedContent {
+        parse_ollama_ndjson(raw_sse)
+    }
+
+    fn parse_response_content(&self, response_body: &Value) -> Option<String> {
+        chunk_text(response_body).filter(|content| !content.is_empty())
+    }
+}
+
+/// Text of a chat (`message.content`) or generate (`response`) chunk
+fn chunk_text(chunk: &Value) -> Option<String> {
+    chunk
+        .get("message")
+        .and_then(|m| m.get("content"))
+        .or_else(|| chunk.get("response"))
+        .and_then(|c| c.as_str())
+        .map(|s| s.to_string())
+}
+
+/// Parse an Ollama NDJSON stream
+///
+/// Each line is a JSON object carrying the next chunk of text; the final line
+/// has `"done": true`.
+pub fn parse_ollama_ndjson(raw: &str) -> ExtractedContent {
+    let mut content = String::new();
+    let mut is_complete = false;
+    let mut event_count = 0;
+
+    for line in raw.lines().map(str::trim).filter(|l| !l.is_empty()) {
+        let Ok(json) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        event_count += 1;
+
+        if let Some(text) = chunk_text(&json) {
+            content.push_str(&text);
+        }
+
+        if json.get("done").and_then(|d| d.as_bool()).unwrap_or(false) {
+            is_complete = true;
+        }
+    }
+
+    ExtractedContent {
+        content,
+        tool_calls: Vec::new(),
+        is_complete,
+        event_count,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proxy::InjectionBudget;
+    use crate::proxy::providers::test_fixtures::create_retrieved_memory;
+
+    #[test]
+    fn test_ollama_chat_inject_into_system_message() {
+        let provider = OllamaProvider::new();
+        let mut request = serde_json::json!({
+            "model": "llama3.2",
+            "messages": [{"role": "user", "content": "Hello"}]
+        });
+
+        provider
+            .inject_memories(
+                &mut request,
+                &[create_retrieved_memory()],
+                &[],
+                InjectionBudget::new(2000),
+            )
+            .unwrap();
+
+        assert_eq!(request["messages"][0]["role"], "system");
+        assert!(
+            request["messages"][0]["content"]
+                .as_str()
+                .unwrap()
+                .contains("<mnemo-memories>")
+        );
+    }
+
+    #[test]
+    fn test_ollama_generate_inject_appends_to_system() {
+        let provider = OllamaProvider::new();
+        let mut request = serde_json::json!({
+            "model": "llama3.2",
+            "system": "You are helpful.",
+            "prompt": "Hello"
+        });
+
+        provider
+            .inject_memories(
+                &mut request,
+                &[create_retrieved_memory()],
+                &[],
+                InjectionBudget::new(2000),
+            )
+            .unwrap();
+
+        let system = request["system"].as_str().unwrap();
+        assert!(system.starts_with("You are helpful.\n\n"));
+        assert!(system.contains("<mnemo-memories>"));
+        assert_eq!(request["prompt"], "Hello");
+    }
+
+    #[test]
+    fn test_ollama_generate_raw_inject_prepends_to_prompt() {
+        let provider = OllamaProvider::new();
+        let mut request = serde_json::json!({
+            "model": "llama3.2",
+            "raw": true,
+            "prompt": "Hello"
+        });
+
+        provider
+            .inject_memories(
+                &mut request,
+                &[create_retrieved_memory()],
+                &[],
+                InjectionBudget::new(2000),
+            )
+            .unwrap();
+
+        let prompt = request["prompt"].as_str().unwrap();
+        assert!(prompt.starts_with("<mnemo-memories>"));
+        assert!(prompt.ends_with("\n\nHello"));
+        assert!(request.get("system").is_none());
+    }
+
+    #[test]
+    fn test_ollama_extract_query() {
+        let provider = OllamaProvider::new();
+        let chat = serde_json::json!({
+            "messages": [
+                {"role": "user", "content": "First question"},
+                {"role": "assistant", "content": "First answer"},
+                {"role": "user", "content": "Second question"}
+            ]
+        });
+        let generate = serde_json::json!({"prompt": "Why is the sky blue?"});
+
+        assert_eq!(
+            provider.extract_user_query(&chat),
+            Some("Second question".to_string())
+        );
+        assert_eq!(
+            provider.extract_user_query(&generate),
+            Some("Why is the sky blue?".to_string())
+        );
+    }
+
+    #[test]
+    fn test_ollama_extract_message_with_images() {
+        let provider = OllamaProvider::new();
+        let chat = serde_json::json!({
+            "messages": [
+                {"role": "user", "content": "What is in this picture?", "images": ["iVBORw0KGgo"]}
+            ]
+        });
+        let generate = serde_json::json!({"prompt": "", "images": ["iVBOR", "/9j/4AAQ"]});
+
+        assert_eq!(
+            provider.extract_user_query(&chat),
+            Some("What is in this picture?".to_string())
+        );
+        assert_eq!(
+            provider.extract_user_message(&chat),
+            Some("What is in this picture?\n[image]".to_string())
+        );
+        assert_eq!(
+            provider.extract_user_message(&generate),
+            Some("[image]\n[image]".to_string())
+        );
+    }
+
+    #[test]
+    fn test_ollama_parse_response_content() {
+        let provider = OllamaProvider::new();
+        let chat = serde_json::json!({
+            "model": "llama3.2",
+            "message": {"role": "assistant", "content": "Hello World"},
+            "done": true
+        });
+        let generate = serde_json::json!({
+            "model": "llama3.2",
+            "response": "Hello World",
+            "done": true
+        });
+
+        assert_eq!(
+            provider.parse_response_content(&chat),
+            Some("Hello World".to_string())
+        );
+        assert_eq!(
+            provider.parse_response_content(&generate),
+            Some("Hello World".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_ollama_ndjson_chat() {
+        let raw = r#"{"model":"llama3.2","message":{"role":"assistant","content":"Hello"},"done":false}
+{"model":"llama3.2","message":{"role":"assistant","content":" World"},"done":false}
+{"model":"llama3.2","message":{"role":"assistant","content":""},"done":true,"done_reason":"stop"}
+"#;
+        let result = parse_ollama_ndjson(raw);
+
+        assert_eq!(result.content, "Hello World");
+        assert!(result.is_complete);
+        assert_eq!(result.event_count, 3);
+    }
+
+    #[test]
+    fn test_parse_ollama_ndjson_generate_incomplete() {
+        let raw = "{\"model\":\"llama3.2\",\"response\":\"Hel\",\"done\":false}\n";
+
+        let result = parse_ollama_ndjson(raw);
+
+        assert_eq!(result.content, "Hel");
+        assert!(!result.is_complete);
+    }
+}
@@ -1,16 +1,73 @@
-use crate::error::Result;
-use crate::memory::retrieval::RetrievedMemory;
+use crate::error::{MnemoError, Result};
 use crate::proxy::providers::LLMProvider;
-use crate::proxy::streaming::ExtractedContent;
-use crate::proxy::streaming::StreamingProxy;
-use crate::proxy::{extract_user_query as do_extract_query, inject_memories as do_inject_memories};
+use crate::proxy::streaming::{ExtractedContent, SseEvent, StreamingProxy, ToolCall};
+use crate::proxy::{
+    InjectionPlan, content_text, content_with_placeholders, extract_recent_turns,
+    extract_user_message, extract_user_query as do_extract_query, inject_block,
+};
 use serde_json::Value;
+use url::Url;
 
-pub struct OpenAiProvider;
+/// Which OpenAI API a request targets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OpenAiApi {
+    /// `/v1/chat/completions` with a `messages` array
+    #[default]
+    ChatCompletions,
+    /// `/v1/responses` with `instructions` and `input` items
+    Responses,
+}
+
+impl OpenAiApi {
+    /// Detect the API from the request path, falling back to the body shape
+    pub fn detect(url: &Url, body: &Value) -> Self {
+        let path = url.path().trim_end_matches('/');
+        if path.ends_with("/responses") {
+            return OpenAiApi::Responses;
+        }
+        if path.ends_with("/chat/completions") {
+            return OpenAiApi::ChatCompletions;
+        }
+
+        if body.get("input").is_some() && body.get("messages").is_none() {
+            OpenAiApi::Responses
+        } else {
+            OpenAiApi::ChatCompletions
+        }
+    }
+}
+
+/// Deployment name of an Azure OpenAI request
+///
+/// Azure routes by deployment rather than model, under
+/// `/openai/deployments/{name}/...` on the resource host.
+pub fn azure_deployment(url: &Url) -> Option<String> {
+    let mut segments = url.path_segments()?;
+    while let Some(segment) = segments.next() {
+        if segment == "openai" {
+            return match (segments.next(), segments.next()) {
+                (Some("deployments"), Some(name)) if !name.is_empty() => Some(name.to_string()),
+                _ => None,
+            };
+        }
+    }
+    None
+}
+
+pub struct OpenAiProvider {
+    api: OpenAiApi,
+}
 
 impl OpenAiProvider {
     pub fn new() -> Self {
-        Self
+        Self {
+            api: OpenAiApi::ChatCompletions,
+        }
+    }
+
+    /// Create a provider for a specific OpenAI API
+    pub fn with_api(api: OpenAiApi) -> Self {
+        Self { api }
     }
 }
 
@@ -21,37 +78,368 @@ impl Default for OpenAiProvider {
 }
 
 impl LLMProvider for OpenAiProvider {
-    fn inject_memories(
-        &self,
-        request_body: &mut Value,
-        memories: &[RetrievedMemory],
-        max_tokens: usize,
-    ) -> Result<()> {
-        do_inject_memories(request_body, memories, max_tokens)
+    fn inject_plan(&self, request_body: &mut Value, plan: &InjectionPlan) -> Result<()> {
+        match self.api {
+            OpenAiApi::ChatCompletions => inject_block(request_body, &plan.block),
+            OpenAiApi::Responses => inject_instructions(request_body, &plan.block),
+        }
     }
 
     fn extract_user_query(&self, request_body: &Value) -> Option<String> {
-        do_extract_query(request_body)
+        match self.api {
+            OpenAiApi::ChatCompletions => do_extract_query(request_body),
+            OpenAiApi::Responses => responses_user_content(request_body).and_then(content_text),
+        }
+    }
+
+    fn extract_recent_turns(&self, request_body: &Value, max_turns: usize) -> Vec<String> {
+        match self.api {
+            OpenAiApi::ChatCompletions => extract_recent_turns(request_body, max_turns),
+            OpenAiApi::Responses => responses_recent_turns(request_body, max_turns),
+        }
+    }
+
+    fn extract_user_message(&self, request_body: &Value) -> Option<String> {
+        match self.api {
+            OpenAiApi::ChatCompletions => extract_user_message(request_body),
+            OpenAiApi::Responses => {
+                responses_user_content(request_body).and_then(content_with_placeholders)
+            }
+        }
     }
 
     fn parse_sse_content(&self, raw_sse: &str) -> ExtractedContent {
-        StreamingProxy::extract_response_content(raw_sse)
+        match self.api {
+            OpenAiApi::ChatCompletions => StreamingProxy::extract_response_content(raw_sse),
+            OpenAiApi::Responses => parse_responses_sse(raw_sse),
+        }
     }
 
     fn parse_response_content(&self, response_body: &Value) -> Option<String> {
-        response_body
-            .get("choices")?
-            .get(0)?
-            .get("message")?
-            .get("content")?
-            .as_str()
-            .map(|s| s.to_string())
+        match self.api {
+            OpenAiApi::ChatCompletions => response_body
+                .get("choices")?
+                .get(0)?
+                .get("message")?
+                .get("content")?
+                .as_str()
+                .map(|s| s.to_string()),
+            OpenAiApi::Responses => responses_output_text(response_body),
+        }
+    }
+
+    fn parse_response_tool_calls(&self, response_body: &Value) -> Vec<ToolCall> {
+        match self.api {
+            OpenAiApi::ChatCompletions => response_body
+                .pointer("/choices/0/message/tool_calls")
+                .and_then(|c| c.as_array())
+                .map(|calls| calls.iter().filter_map(chat_tool_call).collect()),
+            OpenAiApi::Responses => response_body
+                .get("output")
+                .and_then(|o| o.as_array())
+                .map(|items| items.iter().filter_map(responses_function_call).collect()),
+        }
+        .unwrap_or_default()
+    }
+
+    fn extract_tool_results(&self, request_body: &Value) -> Vec<ToolCall> {
+        match self.api {
+            OpenAiApi::ChatCompletions => chat_tool_results(request_body),
+            OpenAiApi::Responses => responses_tool_results(request_body),
+        }
+    }
+}
+
+/// Parse a Chat Completions `tool_calls` entry
+fn chat_tool_call(call: &Value) -> Option<ToolCall> {
+    let function = call.get("function")?;
+
+    Some(ToolCall {
+        id: call
+            .get("id")
+            .and_then(|i| i.as_str())
+            .map(|s| s.to_string()),
+        name: function.get("name")?.as_str()?.to_string(),
+        arguments: function
+            .get("arguments")
+            .and_then(|a| a.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        result: None,
+    })
+}
+
+/// Parse a Responses API `function_call` output item
+fn responses_function_call(item: &Value) -> Option<ToolCall> {
+    if item.get("type").and_then(|t| t.as_str()) != Some("function_call") {
+        return None;
+    }
+
+    Some(ToolCall {
+        id: item
+            .get("call_id")
+            .and_then(|i| i.as_str())
+            .map(|s| s.to_string()),
+        name: item.get("name")?.as_str()?.to_string(),
+        arguments: item
+            .get("arguments")
+            .and_then(|a| a.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        result: None,
+    })
+}
+
+/// Text of a tool message, which is a string or a list of text parts
+fn tool_message_text(content: &Value) -> Option<String> {
+    if let Some(s) = content.as_str() {
+        return Some(s.to_string());
+    }
+
+    let text: Vec<&str> = content
+        .as_array()?
+        .iter()
+        .filter_map(|part| part.get("text").and_then(|t| t.as_str()))
+        .collect();
+
+    Some(text.join("\n"))
+}
+
+/// Pair the trailing `tool` messages of a Chat Completions request with the
+/// assistant `tool_calls` they answer
+fn chat_tool_results(request_body: &Value) -> Vec<ToolCall> {
+    let Some(messages) = request_body.get("messages").and_then(|m| m.as_array()) else {
+        return Vec::new();
+    };
+
+    let calls: Vec<ToolCall> = messages
+        .iter()
+        .filter_map(|m| m.get("tool_calls").and_then(|t| t.as_array()))
+        .flatten()
+        .filter_map(chat_tool_call)
+        .collect();
+
+    messages
+        .iter()
+        .rev()
+        .take_while(|m| m.get("role").and_then(|r| r.as_str()) == Some("tool"))
+        .filter_map(|m| {
+            let id = m.get("tool_call_id")?.as_str()?;
+            let call = calls.iter().find(|c| c.id.as_deref() == Some(id))?;
+            Some(ToolCall {
+                result: tool_message_text(m.get("content")?),
+                ..call.clone()
+            })
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect()
+}
+
+/// Pair the trailing `function_call_output` items of a Responses API request
+/// with the `function_call` items they answer
+///
+/// Requests chained with `previous_response_id` omit the calls, so their
+/// results can't be attributed to a tool and are skipped.
+fn responses_tool_results(request_body: &Value) -> Vec<ToolCall> {
+    let Some(items) = request_body.get("input").and_then(|i| i.as_array()) else {
+        return Vec::new();
+    };
+
+    let calls: Vec<ToolCall> = items.iter().filter_map(responses_function_call).collect();
+
+    items
+        .iter()
+        .rev()
+        .take_while(|item| {
+            item.get("type").and_then(|t| t.as_str()) == Some("function_call_output")
+        })
+        .filter_map(|item| {
+            let id = item.get("call_id")?.as_str()?;
+            let call = calls.iter().find(|c| c.id.as_deref() == Some(id))?;
+            Some(ToolCall {
+                result: item.get("output").and_then(tool_message_text),
+                ..call.clone()
+            })
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect()
+}
+
+/// Append the injection block to the Responses API `instructions` field
+fn inject_instructions(request_body: &mut Value, memory_block: &str) -> Result<()> {
+    if memory_block.is_empty() {
+        return Ok(());
+    }
+
+    let obj = request_body
+        .as_object_mut()
+        .ok_or_else(|| MnemoError::Proxy("Request body is not an object".into()))?;
+
+    match obj.get_mut("instructions") {
+        Some(Value::String(instructions)) if !instructions.is_empty() => {
+            instructions.push_str("\n\n");
+            instructions.push_str(memory_block);
+        }
+        Some(Value::String(_)) | Some(Value::Null) | None => {
+            obj.insert(
+                "instructions".to_string(),
+                Value::String(memory_block.to_string()),
+            );
+        }
+        Some(_) => {
+            return Err(MnemoError::Proxy(
+                "Instructions field is not a string".into(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Content of the last user input in a Responses API request
+///
+/// `input` is either a plain string, returned as is, or a list of items. User
+/// messages carry their content as a string or as `input_*` parts.
+fn responses_user_content(request_body: &Value) -> Option<&Value> {
+    let input = request_body.get("input")?;
+
+    if input.is_string() {
+        return Some(input);
+    }
+
+    input
+        .as_array()?
+        .iter()
+        .rev()
+        .find(|item| item.get("role").and_then(|r| r.as_str()) == Some("user"))?
+        .get("content")
+}
+
+/// Text of the last `max_turns` user and assistant messages in a Responses
+/// API request, oldest first
+fn responses_recent_turns(request_body: &Value, max_turns: usize) -> Vec<String> {
+    let Some(input) = request_body.get("input") else {
+        return Vec::new();
+    };
+
+    if let Some(s) = input.as_str() {
+        return vec![s.to_string()];
+    }
+
+    let mut turns: Vec<String> = input
+        .as_array()
+        .into_iter()
+        .flatten()
+        .rev()
+        .filter(|item| {
+            matches!(
+                item.get("role").and_then(|r| r.as_str()),
+                Some("user") | Some("assistant")
+            )
+        })
+        .filter_map(|item| item.get("content").and_then(content_text))
+        .take(max_turns)
+        .collect();
+    turns.reverse();
+    turns
+}
+
+/// Concatenate the `output_text` parts of assistant messages in a response
+fn responses_output_text(response_body: &Value) -> Option<String> {
+    let text: String = response_body
+        .get("output")?
+        .as_array()?
+        .iter()
+        .filter(|item| item.get("type").and_then(|t| t.as_str()) == Some("message"))
+        .filter_map(|item| item.get("content").and_then(|c| c.as_array()))
+        .flatten()
+        .filter(|part| part.get("type").and_then(|t| t.as_str()) == Some("output_text"))
+        .filter_map(|part| part.get("text").and_then(|t| t.as_str()))
+        .collect();
+
+    if text.is_empty() { None } else { Some(text) }
+}
+
+/// Parse a Responses API event stream
+///
+/// Text arrives in `response.output_text.delta` events and function calls in
+/// `response.output_item.done` events; the stream is complete once
+/// `response.completed` is received.
+pub fn parse_responses_sse(raw: &str) -> ExtractedContent {
+    let events = StreamingProxy::parse_sse_events(raw);
+    let mut content = String::new();
+    let mut tool_calls = Vec::new();
+    let mut is_complete = false;
+
+    for event in &events {
+        let SseEvent::Data(data) = event else {
+            continue;
+        };
+        let Ok(json) = serde_json::from_str::<Value>(data) else {
+            continue;
+        };
+
+        match json.get("type").and_then(|t| t.as_str()) {
+            Some("response.output_text.delta") => {
+                if let Some(delta) = json.get("delta").and_then(|d| d.as_str()) {
+                    content.push_str(delta);
+                }
+            }
+            // Completed items carry the full arguments, so the argument
+            // deltas before them don't need to be reassembled
+            Some("response.output_item.done") => {
+                if let Some(call) = json.get("item").and_then(responses_function_call) {
+                    tool_calls.push(call);
+                }
+            }
+            Some("response.completed") => is_complete = true,
+            _ => {}
+        }
+    }
+
+    ExtractedContent {
+        content,
+        tool_calls,
+        is_complete,
+        event_count: events.len(),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::proxy::InjectionBudget;
+    use crate::proxy::providers::test_fixtures::create_retrieved_memory;
+
+    #[test]
+    fn test_azure_deployment() {
+        let url = Url::parse(
+            "https://my-resource.openai.azure.com/openai/deployments/gpt-4o/chat/completions?api-version=2024-10-21",
+        )
+        .unwrap();
+        assert_eq!(azure_deployment(&url), Some("gpt-4o".to_string()));
+
+        let url = Url::parse("https://api.openai.com/v1/chat/completions").unwrap();
+        assert_eq!(azure_deployment(&url), None);
+
+        let url = Url::parse("https://my-resource.openai.azure.com/openai/v1/responses").unwrap();
+        assert_eq!(azure_deployment(&url), None);
+    }
+
+    #[test]
+    fn test_azure_chat_completions_api() {
+        let url = Url::parse(
+            "https://my-resource.openai.azure.com/openai/deployments/gpt-4o/chat/completions?api-version=2024-10-21",
+        )
+        .unwrap();
+        let body = serde_json::json!({"messages": [{"role": "user", "content": "Hi"}]});
+
+        assert_eq!(OpenAiApi::detect(&url, &body), OpenAiApi::ChatCompletions);
+    }
 
     #[test]
     fn test_openai_parse_response_content() {
@@ -80,4 +468,288 @@ mod tests {
 
         assert_eq!(provider.parse_response_content(&response), None);
     }
+
+    #[test]
+    fn test_detect_api_from_path() {
+        let body = serde_json::json!({});
+        let responses = Url::parse("https://api.openai.com/v1/responses").unwrap();
+        let chat = Url::parse("https://api.openai.com/v1/chat/completions").unwrap();
+
+        assert_eq!(OpenAiApi::detect(&responses, &body), OpenAiApi::Responses);
+        assert_eq!(OpenAiApi::detect(&chat, &body), OpenAiApi::ChatCompletions);
+    }
+
+    #[test]
+    fn test_detect_api_from_body() {
+        let url = Url::parse("https://gateway.example.com/openai").unwrap();
+
+        let responses = serde_json::json!({"model": "gpt-4o", "input": "Hello"});
+        let chat = serde_json::json!({"model": "gpt-4o", "messages": []});
+
+        assert_eq!(OpenAiApi::detect(&url, &responses), OpenAiApi::Responses);
+        assert_eq!(OpenAiApi::detect(&url, &chat), OpenAiApi::ChatCompletions);
+    }
+
+    #[test]
+    fn test_responses_inject_appends_to_instructions() {
+        let provider = OpenAiProvider::with_api(OpenAiApi::Responses);
+        let mut request = serde_json::json!({
+            "model": "gpt-4o",
+            "instructions": "You are helpful.",
+            "input": "Hello"
+        });
+
+        provider
+            .inject_memories(
+                &mut request,
+                &[create_retrieved_memory()],
+                &[],
+                InjectionBudget::new(2000),
+            )
+            .unwrap();
+
+        let instructions = request["instructions"].as_str().unwrap();
+        assert!(instructions.starts_with("You are helpful.\n\n"));
+        assert!(instructions.contains("<mnemo-memories>"));
+        assert!(request.get("messages").is_none());
+    }
+
+    #[test]
+    fn test_responses_inject_creates_instructions() {
+        let provider = OpenAiProvider::with_api(OpenAiApi::Responses);
+        let mut request = serde_json::json!({"model": "gpt-4o", "input": "Hello"});
+
+        provider
+            .inject_memories(
+                &mut request,
+                &[create_retrieved_memory()],
+                &[],
+                InjectionBudget::new(2000),
+            )
+            .unwrap();
+
+        assert!(
+            request["instructions"]
+                .as_str()
+                .unwrap()
+                .starts_with("<mnemo-memories>")
+        );
+    }
+
+    #[test]
+    fn test_responses_extract_query_string_input() {
+        let provider = OpenAiProvider::with_api(OpenAiApi::Responses);
+        let request = serde_json::json!({"input": "Hello"});
+
+        assert_eq!(
+            provider.extract_user_query(&request),
+            Some("Hello".to_string())
+        );
+    }
+
+    #[test]
+    fn test_responses_extract_query_from_items() {
+        let provider = OpenAiProvider::with_api(OpenAiApi::Responses);
+        let request = serde_json::json!({
+            "input": [
+                {"role": "user", "content": "First question"},
+                {"type": "message", "role": "assistant", "content": [
+                    {"type": "output_text", "text": "First answer"}
+                ]},
+                {"type": "message", "role": "user", "content": [
+                    {"type": "input_image", "image_url": "https://example.com/cat.png"},
+                    {"type": "input_text", "text": "What is this?"}
+                ]},
+                {"type": "function_call_output", "call_id": "call_1", "output": "{}"}
+            ]
+        });
+
+        assert_eq!(
+            provider.extract_user_query(&request),
+            Some("What is this?".to_string())
+        );
+        assert_eq!(
+            provider.extract_user_message(&request),
+            Some("[image: example.com/cat.png]\nWhat is this?".to_string())
+        );
+        assert_eq!(
+            provider.extract_recent_turns(&request, 2),
+            vec!["First answer".to_string(), "What is this?".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_responses_parse_response_content() {
+        let provider = OpenAiProvider::with_api(OpenAiApi::Responses);
+        let response = serde_json::json!({
+            "object": "response",
+            "output": [
+                {"type": "reasoning", "summary": []},
+                {"type": "message", "role": "assistant", "content": [
+                    {"type": "output_text", "text": "Hello", "annotations": []},
+                    {"type": "output_text", "text": " World", "annotations": []}
+                ]}
+            ]
+        });
+
+        assert_eq!(
+            provider.parse_response_content(&response),
+            Some("Hello World".to_string())
+        );
+    }
+
+    #[test]
+    fn test_openai_parse_response_tool_calls() {
+        let provider = OpenAiProvider::new();
+        let response = serde_json::json!({
+            "choices": [{
+                "message": {
+                    "role": "assistant",
+                    "content": null,
+                    "tool_calls": [{
+                        "id": "call_1",
+                        "type": "function",
+                        "function": {"name": "get_weather", "arguments": "{\"city\":\"Paris\"}"}
+                    }]
+                },
+                "finish_reason": "tool_calls"
+            }]
+        });
+
+        let calls = provider.parse_response_tool_calls(&response);
+
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].id.as_deref(), Some("call_1"));
+        assert_eq!(calls[0].name, "get_weather");
+        assert_eq!(calls[0].arguments, r#"{"city":"Paris"}"#);
+        assert_eq!(provider.parse_response_content(&response), None);
+    }
+
+    #[test]
+    fn test_openai_extract_trailing_tool_results() {
+        let provider = OpenAiProvider::new();
+        let request = serde_json::json!({
+            "messages": [
+                {"role": "user", "content": "Weather in Paris and Rome?"},
+                {"role": "assistant", "content": null, "tool_calls": [
+                    {"id": "call_old", "type": "function",
+                     "function": {"name": "lookup", "arguments": "{}"}}
+                ]},
+                {"role": "tool", "tool_call_id": "call_old", "content": "stale"},
+                {"role": "assistant", "content": null, "tool_calls": [
+                    {"id": "call_1", "type": "function",
+                     "function": {"name": "get_weather", "arguments": "{\"city\":\"Paris\"}"}},
+                    {"id": "call_2", "type": "function",
+                     "function": {"name": "get_weather", "arguments": "{\"city\":\"Rome\"}"}}
+                ]},
+                {"role": "tool", "tool_call_id": "call_1", "content": "18C and sunny"},
+                {"role": "tool", "tool_call_id": "call_2", "content": [
+                    {"type": "text", "text": "22C and cloudy"}
+                ]}
+            ]
+        });
+
+        let results = provider.extract_tool_results(&request);
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].arguments, r#"{"city":"Paris"}"#);
+        assert_eq!(results[0].result.as_deref(), Some("18C and sunny"));
+        assert_eq!(results[1].arguments, r#"{"city":"Rome"}"#);
+        assert_eq!(results[1].result.as_deref(), Some("22C and cloudy"));
+    }
+
+    #[test]
+    fn test_openai_no_tool_results_after_user_turn() {
+        let provider = OpenAiProvider::new();
+        let request = serde_json::json!({
+            "messages": [
+                {"role": "assistant", "content": null, "tool_calls": [
+                    {"id": "call_1", "type": "function",
+                     "function": {"name": "lookup", "arguments": "{}"}}
+                ]},
+                {"role": "tool", "tool_call_id": "call_1", "content": "done"},
+                {"role": "assistant", "content": "All done"},
+                {"role": "user", "content": "Thanks"}
+            ]
+        });
+
+        assert!(provider.extract_tool_results(&request).is_empty());
+    }
+
+    #[test]
+    fn test_responses_tool_calls_and_results() {
+        let provider = OpenAiProvider::with_api(OpenAiApi::Responses);
+        let response = serde_json::json!({
+            "output": [{
+                "type": "function_call",
+                "id": "fc_1",
+                "call_id": "call_1",
+                "name": "get_weather",
+                "arguments": "{\"city\":\"Paris\"}"
+            }]
+        });
+        let request = serde_json::json!({
+            "input": [
+                {"role": "user", "content": "Weather in Paris?"},
+                {"type": "function_call", "call_id": "call_1", "name": "get_weather",
+                 "arguments": "{\"city\":\"Paris\"}"},
+                {"type": "function_call_output", "call_id": "call_1", "output": "18C"}
+            ]
+        });
+
+        let calls = provider.parse_response_tool_calls(&response);
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].id.as_deref(), Some("call_1"));
+
+        let results = provider.extract_tool_results(&request);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].name, "get_weather");
+        assert_eq!(results[0].result.as_deref(), Some("18C"));
+    }
+
+    #[test]
+    fn test_parse_responses_sse_function_call() {
+        let raw = r#"event: response.function_call_arguments.delta
+data: {"type":"response.function_call_arguments.delta","item_id":"fc_1","output_index":0,"delta":"{\"city\":"}
+
+event: response.output_item.done
+data: {"type":"response.output_item.done","output_index":0,"item":{"type":"function_call","id":"fc_1","call_id":"call_1","name":"get_weather","arguments":"{\"city\":\"Paris\"}"}}
+
+event: response.completed
+data: {"type":"response.completed","response":{"id":"resp_1","status":"completed"}}
+
+"#;
+        let result = parse_responses_sse(raw);
+
+        assert_eq!(result.content, "");
+        assert_eq!(result.tool_calls.len(), 1);
+        assert_eq!(result.tool_calls[0].name, "get_weather");
+        assert_eq!(result.tool_calls[0].arguments, r#"{"city":"Paris"}"#);
+        assert!(result.is_complete);
+    }
+
+    #[test]
+    fn test_parse_responses_sse() {
+        let raw = r#"event: response.created
+data: {"type":"response.created","response":{"id":"resp_1","status":"in_progress"}}
+
+event: response.output_text.delta
+data: {"type":"response.output_text.delta","item_id":"msg_1","output_index":0,"content_index":0,"delta":"Hello"}
+
+event: response.output_text.delta
+data: {"type":"response.output_text.delta","item_id":"msg_1","output_index":0,"content_index":0,"delta":" World"}
+
+event: response.output_text.done
+data: {"type":"response.output_text.done","item_id":"msg_1","output_index":0,"content_index":0,"text":"Hello World"}
+
+event: response.completed
+data: {"type":"response.completed","response":{"id":"resp_1","status":"completed"}}
+
+"#;
+        let result = parse_responses_sse(raw);
+
+        assert_eq!(result.content, "Hello World");
+        assert!(result.is_complete);
+        assert_eq!(result.event_count, 5);
+    }
 }
@@ -13,8 +13,11 @@ use axum::{
     response::Response,
     routing::{any, get},
 };
+use bytes::Bytes;
 use dashmap::DashMap;
+use futures::{Stream, StreamExt};
 use std::net::SocketAddr;
+use std::pin::Pin;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::net::TcpListener;
@@ -26,24 +29,48 @@ use uuid::Uuid;
 
 use chrono::Utc;
 
-use crate::admin::handlers::{events_handler, memories_handler, stats_handler};
-use crate::admin::{DaemonStats, ProxyEvent};
-use crate::config::{CuratorConfig, ProxyConfig, RouterConfig};
+use crate::admin::handlers::{
+    collect_daemon_stats, events_handler, memories_handler, metrics_handler, stats_handler,
+};
+use crate::admin::{
+    CurationOutcome, IngestionOutcome, InjectedMemory, ProxyEvent, StatsCollector,
+};
+use crate::config::{
+    CaptureConfig, CuratorConfig, IngestionConfig, MaintenanceConfig, ProxyConfig, RouterConfig,
+};
 use crate::curator::{ConversationBuffer, ConversationTurn, CuratorProvider, RemoteCurator, Role};
 use crate::embedding::EmbeddingModel;
 use crate::error::{MnemoError, Result};
+use crate::maintenance::MaintenanceScheduler;
 use crate::memory::ingestion::IngestionPipeline;
-use crate::memory::retrieval::RetrievalPipeline;
-use crate::memory::types::MemorySource;
+use crate::memory::injection_tracker::SessionInjectionTrackers;
+use crate::memory::query::{
+    DEFAULT_ROLLING_SESSIONS, QueryBuilder, RetrievalQuery, RollingQueryEmbeddings,
+};
+use crate::memory::retrieval::{RetrievalConfig, RetrievalPipeline};
+use crate::memory::tombstone::Tombstone;
+use crate::memory::types::{Memory, MemorySource};
 use crate::router::MemoryRouter;
 use crate::storage::LanceStore;
 use crate::storage::filter::MemoryFilter;
-use serde_json::Value;
+use crate::telemetry;
+use serde_json::{Map, Value};
 
+use super::injection::{InjectionBudget, InjectionPlan, plan_injection};
 use super::passthrough::UpstreamTarget;
-use super::provider::Provider;
-use super::providers::{AnthropicProvider, LLMProvider, OpenAiProvider};
+use super::provider::{Provider, Route};
+use super::providers::{azure_deployment, llm_provider_for};
 use super::session::SessionId;
+use super::streaming::{StreamAbort, StreamingProxy, TeeResult, ToolCall};
+
+/// Upstream response body as a boxed byte stream, ready to be teed
+type UpstreamByteStream = Pin<Box<dyn Stream<Item = std::io::Result<Bytes>> + Send>>;
+
+/// How long shutdown waits for an in-flight maintenance run to stop
+const MAINTENANCE_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Status recorded when the client disconnects before a streamed response ends
+const CLIENT_CLOSED_REQUEST: u16 = 499;
 
 /// Hop-by-hop headers that should not be forwarded to upstream
 const HOP_BY_HOP_HEADERS: &[&str] = &[
@@ -69,6 +96,10 @@ pub struct AppState {
     pub curator: Option<Arc<dyn CuratorProvider + Send + Sync>>,
     pub conversation_buffers: Arc<DashMap<String, ConversationBuffer>>,
     pub curator_config: Option<CuratorConfig>,
+    pub capture_config: CaptureConfig,
+    pub stats: Arc<StatsCollector>,
+    pub injection_trackers: Arc<SessionInjectionTrackers>,
+    pub rolling_queries: Arc<RollingQueryEmbeddings>,
 }
 
 pub struct ProxyServer {
@@ -78,6 +109,9 @@ pub struct ProxyServer {
     router: Arc<MemoryRouter>,
     router_config: RouterConfig,
     curator_config: Option<CuratorConfig>,
+    ingestion_config: IngestionConfig,
+    capture_config: CaptureConfig,
+    maintenance_config: Option<MaintenanceConfig>,
 }
 
 impl ProxyServer {
@@ -96,20 +130,44 @@ impl ProxyServer {
             router,
             router_config,
             curator_config,
+            ingestion_config: IngestionConfig::default(),
+            capture_config: CaptureConfig::default(),
+            maintenance_config: None,
         }
     }
 
+    /// Use the given ingestion configuration (near-duplicate merging)
+    pub fn with_ingestion(mut self, config: IngestionConfig) -> Self {
+        self.ingestion_config = config;
+        self
+    }
+
+    /// Use the given conversation capture configuration
+    pub fn with_capture(mut self, config: CaptureConfig) -> Self {
+        self.capture_config = config;
+        self
+    }
+
+    /// Run background maintenance (compaction, eviction, tier migration) while serving
+    pub fn with_maintenance(mut self, config: MaintenanceConfig) -> Self {
+        self.maintenance_config = Some(config);
+        self
+    }
+
     pub async fn serve(&self) -> Result<()> {
         let client = reqwest::Client::builder()
             .timeout(Duration::from_secs(self.config.timeout_secs))
             .build()
             .map_err(|e| MnemoError::Proxy(format!("Failed to create HTTP client: {e}")))?;
 
-        let ingestion_pipeline = Arc::new(TokioMutex::new(IngestionPipeline::new(
-            self.store.clone(),
-            self.embedding_model.clone(),
-            self.router.clone(),
-        )));
+        let ingestion_pipeline = Arc::new(TokioMutex::new(
+            IngestionPipeline::new(
+                self.store.clone(),
+                self.embedding_model.clone(),
+                self.router.clone(),
+            )
+            .with_config(self.ingestion_config.clone()),
+        ));
 
         let (event_tx, _) = broadcast::channel::<ProxyEvent>(1024);
 
@@ -170,6 +228,12 @@ impl ProxyServer {
                 None
             };
 
+        let injection_tracking = self
+            .curator_config
+            .as_ref()
+            .map(|config| config.injection_tracking.clone())
+            .unwrap_or_default();
+
         let app_state = Arc::new(AppState {
             config: self.config.clone(),
             client,
@@ -182,15 +246,31 @@ impl ProxyServer {
             curator,
             conversation_buffers: Arc::new(DashMap::new()),
             curator_config: self.curator_config.clone(),
+            capture_config: self.capture_config.clone(),
+            stats: Arc::new(StatsCollector::new()),
+            injection_trackers: Arc::new(SessionInjectionTrackers::new(injection_tracking)),
+            rolling_queries: Arc::new(RollingQueryEmbeddings::new(
+                self.router_config.query.rolling_alpha,
+                DEFAULT_ROLLING_SESSIONS,
+            )),
         });
 
+        let maintenance = match self.maintenance_config {
+            Some(ref config) => Some(
+                MaintenanceScheduler::new(self.store.clone(), config.clone(), event_tx.clone())
+                    .spawn()?,
+            ),
+            None => None,
+        };
+
+        let heartbeat_state = Arc::clone(&app_state);
         let app = create_router(app_state);
 
         tokio::spawn(async move {
             let mut interval = tokio::time::interval(Duration::from_secs(5));
             loop {
                 interval.tick().await;
-                let stats = DaemonStats::default();
+                let stats = collect_daemon_stats(&heartbeat_state).await;
                 let _ = event_tx.send(ProxyEvent::Heartbeat {
                     timestamp: Utc::now(),
                     stats,
@@ -224,10 +304,16 @@ impl ProxyServer {
             .await
             .map_err(|e| MnemoError::Proxy(format!("Failed to bind to {addr}: {e}")))?;
 
-        axum::serve(listener, app)
+        let served = axum::serve(listener, app)
             .with_graceful_shutdown(shutdown_signal())
-            .await
-            .map_err(|e| MnemoError::Proxy(format!("Server error: {e}")))?;
+            .await;
+
+        // Stop background maintenance before returning so no run outlives the server
+        if let Some(maintenance) = maintenance {
+            maintenance.shutdown(MAINTENANCE_SHUTDOWN_TIMEOUT).await;
+        }
+
+        served.map_err(|e| MnemoError::Proxy(format!("Server error: {e}")))?;
 
         tracing::info!("Proxy server shut down gracefully");
         Ok(())
@@ -236,8 +322,11 @@ impl ProxyServer {
 
 /// Create the router with all routes configured
 pub fn create_router(state: Arc<AppState>) -> Router {
+    telemetry::install();
+
     Router::new()
         .route("/health", get(health_handler))
+        .route("/metrics", get(metrics_handler))
         .route("/admin/events", get(events_handler))
         .route("/admin/stats", get(stats_handler))
         .route("/admin/memories", get(memories_handler))
@@ -290,7 +379,7 @@ async fn dynamic_proxy_handler(
 
     match forward_request(&state, &target.url, method, headers, body).await {
         Ok(response) => response,
-        Err(e) => e.into_response(),
+        Err(e) => error_response(&state, e),
     }
 }
 
@@ -351,16 +440,53 @@ async fn configured_proxy_handler(
 
     match forward_request(&state, &target_url, method, headers, body).await {
         Ok(response) => response,
-        Err(e) => e.into_response(),
+        Err(e) => error_response(&state, e),
+    }
+}
+
+/// Label for an upstream host in request metrics
+///
+/// Only the configured upstream and allowlisted hosts are reported by name,
+/// with wildcard allowlist entries reported as the pattern itself. Any other
+/// host is reported as "other" so the label set stays bounded.
+fn metrics_host(config: &ProxyConfig, target_url: &Url) -> String {
+    const OTHER_HOST: &str = "other";
+
+    let Some(host) = target_url.host_str() else {
+        return OTHER_HOST.to_string();
+    };
+
+    let upstream = config
+        .upstream_url
+        .as_deref()
+        .and_then(|url| Url::parse(url).ok());
+    if upstream.as_ref().and_then(|url| url.host_str()) == Some(host) {
+        return host.to_string();
     }
+
+    config
+        .allowed_hosts
+        .iter()
+        .find(|pattern| UpstreamTarget::host_matches_pattern(host, pattern))
+        .cloned()
+        .unwrap_or_else(|| OTHER_HOST.to_string())
+}
+
+/// Convert a forwarding error into a response, counting its status
+fn error_response(state: &AppState, error: super::ProxyError) -> Response<Body> {
+    let response = error.into_response();
+    state.stats.record_status(response.status().as_u16());
+    response
 }
 
 /// Forward a request to the upstream server
 ///
 /// This is the shared request forwarding logic used by both dynamic and
-/// configured proxy handlers.
+/// configured proxy handlers. The upstream response body is streamed to the
+/// client as it arrives; capture and ingestion run in the background once
+/// the stream has finished.
 async fn forward_request(
-    state: &AppState,
+    state: &Arc<AppState>,
     target_url: &Url,
     method: Method,
     headers: HeaderMap,
@@ -393,16 +519,29 @@ async fn forward_request(
         .map_err(|e| super::ProxyError::Request(format!("Failed to read request body: {e}")))?;
 
     let body_json: Option<serde_json::Value> = serde_json::from_slice(&body_bytes).ok();
-    let provider = body_json
-        .as_ref()
-        .map(|json| Provider::detect(target_url, &headers, json))
-        .unwrap_or(Provider::Unknown);
-
+    let route = Route::resolve(
+        &state.config.routes,
+        target_url,
+        &headers,
+        body_json.as_ref(),
+    );
+
+    let provider_name = format!("{:?}", route.provider);
+    let host_label = metrics_host(&state.config, target_url);
+    let deployment = match route.provider {
+        Provider::OpenAI => azure_deployment(target_url),
+        _ => None,
+    };
+    if let Some(ref deployment) = deployment {
+        tracing::debug!("Azure OpenAI deployment: {deployment}");
+    }
+    state.stats.record_request(&provider_name);
     let _ = state.event_tx.send(ProxyEvent::RequestStarted {
         request_id: request_id.clone(),
         method: method.to_string(),
         path: target_url.path().to_string(),
-        provider: format!("{provider:?}"),
+        provider: provider_name.clone(),
+        deployment,
         timestamp: Utc::now(),
     });
 
@@ -423,15 +562,46 @@ async fn forward_request(
         .map(|s| s.eq_ignore_ascii_case("true"))
         .unwrap_or(false);
 
-    let final_body = match try_inject_memories(state, target_url, &headers, &body_bytes, session_id.clone()).await {
-        Ok(modified) => {
+    let capture_scope = CaptureScope {
+        session_id: session_id.clone(),
+        promote_to_global,
+        labels: MemoryLabels::from_headers(&headers),
+    };
+
+    let injection = if route.inject {
+        try_inject_memories(
+            state,
+            route.provider,
+            target_url,
+            &body_bytes,
+            session_id.clone(),
+        )
+        .await
+    } else {
+        Ok(None)
+    };
+
+    let final_body = match injection {
+        Ok(Some(injection)) => {
+            state.stats.record_injection(injection.plan.memories.len());
+            telemetry::record_injection(
+                &provider_name,
+                injection.plan.memories.len(),
+                injection.plan.tokens(),
+            );
+            let memories: Vec<InjectedMemory> =
+                injection.plan.memories.iter().map(InjectedMemory::from).collect();
             let _ = state.event_tx.send(ProxyEvent::MemoriesInjected {
                 request_id: request_id.clone(),
-                memory_ids: vec![],
-                count: 0,
+                memory_ids: memories.iter().map(|m| m.id.clone()).collect(),
+                count: memories.len(),
+                query: injection.query,
+                tokens: injection.plan.tokens(),
+                memories,
             });
-            modified
+            injection.body
         }
+        Ok(None) => body_bytes.to_vec(),
         Err(e) => {
             tracing::debug!("Memory injection skipped: {e}");
             body_bytes.to_vec()
@@ -468,6 +638,12 @@ async fn forward_request(
         .send()
         .await
         .map_err(|e| {
+            telemetry::record_request(
+                &host_label,
+                &provider_name,
+                StatusCode::BAD_GATEWAY.as_u16(),
+                start_time.elapsed(),
+            );
             if e.is_timeout() {
                 super::ProxyError::Network(format!("Request timed out: {e}"))
             } else if e.is_connect() {
@@ -496,36 +672,83 @@ async fn forward_request(
         }
     }
 
-    let response_body = response
-        .bytes()
-        .await
-        .map_err(|e| super::ProxyError::Network(format!("Failed to read response body: {e}")))?;
-
-    if let Some(content) = try_capture_response(
-        state,
-        &body_bytes,
-        target_url,
-        &headers,
-        &response_body,
-        session_id,
-        promote_to_global,
-    )
-    .await
-    {
-        tracing::debug!(
-            "Captured response content ({} chars): {}...",
-            content.len(),
-            &content[..content.len().min(100)]
+    let upstream_stream: UpstreamByteStream = Box::pin(
+        response
+            .bytes_stream()
+            .map(|chunk| chunk.map_err(std::io::Error::other)),
+    );
+    let TeeResult {
+        client_stream,
+        buffer_handle,
+    } = StreamingProxy::tee_stream(upstream_stream);
+
+    // Capture runs once the upstream stream closes so the client receives
+    // chunks as they arrive instead of waiting for the full response.
+    let capture_state = Arc::clone(state);
+    let capture_url = target_url.clone();
+    tokio::spawn(async move {
+        let finished = buffer_handle.finish().await;
+        let latency = start_time.elapsed();
+        let latency_ms = latency.as_millis() as u64;
+        let response_body = match finished {
+            Ok(body) => body,
+            Err(abort) => {
+                // A truncated response is never captured and the request is
+                // reported as failed rather than completed.
+                let failed_status = match abort {
+                    StreamAbort::Upstream(_) => StatusCode::BAD_GATEWAY.as_u16(),
+                    StreamAbort::ClientDisconnected => CLIENT_CLOSED_REQUEST,
+                };
+                tracing::warn!("Streamed response for {} aborted: {}", request_id, abort);
+                telemetry::record_request(&host_label, &provider_name, failed_status, latency);
+                capture_state.stats.record_status(failed_status);
+                capture_state.stats.record_latency(latency_ms);
+                let _ = capture_state.event_tx.send(ProxyEvent::RequestFailed {
+                    request_id,
+                    status: failed_status,
+                    latency_ms,
+                    error: abort.to_string(),
+                });
+                return;
+            }
+        };
+        telemetry::record_request(
+            &host_label,
+            &provider_name,
+            status.as_u16(),
+            latency,
         );
-    }
 
-    let response_bytes = response_body.len() as u64;
+        let captured = if route.capture {
+            try_capture_response(
+                &capture_state,
+                route.provider,
+                &body_bytes,
+                &capture_url,
+                &response_body,
+                capture_scope,
+            )
+            .await
+        } else {
+            None
+        };
+
+        if let Some(content) = captured {
+            tracing::debug!(
+                "Captured response content ({} chars): {}",
+                content.chars().count(),
+                content_preview(&content)
+            );
+        }
 
-    let _ = state.event_tx.send(ProxyEvent::RequestCompleted {
-        request_id,
-        status: status.as_u16(),
-        latency_ms: start_time.elapsed().as_millis() as u64,
-        bytes: Some(response_bytes),
+        capture_state.stats.record_status(status.as_u16());
+        capture_state.stats.record_latency(latency_ms);
+        let _ = capture_state.event_tx.send(ProxyEvent::RequestCompleted {
+            request_id,
+            status: status.as_u16(),
+            latency_ms,
+            bytes: Some(response_body.len() as u64),
+        });
     });
 
     let mut builder = Response::builder().status(status);
@@ -534,60 +757,116 @@ async fn forward_request(
     }
 
     builder
-        .body(Body::from(response_body))
+        .body(Body::from_stream(client_stream))
         .map_err(|e| super::ProxyError::Network(format!("Failed to build response: {e}")))
 }
 
 async fn try_capture_response(
     state: &AppState,
+    provider: Provider,
     request_body: &[u8],
     target_url: &Url,
-    headers: &HeaderMap,
     response_body: &[u8],
-    session_id: Option<String>,
-    promote_to_global: bool,
+    scope: CaptureScope,
 ) -> Option<String> {
+    let CaptureScope {
+        session_id,
+        promote_to_global,
+        labels,
+    } = scope;
     let request_json: Value = serde_json::from_slice(request_body).ok()?;
-    let provider = Provider::detect(target_url, headers, &request_json);
 
-    let llm_provider: Box<dyn LLMProvider + Send + Sync> = match provider {
-        Provider::OpenAI => Box::new(OpenAiProvider::new()),
-        Provider::Anthropic => Box::new(AnthropicProvider::new()),
-        Provider::Unknown => return None,
+    let llm_provider = llm_provider_for(provider, target_url, &request_json)?;
+
+    let (result, tool_calls) =
+        if let Ok(response_json) = serde_json::from_slice::<Value>(response_body) {
+            let content = llm_provider
+                .parse_response_content(&response_json)
+                .filter(|content| !content.trim().is_empty());
+            (content, llm_provider.parse_response_tool_calls(&response_json))
+        } else if let Ok(response_str) = std::str::from_utf8(response_body) {
+            let extracted = llm_provider.parse_sse_content(response_str);
+            let content = if !extracted.content.trim().is_empty() {
+                Some(extracted.content)
+            } else {
+                None
+            };
+            (content, extracted.tool_calls)
+        } else {
+            (None, Vec::new())
+        };
+
+    let final_session_id = if promote_to_global {
+        None
+    } else {
+        session_id.clone()
     };
 
-    let result = if let Ok(response_json) = serde_json::from_slice::<Value>(response_body) {
+    // Results sent back in this request complete the tool calls made in the
+    // previous response, so that is when they become procedural memories
+    let tool_results = llm_provider.extract_tool_results(&request_json);
+
+    // A request that returns tool results continues the previous turn rather
+    // than carrying a new user message
+    let user_message = if tool_results.is_empty() {
         llm_provider
-            .parse_response_content(&response_json)
-            .filter(|content| !content.trim().is_empty())
-    } else if let Ok(response_str) = std::str::from_utf8(response_body) {
-        let extracted = llm_provider.parse_sse_content(response_str);
-        if !extracted.content.trim().is_empty() {
-            Some(extracted.content)
-        } else {
-            None
-        }
+            .extract_user_message(&request_json)
+            .filter(|message| !message.trim().is_empty())
     } else {
         None
     };
 
-    if let Some(ref content) = result {
-        let final_session_id = if promote_to_global {
-            None
-        } else {
-            session_id.clone()
-        };
+    if let (Some(sid), Some(curator_config)) = (&session_id, &state.curator_config) {
+        // Drop buffers of sessions that have gone quiet so the map stays bounded
+        state
+            .conversation_buffers
+            .retain(|id, buffer| id == sid || !buffer.is_idle());
+
+        let mut buffer = state
+            .conversation_buffers
+            .entry(sid.clone())
+            .or_insert_with(|| ConversationBuffer::new(&curator_config.buffer));
 
-        if let Some(sid) = &session_id {
-            if let Some(ref curator_config) = state.curator_config {
-                let mut buffer = state
-                    .conversation_buffers
-                    .entry(sid.clone())
-                    .or_insert_with(|| ConversationBuffer::new(&curator_config.buffer));
-                buffer.push(ConversationTurn::new(Role::Assistant, content.clone()));
+        if state.capture_config.tool_turns {
+            for call in &tool_results {
+                buffer.push_unique(ConversationTurn::new(Role::Tool, call.to_memory_content()));
             }
         }
+        if let Some(message) = user_message
+            .as_ref()
+            .filter(|_| state.capture_config.user_turns)
+        {
+            buffer.push_unique(ConversationTurn::new(Role::User, message.clone()));
+        }
+
+        let turn = tool_calls
+            .iter()
+            .map(|call| call.to_memory_content())
+            .chain(result.clone())
+            .collect::<Vec<_>>()
+            .join("\n");
+        if !turn.is_empty() {
+            buffer.push(ConversationTurn::new(Role::Assistant, turn));
+        }
+    }
+
+    if !tool_results.is_empty() {
+        spawn_tool_call_ingestion(
+            state,
+            tool_results,
+            final_session_id.clone(),
+            labels.clone(),
+        );
+    }
+
+    // Without a curator to pick out facts, user messages are stored as-is
+    if let Some(message) = user_message {
+        if state.curator.is_none() && state.capture_config.ingest_user_messages {
+            spawn_blind_ingestion(state, message, final_session_id.clone(), labels.clone());
+        }
+    }
 
+    if let Some(ref content) = result {
         if let Some(ref curator) = state.curator {
             let buffer_context = session_id
                 .as_ref()
@@ -598,45 +877,48 @@ async fn try_capture_response(
             let curator = Arc::clone(curator);
             let pipeline = state.ingestion_pipeline.clone();
             let event_tx = state.event_tx.clone();
+            let stats = state.stats.clone();
             let final_session = final_session_id.clone();
             let content_for_fallback = content.clone();
+            let labels = labels.clone();
 
             tokio::spawn(async move {
                 match curator.curate(&buffer_context).await {
                     Ok(curation_result) if curation_result.should_store => {
+                        stats.record_curation(CurationOutcome::Stored);
                         let mut pipeline = pipeline.lock().await;
                         for memory in curation_result.memories {
                             match pipeline
                                 .ingest_curated(memory, final_session.clone())
                                 .await
                             {
-                                Ok(mem) => {
+                                Ok(mut mem) => {
+                                    labels.apply(&pipeline, &mut mem).await;
+                                    stats.record_ingestion(IngestionOutcome::Ingested);
                                     tracing::debug!(
                                         "Ingested curated memory {} (session: {:?})",
                                         mem.id,
                                         mem.conversation_id
                                     );
-                                    let content_preview = if mem.content.len() > 100 {
-                                        format!("{}...", &mem.content[..100])
-                                    } else {
-                                        mem.content.clone()
-                                    };
                                     let _ = event_tx.send(ProxyEvent::MemoryIngested {
                                         memory_id: mem.id.to_string(),
                                         memory_type: format!("{:?}", mem.memory_type),
-                                        content_preview,
+                                        content_preview: content_preview(&mem.content),
                                     });
                                 }
                                 Err(e) => {
+                                    stats.record_ingestion(IngestionOutcome::Failed);
                                     tracing::warn!("Failed to ingest curated memory: {}", e);
                                 }
                             }
                         }
                     }
                     Ok(_) => {
+                        stats.record_curation(CurationOutcome::Rejected);
                         tracing::debug!("Curator decided not to store content");
                     }
                     Err(e) => {
+                        stats.record_curation(CurationOutcome::Failed);
                         tracing::warn!("Curator failed, falling back to blind storage: {}", e);
                         let mut pipeline = pipeline.lock().await;
                         match pipeline
@@ -647,27 +929,26 @@ async fn try_capture_response(
                             )
                             .await
                         {
-                            Ok(Some(memory)) => {
+                            Ok(Some(mut memory)) => {
+                                labels.apply(&pipeline, &mut memory).await;
+                                stats.record_ingestion(IngestionOutcome::Ingested);
                                 tracing::debug!(
                                     "Fallback ingested response as memory {} (session: {:?})",
                                     memory.id,
                                     memory.conversation_id
                                 );
-                                let content_preview = if memory.content.len() > 100 {
-                                    format!("{}...", &memory.content[..100])
-                                } else {
-                                    memory.content.clone()
-                                };
                                 let _ = event_tx.send(ProxyEvent::MemoryIngested {
                                     memory_id: memory.id.to_string(),
                                     memory_type: format!("{:?}", memory.memory_type),
-                                    content_preview,
+                                    content_preview: content_preview(&memory.content),
                                 });
                             }
                             Ok(None) => {
+                                stats.record_ingestion(IngestionOutcome::Filtered);
                                 tracing::debug!("Fallback: Response filtered by ingestion pipeline");
                             }
                             Err(e) => {
+                                stats.record_ingestion(IngestionOutcome::Failed);
                                 tracing::warn!("Fallback ingestion failed: {}", e);
                             }
                         }
@@ -675,86 +956,315 @@ async fn try_capture_response(
                 }
             });
         } else {
-            let pipeline = state.ingestion_pipeline.clone();
-            let content_clone = content.clone();
-            let event_tx = state.event_tx.clone();
+            spawn_blind_ingestion(state, content.clone(), final_session_id, labels);
+        }
+    }
 
-            tokio::spawn(async move {
-                let mut pipeline = pipeline.lock().await;
-                match pipeline
-                    .ingest(&content_clone, MemorySource::Conversation, final_session_id)
-                    .await
-                {
-                    Ok(Some(memory)) => {
-                        tracing::debug!(
-                            "Ingested response as memory {} (session: {:?})",
-                            memory.id,
-                            memory.conversation_id
-                        );
-                        let content_preview = if memory.content.len() > 100 {
-                            format!("{}...", &memory.content[..100])
-                        } else {
-                            memory.content.clone()
-                        };
-                        let _ = event_tx.send(ProxyEvent::MemoryIngested {
-                            memory_id: memory.id.to_string(),
-                            memory_type: format!("{:?}", memory.memory_type),
-                            content_preview,
-                        });
-                    }
-                    Ok(None) => {
-                        tracing::debug!("Response filtered by ingestion pipeline");
-                    }
-                    Err(e) => {
-                        tracing::warn!("Failed to ingest response: {}", e);
-                    }
+    result
+}
+
+/// Ingest captured text as a conversation memory in the background, without
+/// curation
+fn spawn_blind_ingestion(
+    state: &AppState,
+    content: String,
+    session_id: Option<String>,
+    labels: MemoryLabels,
+) {
+    let pipeline = state.ingestion_pipeline.clone();
+    let event_tx = state.event_tx.clone();
+    let stats = state.stats.clone();
+
+    tokio::spawn(async move {
+        let mut pipeline = pipeline.lock().await;
+        match pipeline
+            .ingest(&content, MemorySource::Conversation, session_id)
+            .await
+        {
+            Ok(Some(mut memory)) => {
+                labels.apply(&pipeline, &mut memory).await;
+                stats.record_ingestion(IngestionOutcome::Ingested);
+                tracing::debug!(
+                    "Ingested conversation text as memory {} (session: {:?})",
+                    memory.id,
+                    memory.conversation_id
+                );
+                let _ = event_tx.send(ProxyEvent::MemoryIngested {
+                    memory_id: memory.id.to_string(),
+                    memory_type: format!("{:?}", memory.memory_type),
+                    content_preview: content_preview(&memory.content),
+                });
+            }
+            Ok(None) => {
+                stats.record_ingestion(IngestionOutcome::Filtered);
+                tracing::debug!("Conversation text filtered by ingestion pipeline");
+            }
+            Err(e) => {
+                stats.record_ingestion(IngestionOutcome::Failed);
+                tracing::warn!("Failed to ingest conversation text: {}", e);
+            }
+        }
+    });
+}
+
+/// Maximum number of characters of memory content shown in event previews
+const CONTENT_PREVIEW_CHARS: usize = 100;
+
+/// First characters of `content` for event previews, with "..." if cut short
+///
+/// Cuts on a character boundary, so multi-byte characters are never split.
+fn content_preview(content: &str) -> String {
+    match content.char_indices().nth(CONTENT_PREVIEW_CHARS) {
+        Some((end, _)) => format!("{}...", &content[..end]),
+        None => content.to_string(),
+    }
+}
+
+/// Ingest completed tool calls as procedural memories in the background
+fn spawn_tool_call_ingestion(
+    state: &AppState,
+    calls: Vec<ToolCall>,
+    session_id: Option<String>,
+    labels: MemoryLabels,
+) {
+    let pipeline = state.ingestion_pipeline.clone();
+    let event_tx = state.event_tx.clone();
+    let stats = state.stats.clone();
+
+    tokio::spawn(async move {
+        let mut pipeline = pipeline.lock().await;
+        for call in calls {
+            match pipeline.ingest_tool_call(&call, session_id.clone()).await {
+                Ok(Some(mut memory)) => {
+                    labels.apply(&pipeline, &mut memory).await;
+                    stats.record_ingestion(IngestionOutcome::Ingested);
+                    tracing::debug!(
+                        "Ingested tool call `{}` as memory {} (session: {:?})",
+                        call.name,
+                        memory.id,
+                        memory.conversation_id
+                    );
+                    let _ = event_tx.send(ProxyEvent::MemoryIngested {
+                        memory_id: memory.id.to_string(),
+                        memory_type: format!("{:?}", memory.memory_type),
+                        content_preview: content_preview(&memory.content),
+                    });
                 }
-            });
+                Ok(None) => {
+                    stats.record_ingestion(IngestionOutcome::Filtered);
+                }
+                Err(e) => {
+                    stats.record_ingestion(IngestionOutcome::Failed);
+                    tracing::warn!("Failed to ingest tool call `{}`: {}", call.name, e);
+                }
+            }
         }
+    });
+}
+
+/// Where memories captured from a request are stored and how they are labelled
+struct CaptureScope {
+    /// Session the request belongs to (`x-mnemo-session-id`)
+    session_id: Option<String>,
+    /// Store captured memories globally (`x-mnemo-promote-response`)
+    promote_to_global: bool,
+    labels: MemoryLabels,
+}
+
+/// Tags and metadata applied to every memory captured from a request
+#[derive(Debug, Clone, Default, PartialEq)]
+struct MemoryLabels {
+    /// Comma-separated tags from `x-mnemo-tags`
+    tags: Vec<String>,
+    /// JSON object from `x-mnemo-metadata`
+    metadata: Map<String, Value>,
+}
+
+impl MemoryLabels {
+    /// Parse labels from the request headers, ignoring invalid values
+    fn from_headers(headers: &HeaderMap) -> Self {
+        let tags = headers
+            .get("x-mnemo-tags")
+            .and_then(|v| v.to_str().ok())
+            .map(|s| {
+                s.split(',')
+                    .map(str::trim)
+                    .filter(|tag| !tag.is_empty())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let metadata = headers
+            .get("x-mnemo-metadata")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|s| {
+                serde_json::from_str::<Map<String, Value>>(s)
+                    .map_err(|e| tracing::debug!("Invalid x-mnemo-metadata header: {e}"))
+                    .ok()
+            })
+            .unwrap_or_default();
+
+        Self { tags, metadata }
     }
 
-    result
+    /// Apply the labels to a freshly ingested memory; failures are logged
+    async fn apply(&self, pipeline: &IngestionPipeline, memory: &mut Memory) {
+        if let Err(e) = pipeline
+            .apply_labels(memory, &self.tags, &self.metadata)
+            .await
+        {
+            tracing::warn!("Failed to label memory {}: {}", memory.id, e);
+        }
+    }
+}
+
+/// Request body with memories injected, and what was injected into it
+struct InjectedRequest {
+    body: Vec<u8>,
+    /// The query used for retrieval
+    query: String,
+    /// The memories and tombstones that fit the injection budget
+    plan: InjectionPlan,
 }
 
+/// Inject relevant memories into a request body
+///
+/// Returns `Ok(None)` when injection does not apply to the request (unknown
+/// provider or no user query to retrieve with).
 async fn try_inject_memories(
     state: &AppState,
+    provider: Provider,
     target_url: &Url,
-    headers: &HeaderMap,
     body_bytes: &[u8],
     session_id: Option<String>,
-) -> crate::error::Result<Vec<u8>> {
+) -> crate::error::Result<Option<InjectedRequest>> {
     let mut body_json: Value = serde_json::from_slice(body_bytes)
         .map_err(|e| crate::error::MnemoError::Proxy(format!("Invalid JSON: {e}")))?;
 
-    let provider = Provider::detect(target_url, headers, &body_json);
-
-    let llm_provider: Box<dyn LLMProvider + Send + Sync> = match provider {
-        Provider::OpenAI => Box::new(OpenAiProvider::new()),
-        Provider::Anthropic => Box::new(AnthropicProvider::new()),
-        Provider::Unknown => {
-            return Ok(body_bytes.to_vec());
-        }
+    let Some(llm_provider) = llm_provider_for(provider, target_url, &body_json) else {
+        return Ok(None);
     };
 
     let query = match llm_provider.extract_user_query(&body_json) {
         Some(query) => query,
-        None => return Ok(body_bytes.to_vec()),
+        None => return Ok(None),
+    };
+
+    let query_builder = QueryBuilder::new(
+        &state.router_config.query,
+        &state.embedding_model,
+        &state.rolling_queries,
+    );
+    let turns = if query_builder.needs_turns() {
+        llm_provider.extract_recent_turns(&body_json, state.router_config.query.turns)
+    } else {
+        Vec::new()
+    };
+    let retrieval_query = query_builder.build(&query, &turns, session_id.as_deref())?;
+
+    // Entities of earlier turns resolve references like "the second one"
+    let context = if turns.is_empty() {
+        query.clone()
+    } else {
+        turns.join("\n")
+    };
+
+    // Routing only feeds deterministic ordering and tombstone lookup, so a
+    // failure degrades to plain retrieval rather than skipping injection
+    let router_output = match state.router.route(&context) {
+        Ok(output) => Some(output),
+        Err(e) => {
+            tracing::debug!("Query routing failed: {}", e);
+            None
+        }
     };
+    let query_entities: Option<Vec<String>> = router_output
+        .as_ref()
+        .map(|output| output.entities.iter().map(|e| e.text.clone()).collect());
+    let topics = router_output.map(|output| output.topics).unwrap_or_default();
 
     let store = state.store.lock().await;
-    let filter = MemoryFilter::new().with_session_filter(session_id);
-    let mut pipeline = RetrievalPipeline::with_defaults(&store, &state.embedding_model);
-    let memories = pipeline
-        .retrieve_filtered(&query, &filter, state.router_config.max_memories)
-        .await?;
+    let filter = MemoryFilter::new().with_session_filter(session_id.clone());
+    let trackers = &state.injection_trackers;
+    let config = RetrievalConfig {
+        injection_tracking_enabled: trackers.enabled(),
+        injection_penalty_factor: trackers.penalty_factor(),
+        ..RetrievalConfig::from_router_config(&state.router_config)
+    };
+    let tracker = session_id.as_deref().and_then(|id| trackers.tracker_for(id));
+    let mut pipeline = match tracker.clone() {
+        Some(tracker) => {
+            RetrievalPipeline::with_injection_tracker(&store, &state.embedding_model, config, tracker)
+        }
+        None => RetrievalPipeline::new(&store, &state.embedding_model, config),
+    }
+    .with_text_query(query.as_str());
+    let limit = state.router_config.max_memories;
+    let entities = query_entities.as_deref();
+    let memories = match retrieval_query {
+        RetrievalQuery::Text(text) => {
+            pipeline
+                .retrieve_filtered_with_entities(&text, &filter, limit, entities)
+                .await?
+        }
+        RetrievalQuery::Embedding(embedding) => {
+            pipeline
+                .retrieve_by_embedding_filtered_with_entities(&embedding, &filter, limit, entities)
+                .await?
+        }
+        RetrievalQuery::Fused(queries) => {
+            pipeline
+                .retrieve_fused(&queries, &filter, limit, entities)
+                .await?
+        }
+    };
+    let tombstones = find_matching_tombstones(&store, &topics).await;
     drop(store);
 
-    llm_provider.inject_memories(&mut body_json, &memories, state.config.max_injection_tokens)?;
+    let budget = InjectionBudget::new(state.config.max_injection_tokens)
+        .with_tombstone_share(state.config.tombstone_budget_share);
+    let plan = plan_injection(&memories, &tombstones, budget);
+    llm_provider.inject_plan(&mut body_json, &plan)?;
+
+    // Only memories that fit the budget reached the model
+    if let Some(tracker) = tracker {
+        let mut tracker = tracker.lock().unwrap_or_else(|e| e.into_inner());
+        for retrieved in &plan.memories {
+            tracker.mark_injected(retrieved.memory.id);
+        }
+    }
 
     let modified = serde_json::to_vec(&body_json)
         .map_err(|e| crate::error::MnemoError::Proxy(format!("Failed to serialize: {e}")))?;
 
-    Ok(modified)
+    Ok(Some(InjectedRequest {
+        body: modified,
+        query,
+        plan,
+    }))
+}
+
+/// Find tombstones matching the router topics of a query
+///
+/// Tombstones are best-effort context, so lookup failures are logged and
+/// result in no tombstones rather than failing injection.
+async fn find_matching_tombstones(store: &LanceStore, topics: &[String]) -> Vec<Tombstone> {
+    let mut seen = std::collections::HashSet::new();
+    let mut tombstones = Vec::new();
+    for topic in topics {
+        match store.search_tombstones_by_topic(topic).await {
+            Ok(matches) => {
+                tombstones.extend(matches.into_iter().filter(|t| seen.insert(t.original_id)));
+            }
+            Err(e) => {
+                tracing::debug!("Tombstone lookup failed for topic '{}': {}", topic, e);
+                break;
+            }
+        }
+    }
+
+    tombstones
 }
 
 /// Create a JSON error response
@@ -810,6 +1320,7 @@ async fn shutdown_signal() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::InjectionTrackingConfig;
     use axum::body::Body;
     use axum::http::Request;
     use tower::ServiceExt;
@@ -838,6 +1349,8 @@ mod tests {
                 allowed_hosts,
                 timeout_secs: 30,
                 max_injection_tokens: 2000,
+                tombstone_budget_share: 0.2,
+                routes: Vec::new(),
             },
             client: reqwest::Client::new(),
             store,
@@ -849,6 +1362,12 @@ mod tests {
             curator: None,
             conversation_buffers: Arc::new(DashMap::new()),
             curator_config: None,
+            capture_config: CaptureConfig::default(),
+            stats: Arc::new(StatsCollector::new()),
+            injection_trackers: Arc::new(SessionInjectionTrackers::new(
+                InjectionTrackingConfig::default(),
+            )),
+            rolling_queries: Arc::new(RollingQueryEmbeddings::default()),
         })
     }
 
@@ -952,6 +1471,59 @@ mod tests {
         assert!(body_str.contains("host_not_allowed"));
     }
 
+    #[test]
+    fn test_memory_labels_from_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-mnemo-tags", HeaderValue::from_static("rust, mnemo,,"));
+        headers.insert(
+            "x-mnemo-metadata",
+            HeaderValue::from_static(r#"{"repo":"watzon/mnemo","client":"zed"}"#),
+        );
+
+        let labels = MemoryLabels::from_headers(&headers);
+        assert_eq!(labels.tags, vec!["rust".to_string(), "mnemo".to_string()]);
+        assert_eq!(labels.metadata["repo"], Value::from("watzon/mnemo"));
+        assert_eq!(labels.metadata["client"], Value::from("zed"));
+    }
+
+    #[test]
+    fn test_memory_labels_ignore_invalid_metadata() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-mnemo-metadata", HeaderValue::from_static("[1, 2]"));
+
+        assert_eq!(MemoryLabels::from_headers(&headers), MemoryLabels::default());
+    }
+
+    #[tokio::test]
+    async fn test_metrics_host_buckets_unknown_hosts() {
+        let state = create_test_state_with_allowed_hosts(vec![
+            "api.openai.com".to_string(),
+            "*.openai.azure.com".to_string(),
+        ])
+        .await;
+        let label = |url: &str| metrics_host(&state.config, &Url::parse(url).unwrap());
+
+        assert_eq!(label("https://api.openai.com/v1/chat"), "api.openai.com");
+        assert_eq!(
+            label("https://my-deployment.openai.azure.com/openai"),
+            "*.openai.azure.com"
+        );
+        assert_eq!(label("https://random-host.example.com/v1"), "other");
+    }
+
+    #[test]
+    fn test_content_preview_truncates_on_char_boundary() {
+        assert_eq!(content_preview("short"), "short");
+
+        let exact = "a".repeat(CONTENT_PREVIEW_CHARS);
+        assert_eq!(content_preview(&exact), exact);
+
+        // Each character is several bytes, so a byte slice at 100 would panic
+        let long = "é".repeat(CONTENT_PREVIEW_CHARS + 1);
+        let preview = content_preview(&long);
+        assert_eq!(preview, format!("{}...", "é".repeat(CONTENT_PREVIEW_CHARS)));
+    }
+
     #[test]
     fn test_hop_by_hop_headers_defined() {
         assert!(HOP_BY_HOP_HEADERS.contains(&"host"));
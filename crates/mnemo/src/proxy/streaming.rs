@@ -3,14 +3,14 @@
 //! This module provides streaming proxy capabilities that:
 //! - Forward SSE streams to clients with zero added latency
 //! - Buffer the stream content for post-completion ingestion
-//! - Parse SSE events and extract content from OpenAI format
+//! - Parse SSE events and extract content and tool calls from OpenAI format
 
 use bytes::Bytes;
 use futures::stream::Stream;
+use std::fmt;
 use std::pin::Pin;
-use std::sync::Arc;
 use std::task::{Context, Poll};
-use tokio::sync::{Mutex, oneshot};
+use tokio::sync::oneshot;
 
 /// Result of teeing a stream - provides both the forwarding stream and a handle to get buffered content
 pub struct TeeResult<S> {
@@ -20,16 +20,45 @@ pub struct TeeResult<S> {
     pub buffer_handle: BufferHandle,
 }
 
+/// Why a teed stream ended before the upstream finished
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StreamAbort {
+    /// The upstream stream yielded an error
+    Upstream(String),
+    /// The client stream was dropped before it reached the end
+    ClientDisconnected,
+}
+
+impl fmt::Display for StreamAbort {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Upstream(e) => write!(f, "upstream stream failed: {e}"),
+            Self::ClientDisconnected => write!(f, "client disconnected before the stream ended"),
+        }
+    }
+}
+
 /// Handle to retrieve buffered stream content after completion
 pub struct BufferHandle {
-    receiver: oneshot::Receiver<Vec<u8>>,
+    receiver: oneshot::Receiver<Result<Vec<u8>, StreamAbort>>,
 }
 
 impl BufferHandle {
+    /// Wait for the stream to end
+    ///
+    /// Returns the raw bytes accumulated during streaming if the upstream
+    /// completed, or why the stream was aborted otherwise.
+    pub async fn finish(self) -> Result<Vec<u8>, StreamAbort> {
+        self.receiver
+            .await
+            .unwrap_or(Err(StreamAbort::ClientDisconnected))
+    }
+
     /// Get the buffered content after the stream completes
-    /// Returns the raw bytes accumulated during streaming
+    /// Returns the raw bytes accumulated during streaming, or nothing if the
+    /// stream was aborted
     pub async fn get_raw_content(self) -> Vec<u8> {
-        self.receiver.await.unwrap_or_default()
+        self.finish().await.unwrap_or_default()
     }
 
     /// Get the buffered content as a string (lossy UTF-8 conversion)
@@ -40,13 +69,38 @@ impl BufferHandle {
 }
 
 /// A stream wrapper that buffers all chunks while forwarding them
+///
+/// Chunks are appended to the buffer synchronously as they are polled, so the
+/// buffered content always matches the order the client received it in.
+/// The buffer is only handed over once the upstream ends cleanly; an upstream
+/// error or dropping the stream early reports a [`StreamAbort`] instead.
 pub struct TeeStream<S>
 where
     S: Stream<Item = Result<Bytes, std::io::Error>> + Unpin,
 {
     inner: S,
-    buffer: Arc<Mutex<Vec<u8>>>,
-    sender: Option<oneshot::Sender<Vec<u8>>>,
+    buffer: Vec<u8>,
+    sender: Option<oneshot::Sender<Result<Vec<u8>, StreamAbort>>>,
+}
+
+impl<S> TeeStream<S>
+where
+    S: Stream<Item = Result<Bytes, std::io::Error>> + Unpin,
+{
+    fn finish(&mut self, result: Result<Vec<u8>, StreamAbort>) {
+        if let Some(sender) = self.sender.take() {
+            let _ = sender.send(result);
+        }
+    }
+}
+
+impl<S> Drop for TeeStream<S>
+where
+    S: Stream<Item = Result<Bytes, std::io::Error>> + Unpin,
+{
+    fn drop(&mut self) {
+        self.finish(Err(StreamAbort::ClientDisconnected));
+    }
 }
 
 impl<S> Stream for TeeStream<S>
@@ -60,24 +114,16 @@ where
 
         match Pin::new(&mut this.inner).poll_next(cx) {
             Poll::Ready(Some(Ok(bytes))) => {
-                let buffer = Arc::clone(&this.buffer);
-                let bytes_clone = bytes.clone();
-                tokio::spawn(async move {
-                    let mut buf = buffer.lock().await;
-                    buf.extend_from_slice(&bytes_clone);
-                });
-
+                this.buffer.extend_from_slice(&bytes);
                 Poll::Ready(Some(Ok(bytes)))
             }
-            Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(e))),
+            Poll::Ready(Some(Err(e))) => {
+                this.finish(Err(StreamAbort::Upstream(e.to_string())));
+                Poll::Ready(Some(Err(e)))
+            }
             Poll::Ready(None) => {
-                if let Some(sender) = this.sender.take() {
-                    let buffer = Arc::clone(&this.buffer);
-                    tokio::spawn(async move {
-                        let buf = buffer.lock().await;
-                        let _ = sender.send(buf.clone());
-                    });
-                }
+                let buffer = std::mem::take(&mut this.buffer);
+                this.finish(Ok(buffer));
                 Poll::Ready(None)
             }
             Poll::Pending => Poll::Pending,
@@ -103,11 +149,10 @@ impl StreamingProxy {
         S: Stream<Item = Result<Bytes, std::io::Error>> + Unpin + Send + 'static,
     {
         let (tx, rx) = oneshot::channel();
-        let buffer = Arc::new(Mutex::new(Vec::new()));
 
         let tee_stream = TeeStream {
             inner: incoming,
-            buffer,
+            buffer: Vec::new(),
             sender: Some(tx),
         };
 
@@ -185,6 +230,58 @@ impl StreamingProxy {
             .map(|s| s.to_string())
     }
 
+    /// Reconstruct tool calls from OpenAI streaming format
+    ///
+    /// Each call arrives in pieces keyed by `index`: the first chunk carries the
+    /// ID and function name, later chunks append fragments of the arguments:
+    /// ```json
+    /// {"choices":[{"delta":{"tool_calls":[{"index":0,"function":{"arguments":"{\"q"}}]}}]}
+    /// ```
+    pub fn extract_openai_tool_calls(events: &[SseEvent]) -> Vec<ToolCall> {
+        let mut calls: Vec<ToolCall> = Vec::new();
+
+        for event in events {
+            let SseEvent::Data(data) = event else {
+                continue;
+            };
+            let Ok(value) = serde_json::from_str::<serde_json::Value>(data) else {
+                continue;
+            };
+            let Some(deltas) = value
+                .get("choices")
+                .and_then(|c| c.get(0))
+                .and_then(|c| c.get("delta"))
+                .and_then(|d| d.get("tool_calls"))
+                .and_then(|t| t.as_array())
+            else {
+                continue;
+            };
+
+            for delta in deltas {
+                let index = delta.get("index").and_then(|i| i.as_u64()).unwrap_or(0) as usize;
+                if calls.len() <= index {
+                    calls.resize_with(index + 1, ToolCall::default);
+                }
+                let call = &mut calls[index];
+
+                if let Some(id) = delta.get("id").and_then(|i| i.as_str()) {
+                    call.id = Some(id.to_string());
+                }
+                if let Some(function) = delta.get("function") {
+                    if let Some(name) = function.get("name").and_then(|n| n.as_str()) {
+                        call.name.push_str(name);
+                    }
+                    if let Some(arguments) = function.get("arguments").and_then(|a| a.as_str()) {
+                        call.arguments.push_str(arguments);
+                    }
+                }
+            }
+        }
+
+        calls.retain(|call| !call.name.is_empty());
+        calls
+    }
+
     /// Extract full response from buffered SSE stream
     ///
     /// This is the main entry point for post-stream content extraction.
@@ -192,10 +289,12 @@ impl StreamingProxy {
     pub fn extract_response_content(raw_sse: &str) -> ExtractedContent {
         let events = Self::parse_sse_events(raw_sse);
         let content = Self::extract_openai_content(&events);
+        let tool_calls = Self::extract_openai_tool_calls(&events);
         let is_complete = events.iter().any(|e| matches!(e, SseEvent::Done));
 
         ExtractedContent {
             content,
+            tool_calls,
             is_complete,
             event_count: events.len(),
         }
@@ -216,12 +315,61 @@ pub enum SseEvent {
 pub struct ExtractedContent {
     /// The concatenated content from all delta chunks
     pub content: String,
+    /// Tool calls reconstructed from their streamed deltas
+    pub tool_calls: Vec<ToolCall>,
     /// Whether the stream completed with [DONE]
     pub is_complete: bool,
     /// Number of events parsed
     pub event_count: usize,
 }
 
+/// Maximum length of a tool result kept in a procedural memory, in characters
+const MAX_TOOL_RESULT_CHARS: usize = 2000;
+
+/// A tool invocation made by the model
+///
+/// Calls are parsed from responses; the result is filled in when the client
+/// sends it back in a later request.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ToolCall {
+    /// Provider-assigned ID used to match results to calls
+    pub id: Option<String>,
+    /// Name of the tool
+    pub name: String,
+    /// Arguments as JSON text
+    pub arguments: String,
+    /// Result returned by the tool, if known
+    pub result: Option<String>,
+}
+
+impl ToolCall {
+    /// Render the invocation as memory content
+    ///
+    /// Long results are truncated so a single large tool output does not
+    /// dominate the memory.
+    pub fn to_memory_content(&self) -> String {
+        let arguments = if self.arguments.trim().is_empty() {
+            "{}"
+        } else {
+            self.arguments.trim()
+        };
+        let mut content = format!("Called tool `{}` with arguments {}", self.name, arguments);
+
+        if let Some(result) = self.result.as_deref().map(str::trim) {
+            content.push_str("\nResult: ");
+            match result.char_indices().nth(MAX_TOOL_RESULT_CHARS) {
+                Some((end, _)) => {
+                    content.push_str(&result[..end]);
+                    content.push_str("...");
+                }
+                None => content.push_str(result),
+            }
+        }
+
+        content
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -317,6 +465,51 @@ data: [DONE]
         assert!(!extracted.is_complete);
     }
 
+    #[test]
+    fn test_extract_openai_tool_calls() {
+        let raw = r#"data: {"choices":[{"index":0,"delta":{"role":"assistant","tool_calls":[{"index":0,"id":"call_1","type":"function","function":{"name":"get_weather","arguments":""}}]}}]}
+
+data: {"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":"{\"city\":"}}]}}]}
+
+data: {"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":"\"Paris\"}"}}]}}]}
+
+data: {"choices":[{"index":0,"delta":{},"finish_reason":"tool_calls"}]}
+
+data: [DONE]
+"#;
+
+        let extracted = StreamingProxy::extract_response_content(raw);
+
+        assert_eq!(extracted.content, "");
+        assert_eq!(
+            extracted.tool_calls,
+            vec![ToolCall {
+                id: Some("call_1".to_string()),
+                name: "get_weather".to_string(),
+                arguments: r#"{"city":"Paris"}"#.to_string(),
+                result: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_tool_call_memory_content_truncates_result() {
+        let call = ToolCall {
+            id: None,
+            name: "read_file".to_string(),
+            arguments: r#"{"path":"README.md"}"#.to_string(),
+            result: Some("x".repeat(MAX_TOOL_RESULT_CHARS + 10)),
+        };
+
+        let content = call.to_memory_content();
+
+        assert!(content.starts_with(
+            "Called tool `read_file` with arguments {\"path\":\"README.md\"}\nResult: "
+        ));
+        assert!(content.ends_with("..."));
+        assert!(content.len() < MAX_TOOL_RESULT_CHARS + 100);
+    }
+
     #[test]
     fn test_parse_sse_with_comments() {
         let raw = r#": this is a comment
@@ -395,4 +588,65 @@ data: [DONE]
         let end = client_stream.next().await;
         assert!(end.is_none());
     }
+
+    #[tokio::test]
+    async fn test_tee_stream_forwards_before_upstream_finishes() {
+        use futures::FutureExt;
+
+        let (tx, rx) = tokio::sync::mpsc::channel::<Result<Bytes, std::io::Error>>(10);
+
+        let incoming = tokio_stream::wrappers::ReceiverStream::new(rx);
+        let TeeResult {
+            mut client_stream,
+            buffer_handle,
+        } = StreamingProxy::tee_stream(incoming);
+        let mut finished = Box::pin(buffer_handle.finish());
+
+        tx.send(Ok(Bytes::from("chunk1"))).await.unwrap();
+        let first = client_stream.next().await;
+        assert_eq!(first.unwrap().unwrap(), Bytes::from("chunk1"));
+        assert!((&mut finished).now_or_never().is_none());
+
+        drop(tx);
+        assert!(client_stream.next().await.is_none());
+        assert_eq!(finished.await, Ok(b"chunk1".to_vec()));
+    }
+
+    #[tokio::test]
+    async fn test_tee_stream_upstream_error_aborts() {
+        let chunks = vec![
+            Ok(Bytes::from("data: partial\n\n")),
+            Err(std::io::Error::other("connection reset")),
+        ];
+
+        let TeeResult {
+            mut client_stream,
+            buffer_handle,
+        } = StreamingProxy::tee_stream(stream::iter(chunks));
+
+        while client_stream.next().await.is_some() {}
+
+        assert_eq!(
+            buffer_handle.finish().await,
+            Err(StreamAbort::Upstream("connection reset".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tee_stream_dropped_client_aborts() {
+        let chunks = vec![Ok(Bytes::from("chunk1")), Ok(Bytes::from("chunk2"))];
+
+        let TeeResult {
+            mut client_stream,
+            buffer_handle,
+        } = StreamingProxy::tee_stream(stream::iter(chunks));
+
+        assert!(client_stream.next().await.is_some());
+        drop(client_stream);
+
+        assert_eq!(
+            buffer_handle.finish().await,
+            Err(StreamAbort::ClientDisconnected)
+        );
+    }
 }
@@ -1,4 +1,5 @@
 use crate::MnemoError;
+use crate::telemetry;
 use candle_core::{DType, Device, Tensor};
 use candle_nn::{Linear, Module, VarBuilder};
 use candle_transformers::models::bert::{BertModel, Config as BertConfig, DTYPE};
@@ -6,6 +7,7 @@ use hf_hub::{Repo, RepoType, api::sync::Api};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::Instant;
 use tokenizers::Tokenizer;
 
 const MODEL_ID: &str = "dslim/bert-base-NER";
@@ -134,6 +136,13 @@ impl NerModel {
     }
 
     pub fn extract_entities(&self, text: &str) -> Result<Vec<Entity>, MnemoError> {
+        let start = Instant::now();
+        let entities = self.run_inference(text);
+        telemetry::record_ner(start.elapsed());
+        entities
+    }
+
+    fn run_inference(&self, text: &str) -> Result<Vec<Entity>, MnemoError> {
         let encoding = self
             .tokenizer
             .encode(text, true)
@@ -1,14 +1,16 @@
 //! Memory compaction for Mnemo
 //!
 //! Implements automatic compression of old memories to reduce storage requirements
-//! while preserving searchability through embeddings.
+//! while preserving searchability through embeddings. When the store has an
+//! archive table, the original content is archived before the first
+//! compression so it can be rehydrated later.
 
 use chrono::{Duration, Utc};
 use std::collections::HashSet;
 use uuid::Uuid;
 
 use crate::error::{MnemoError, Result};
-use crate::memory::types::{CompressionLevel, StorageTier};
+use crate::memory::types::{CompressionLevel, Memory, StorageTier};
 use crate::storage::LanceStore;
 
 /// Configuration for memory compaction thresholds and policies
@@ -90,7 +92,7 @@ pub struct CompactionResult {
 /// - Full: Complete content preserved (no compression)
 /// - Summary: First N sentences extracted
 /// - Keywords: Significant words only
-/// - Hash: Metadata reference only (original content discarded)
+/// - Hash: Metadata reference only (original content kept in the archive)
 pub struct Compactor<'a> {
     store: &'a LanceStore,
     config: CompactionConfig,
@@ -121,7 +123,8 @@ impl<'a> Compactor<'a> {
     /// - Age > summary_age_days: Full → Summary
     /// - Age > keywords_age_days: Summary → Keywords
     ///
-    /// Memories with weight >= min_weight_to_preserve are skipped.
+    /// Memories with weight >= min_weight_to_preserve are skipped, as are
+    /// memories rehydrated to full content because they are retrieved often.
     ///
     /// # Arguments
     /// * `tier` - The storage tier to compact
@@ -129,10 +132,6 @@ impl<'a> Compactor<'a> {
     /// # Returns
     /// * `CompactionResult` with statistics about the compaction operation
     pub async fn compact(&self, tier: StorageTier) -> Result<CompactionResult> {
-        let now = Utc::now();
-        let summary_threshold = now - Duration::days(self.config.summary_age_days);
-        let keywords_threshold = now - Duration::days(self.config.keywords_age_days);
-
         let mut result = CompactionResult::default();
 
         let memories = self.store.list_by_tier(tier).await?;
@@ -143,11 +142,7 @@ impl<'a> Compactor<'a> {
                 continue;
             }
 
-            let target_compression = if memory.created_at < keywords_threshold {
-                CompressionLevel::Keywords
-            } else if memory.created_at < summary_threshold {
-                CompressionLevel::Summary
-            } else {
+            let Some(target_compression) = self.target_compression(&memory) else {
                 continue;
             };
 
@@ -158,6 +153,7 @@ impl<'a> Compactor<'a> {
                 continue;
             }
 
+            self.archive_original(&memory).await?;
             let compressed_content = self.apply_compression(&memory.content, target_compression);
 
             self.store
@@ -171,6 +167,24 @@ impl<'a> Compactor<'a> {
         Ok(result)
     }
 
+    /// The compression level a memory's age calls for, if any
+    ///
+    /// Rehydrated memories keep their full content.
+    pub fn target_compression(&self, memory: &Memory) -> Option<CompressionLevel> {
+        if memory.rehydrated {
+            return None;
+        }
+
+        let now = Utc::now();
+        if memory.created_at < now - Duration::days(self.config.keywords_age_days) {
+            Some(CompressionLevel::Keywords)
+        } else if memory.created_at < now - Duration::days(self.config.summary_age_days) {
+            Some(CompressionLevel::Summary)
+        } else {
+            None
+        }
+    }
+
     /// Compact a single memory to the specified compression level
     ///
     /// # Arguments
@@ -192,6 +206,18 @@ impl<'a> Compactor<'a> {
             .await?
             .ok_or_else(|| MnemoError::Memory(format!("Memory not found: {memory_id}")))?;
 
+        self.compact_memory(&memory, target_level).await
+    }
+
+    /// Compact an already loaded memory to the specified compression level
+    ///
+    /// Returns `Ok(false)` if the memory was skipped (high weight or already
+    /// compressed).
+    pub async fn compact_memory(
+        &self,
+        memory: &Memory,
+        target_level: CompressionLevel,
+    ) -> Result<bool> {
         if memory.weight >= self.config.min_weight_to_preserve {
             return Ok(false);
         }
@@ -202,15 +228,26 @@ impl<'a> Compactor<'a> {
             return Ok(false);
         }
 
+        self.archive_original(memory).await?;
         let compressed_content = self.apply_compression(&memory.content, target_level);
 
         self.store
-            .update_compression(memory_id, &compressed_content, target_level)
+            .update_compression(memory.id, &compressed_content, target_level)
             .await?;
 
         Ok(true)
     }
 
+    /// Archive a memory's content if it has not been compressed yet
+    async fn archive_original(&self, memory: &Memory) -> Result<()> {
+        if memory.compression == CompressionLevel::Full && self.store.archive_table().is_some() {
+            self.store
+                .archive_content(memory.id, &memory.content)
+                .await?;
+        }
+        Ok(())
+    }
+
     /// Apply compression to content based on target level
     fn apply_compression(&self, content: &str, level: CompressionLevel) -> String {
         match level {
@@ -773,5 +810,68 @@ mod tests {
             let after_old = store.get(old_id).await.unwrap().unwrap();
             assert_eq!(after_old.compression, CompressionLevel::Keywords);
         }
+
+        #[tokio::test]
+        async fn test_compact_archives_original_content() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+            store.create_memories_table().await.unwrap();
+            store.create_archive_table().await.unwrap();
+
+            let long_content = "This is a detailed memory with multiple sentences. \
+                It contains important information about the system. \
+                There are several key points to remember here. \
+                The database handles all persistence operations.";
+
+            let memory = create_test_memory(long_content, 45);
+            let id = memory.id;
+            store.insert(&memory).await.unwrap();
+
+            let compactor = Compactor::new(&store);
+            compactor.compact(StorageTier::Warm).await.unwrap();
+            compactor
+                .compact_single(id, CompressionLevel::Keywords)
+                .await
+                .unwrap();
+
+            let compacted = store.get(id).await.unwrap().unwrap();
+            assert_eq!(compacted.compression, CompressionLevel::Keywords);
+            assert_eq!(
+                store.get_archived(id).await.unwrap().as_deref(),
+                Some(long_content)
+            );
+
+            assert!(store.rehydrate(id).await.unwrap());
+            let restored = store.get(id).await.unwrap().unwrap();
+            assert_eq!(restored.content, long_content);
+            assert_eq!(restored.compression, CompressionLevel::Full);
+        }
+
+        #[tokio::test]
+        async fn test_compact_skips_rehydrated_memories() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+            store.create_memories_table().await.unwrap();
+            store.create_archive_table().await.unwrap();
+
+            let long_content = "This is a detailed memory with multiple sentences. \
+                It contains important information about the system. \
+                There are several key points to remember here.";
+
+            let memory = create_test_memory(long_content, 100);
+            let id = memory.id;
+            store.insert(&memory).await.unwrap();
+
+            let compactor = Compactor::new(&store);
+            compactor.compact(StorageTier::Warm).await.unwrap();
+            assert!(store.rehydrate(id).await.unwrap());
+
+            let result = compactor.compact(StorageTier::Warm).await.unwrap();
+            assert_eq!(result.compacted_count, 0);
+
+            let after = store.get(id).await.unwrap().unwrap();
+            assert_eq!(after.content, long_content);
+            assert_eq!(after.compression, CompressionLevel::Full);
+        }
     }
 }
@@ -186,11 +186,29 @@ impl<'a> Evictor<'a> {
     /// 4. Sort by eviction priority (lowest first)
     /// 5. Create tombstones and delete lowest priority memories until below eviction threshold
     pub async fn evict_if_needed(&self, tier: StorageTier) -> Result<Vec<Uuid>> {
+        let (candidates, reason) = self.plan_eviction(tier).await?;
+
+        let mut evicted = Vec::new();
+        for memory in candidates {
+            // Create tombstone and then delete
+            if self.evict_with_tombstone(&memory, reason.clone()).await? {
+                evicted.push(memory.id);
+            }
+        }
+
+        Ok(evicted)
+    }
+
+    /// Select the memories `evict_if_needed` would evict from a tier, lowest
+    /// priority first, along with the reason to record in their tombstones
+    ///
+    /// Returns no candidates when the tier is under its eviction threshold.
+    pub async fn plan_eviction(&self, tier: StorageTier) -> Result<(Vec<Memory>, EvictionReason)> {
         let status = self.check_capacity(tier).await?;
 
         match status {
             CapacityStatus::Normal | CapacityStatus::Warning => {
-                return Ok(Vec::new());
+                return Ok((Vec::new(), EvictionReason::LowWeight));
             }
             CapacityStatus::EvictionNeeded | CapacityStatus::AggressiveEvictionNeeded => {
                 // Continue with eviction
@@ -209,7 +227,7 @@ impl<'a> Evictor<'a> {
         let target_count = (self.config.max_memories_per_tier as f32 * target_ratio) as usize;
 
         if current_count <= target_count {
-            return Ok(Vec::new());
+            return Ok((Vec::new(), EvictionReason::LowWeight));
         }
 
         let to_evict_count = current_count - target_count;
@@ -236,15 +254,13 @@ impl<'a> Evictor<'a> {
             EvictionReason::LowWeight
         };
 
-        let mut evicted = Vec::new();
-        for (memory, _priority) in candidates.into_iter().take(to_evict_count) {
-            // Create tombstone and then delete
-            if self.evict_with_tombstone(&memory, reason.clone()).await? {
-                evicted.push(memory.id);
-            }
-        }
+        let selected = candidates
+            .into_iter()
+            .take(to_evict_count)
+            .map(|(memory, _priority)| memory)
+            .collect();
 
-        Ok(evicted)
+        Ok((selected, reason))
     }
 
     /// Get eviction candidates sorted by priority (lowest first).
@@ -296,7 +312,11 @@ impl<'a> Evictor<'a> {
 
     /// Create a tombstone and evict a memory.
     /// This is the preferred method for eviction as it preserves metadata.
-    async fn evict_with_tombstone(&self, memory: &Memory, reason: EvictionReason) -> Result<bool> {
+    pub async fn evict_with_tombstone(
+        &self,
+        memory: &Memory,
+        reason: EvictionReason,
+    ) -> Result<bool> {
         // Create tombstone first (before deleting)
         self.create_tombstone(memory, reason).await?;
 
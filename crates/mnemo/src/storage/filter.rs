@@ -1,30 +1,309 @@
 //! Filter types for memory search operations
 //!
 //! Provides filtering capabilities for vector similarity searches,
-//! allowing queries to be narrowed by memory type, weight, time, and conversation.
+//! allowing queries to be narrowed by memory type, source, weight, time,
+//! conversation, entities, tags, metadata, and more.
+//!
+//! Criteria are rendered through [`FilterExpr`], a typed expression that
+//! escapes every literal and can be composed with AND, OR and NOT.
+
+use std::ops::Not;
 
 use chrono::{DateTime, Utc};
+use serde_json::{Map, Value};
 
-use crate::memory::types::{MemoryType, StorageTier};
+use crate::memory::types::{CompressionLevel, MemorySource, MemoryType, StorageTier};
+
+/// A typed predicate over memory columns.
+///
+/// Expressions render to a SQL WHERE clause with [`FilterExpr::to_sql`].
+/// String literals are always quoted and escaped, so user input can be
+/// used in any predicate without risk of SQL injection.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterExpr {
+    /// Memory type is one of these
+    MemoryType(Vec<MemoryType>),
+    /// Source is one of these
+    Source(Vec<MemorySource>),
+    /// Memory is in this storage tier
+    Tier(StorageTier),
+    /// Memory is at this compression level
+    Compression(CompressionLevel),
+    /// Weight is at least this value
+    MinWeight(f32),
+    /// Weight is at most this value
+    MaxWeight(f32),
+    /// Created at or after this time
+    CreatedSince(DateTime<Utc>),
+    /// Created at or before this time
+    CreatedUntil(DateTime<Utc>),
+    /// Accessed at least this many times
+    MinAccessCount(u32),
+    /// Accessed at most this many times
+    MaxAccessCount(u32),
+    /// Belongs to this conversation
+    ConversationId(String),
+    /// Not associated with any conversation
+    Global,
+    /// Entities include this entity (exact match)
+    Entity(String),
+    /// Tags include this tag (exact match)
+    Tag(String),
+    /// Top-level metadata contains this key/value pair
+    Metadata(String, Value),
+    /// All of these hold; matches everything when empty
+    And(Vec<FilterExpr>),
+    /// At least one of these holds; matches nothing when empty
+    Or(Vec<FilterExpr>),
+    /// The expression does not hold
+    Not(Box<FilterExpr>),
+}
+
+impl FilterExpr {
+    /// Combine with another expression using AND
+    pub fn and(self, other: FilterExpr) -> FilterExpr {
+        let mut exprs = match self {
+            FilterExpr::And(exprs) => exprs,
+            expr => vec![expr],
+        };
+        match other {
+            FilterExpr::And(others) => exprs.extend(others),
+            expr => exprs.push(expr),
+        }
+        FilterExpr::And(exprs)
+    }
+
+    /// Combine with another expression using OR
+    pub fn or(self, other: FilterExpr) -> FilterExpr {
+        let mut exprs = match self {
+            FilterExpr::Or(exprs) => exprs,
+            expr => vec![expr],
+        };
+        match other {
+            FilterExpr::Or(others) => exprs.extend(others),
+            expr => exprs.push(expr),
+        }
+        FilterExpr::Or(exprs)
+    }
+
+    /// Render this expression as a SQL WHERE clause
+    pub fn to_sql(&self) -> String {
+        match self {
+            FilterExpr::MemoryType(types) => {
+                Self::in_clause("memory_type", types.iter().map(|t| memory_type_name(*t)))
+            }
+            FilterExpr::Source(sources) => {
+                Self::in_clause("source", sources.iter().map(|s| source_name(*s)))
+            }
+            FilterExpr::Tier(tier) => format!("tier = {}", quote(tier_name(*tier))),
+            FilterExpr::Compression(level) => {
+                format!("compression = {}", quote(compression_name(*level)))
+            }
+            // NaN and infinity are not valid SQL literals; stored weights are
+            // finite, so non-finite bounds render as the comparison's result
+            FilterExpr::MinWeight(weight) if !weight.is_finite() => {
+                sql_bool(*weight == f32::NEG_INFINITY)
+            }
+            FilterExpr::MaxWeight(weight) if !weight.is_finite() => {
+                sql_bool(*weight == f32::INFINITY)
+            }
+            FilterExpr::MinWeight(weight) => format!("weight >= {weight}"),
+            FilterExpr::MaxWeight(weight) => format!("weight <= {weight}"),
+            // created_at is stored as microseconds since epoch
+            FilterExpr::CreatedSince(since) => {
+                format!("created_at >= {}", since.timestamp_micros())
+            }
+            FilterExpr::CreatedUntil(until) => {
+                format!("created_at <= {}", until.timestamp_micros())
+            }
+            FilterExpr::MinAccessCount(count) => format!("access_count >= {count}"),
+            FilterExpr::MaxAccessCount(count) => format!("access_count <= {count}"),
+            FilterExpr::ConversationId(id) => format!("conversation_id = {}", quote(id)),
+            FilterExpr::Global => "conversation_id IS NULL".to_string(),
+            // Entities and tags are stored comma-joined
+            FilterExpr::Entity(entity) => Self::list_contains("entities", entity),
+            FilterExpr::Tag(tag) => Self::list_contains("tags", tag),
+            // Top-level pairs are stored one per line, see `metadata_pairs`
+            FilterExpr::Metadata(key, value) => format!(
+                "strpos(metadata_pairs, {}) > 0",
+                quote(&format!("\n{}\n", metadata_pair(key, value)))
+            ),
+            FilterExpr::And(exprs) => match exprs.as_slice() {
+                [] => "TRUE".to_string(),
+                [expr] => expr.to_sql(),
+                exprs => exprs
+                    .iter()
+                    .map(FilterExpr::to_sql)
+                    .collect::<Vec<_>>()
+                    .join(" AND "),
+            },
+            FilterExpr::Or(exprs) => match exprs.as_slice() {
+                [] => "FALSE".to_string(),
+                [expr] => expr.to_sql(),
+                exprs => format!(
+                    "({})",
+                    exprs
+                        .iter()
+                        .map(FilterExpr::to_sql)
+                        .collect::<Vec<_>>()
+                        .join(" OR ")
+                ),
+            },
+            FilterExpr::Not(expr) => format!("NOT ({})", expr.to_sql()),
+        }
+    }
+
+    fn in_clause<'a>(column: &str, values: impl Iterator<Item = &'a str>) -> String {
+        let values: Vec<String> = values.map(quote).collect();
+        match values.as_slice() {
+            [] => "FALSE".to_string(),
+            [value] => format!("{column} = {value}"),
+            values => format!("{column} IN ({})", values.join(", ")),
+        }
+    }
+
+    fn list_contains(column: &str, item: &str) -> String {
+        format!(
+            "strpos(',' || {column} || ',', {}) > 0",
+            quote(&format!(",{item},"))
+        )
+    }
+}
+
+impl Not for FilterExpr {
+    type Output = FilterExpr;
+
+    fn not(self) -> FilterExpr {
+        match self {
+            FilterExpr::Not(expr) => *expr,
+            expr => FilterExpr::Not(Box::new(expr)),
+        }
+    }
+}
+
+/// Top-level metadata pairs as stored in the `metadata_pairs` column
+///
+/// Each pair is written as compact JSON `"key":value` on its own line, and
+/// the list starts and ends with a newline. Compact JSON never contains a
+/// raw newline, so a pair can only match a top-level key. Numbers are
+/// written in canonical form, see `canonical_value`.
+pub(crate) fn metadata_pairs(metadata: &Map<String, Value>) -> String {
+    let mut pairs = String::from("\n");
+    for (key, value) in metadata {
+        pairs.push_str(&metadata_pair(key, value));
+        pairs.push('\n');
+    }
+    pairs
+}
+
+fn metadata_pair(key: &str, value: &Value) -> String {
+    format!("{}:{}", Value::from(key), canonical_value(value))
+}
+
+/// Write integral floats as integers, at any depth
+///
+/// `1.0` and `1` then render the same, so a metadata filter matches the
+/// number whichever way it was written.
+fn canonical_value(value: &Value) -> Value {
+    match value {
+        Value::Number(number) if number.is_f64() => match number.as_f64() {
+            Some(float) if float.fract() == 0.0 && float.abs() < i64::MAX as f64 => {
+                Value::from(float as i64)
+            }
+            _ => value.clone(),
+        },
+        Value::Array(items) => Value::Array(items.iter().map(canonical_value).collect()),
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .map(|(key, value)| (key.clone(), canonical_value(value)))
+                .collect(),
+        ),
+        _ => value.clone(),
+    }
+}
+
+fn sql_bool(value: bool) -> String {
+    if value { "TRUE" } else { "FALSE" }.to_string()
+}
+
+/// Quote a string literal, escaping embedded single quotes
+fn quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+fn memory_type_name(memory_type: MemoryType) -> &'static str {
+    match memory_type {
+        MemoryType::Episodic => "Episodic",
+        MemoryType::Semantic => "Semantic",
+        MemoryType::Procedural => "Procedural",
+    }
+}
+
+fn source_name(source: MemorySource) -> &'static str {
+    match source {
+        MemorySource::Conversation => "Conversation",
+        MemorySource::File => "File",
+        MemorySource::Web => "Web",
+        MemorySource::Manual => "Manual",
+    }
+}
+
+fn tier_name(tier: StorageTier) -> &'static str {
+    match tier {
+        StorageTier::Hot => "Hot",
+        StorageTier::Warm => "Warm",
+        StorageTier::Cold => "Cold",
+    }
+}
+
+fn compression_name(level: CompressionLevel) -> &'static str {
+    match level {
+        CompressionLevel::Full => "Full",
+        CompressionLevel::Summary => "Summary",
+        CompressionLevel::Keywords => "Keywords",
+        CompressionLevel::Hash => "Hash",
+    }
+}
 
 /// Filter criteria for memory search operations.
 ///
 /// All fields are optional - when `None`, that filter is not applied.
-/// Multiple filters are combined with AND logic.
+/// Multiple filters are combined with AND logic; use [`MemoryFilter::with_expr`]
+/// for OR and NOT.
 #[derive(Debug, Clone, Default)]
 pub struct MemoryFilter {
     /// Filter by specific memory types (OR logic within this filter)
     pub memory_types: Option<Vec<MemoryType>>,
     /// Minimum weight threshold (inclusive)
     pub min_weight: Option<f32>,
+    /// Maximum weight threshold (inclusive)
+    pub max_weight: Option<f32>,
     /// Only return memories created after this time
     pub since: Option<DateTime<Utc>>,
+    /// Only return memories created before this time
+    pub until: Option<DateTime<Utc>>,
     /// Filter to specific conversation
     pub conversation_id: Option<String>,
     /// Session filter: None = not set, Some(None) = global only, Some(Some(id)) = session or global
     pub session_filter: Option<Option<String>>,
     /// Filter by storage tier
     pub tier: Option<StorageTier>,
+    /// Filter by memory sources (OR logic within this filter)
+    pub sources: Option<Vec<MemorySource>>,
+    /// Filter by compression level
+    pub compression: Option<CompressionLevel>,
+    /// Minimum access count (inclusive)
+    pub min_access_count: Option<u32>,
+    /// Maximum access count (inclusive)
+    pub max_access_count: Option<u32>,
+    /// Only return memories mentioning all of these entities
+    pub entities: Option<Vec<String>>,
+    /// Only return memories carrying all of these tags
+    pub tags: Option<Vec<String>>,
+    /// Only return memories whose top-level metadata contains all of these pairs
+    pub metadata: Option<Map<String, Value>>,
+    /// Additional expression that must also hold
+    pub expr: Option<FilterExpr>,
 }
 
 impl MemoryFilter {
@@ -45,12 +324,24 @@ impl MemoryFilter {
         self
     }
 
+    /// Filter by maximum weight
+    pub fn with_max_weight(mut self, max_weight: f32) -> Self {
+        self.max_weight = Some(max_weight);
+        self
+    }
+
     /// Filter by creation time
     pub fn since(mut self, since: DateTime<Utc>) -> Self {
         self.since = Some(since);
         self
     }
 
+    /// Filter by latest creation time
+    pub fn until(mut self, until: DateTime<Utc>) -> Self {
+        self.until = Some(until);
+        self
+    }
+
     /// Filter by conversation ID
     pub fn with_conversation_id(mut self, conversation_id: String) -> Self {
         self.conversation_id = Some(conversation_id);
@@ -61,11 +352,9 @@ impl MemoryFilter {
     /// When `Some(id)`: matches memories for that session OR global memories (conversation_id IS NULL)
     /// When `None`: matches only global memories (conversation_id IS NULL)
     ///
-    /// # Security Note
-    /// The session_id is interpolated directly into a SQL WHERE clause. Callers MUST validate
-    /// session IDs before passing them here. Use `SessionId::try_from()` to ensure the value
-    /// contains only safe characters (`[a-zA-Z0-9_-]`). Passing unvalidated user input could
-    /// allow SQL injection.
+    /// The session ID is escaped when the filter is rendered, but callers should
+    /// still validate it with `SessionId::try_from()` so that only well-formed IDs
+    /// (`[a-zA-Z0-9_-]`) reach storage.
     pub fn with_session_filter(mut self, session_id: Option<String>) -> Self {
         self.session_filter = Some(session_id);
         self
@@ -76,50 +365,88 @@ impl MemoryFilter {
         self
     }
 
-    /// Build a SQL WHERE clause from this filter.
+    /// Filter by memory sources
+    pub fn with_sources(mut self, sources: Vec<MemorySource>) -> Self {
+        self.sources = Some(sources);
+        self
+    }
+
+    /// Filter by compression level
+    pub fn with_compression(mut self, compression: CompressionLevel) -> Self {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// Filter by minimum access count
+    pub fn with_min_access_count(mut self, count: u32) -> Self {
+        self.min_access_count = Some(count);
+        self
+    }
+
+    /// Filter by maximum access count
+    pub fn with_max_access_count(mut self, count: u32) -> Self {
+        self.max_access_count = Some(count);
+        self
+    }
+
+    /// Filter by entities; memories must mention every entity
+    pub fn with_entities(mut self, entities: Vec<String>) -> Self {
+        self.entities = Some(entities);
+        self
+    }
+
+    /// Filter by tags; memories must carry every tag
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = Some(tags);
+        self
+    }
+
+    /// Filter by a metadata key/value pair; may be called repeatedly
+    pub fn with_metadata(mut self, key: impl Into<String>, value: Value) -> Self {
+        self.metadata
+            .get_or_insert_with(Map::new)
+            .insert(key.into(), value);
+        self
+    }
+
+    /// Require an arbitrary expression; repeated calls are combined with AND
+    pub fn with_expr(mut self, expr: FilterExpr) -> Self {
+        self.expr = Some(match self.expr.take() {
+            Some(existing) => existing.and(expr),
+            None => expr,
+        });
+        self
+    }
+
+    /// Build the expression this filter represents.
     /// Returns `None` if no filters are set.
-    pub fn to_sql_clause(&self) -> Option<String> {
+    pub fn to_expr(&self) -> Option<FilterExpr> {
         let mut conditions = Vec::new();
 
-        // Memory types filter (OR within types)
         if let Some(ref types) = self.memory_types {
             if !types.is_empty() {
-                let type_strs: Vec<&str> = types
-                    .iter()
-                    .map(|t| match t {
-                        MemoryType::Episodic => "Episodic",
-                        MemoryType::Semantic => "Semantic",
-                        MemoryType::Procedural => "Procedural",
-                    })
-                    .collect();
-
-                if type_strs.len() == 1 {
-                    conditions.push(format!("memory_type = '{}'", type_strs[0]));
-                } else {
-                    let in_clause = type_strs
-                        .iter()
-                        .map(|s| format!("'{s}'"))
-                        .collect::<Vec<_>>()
-                        .join(", ");
-                    conditions.push(format!("memory_type IN ({in_clause})"));
-                }
+                conditions.push(FilterExpr::MemoryType(types.clone()));
             }
         }
 
-        // Min weight filter
         if let Some(min_weight) = self.min_weight {
-            conditions.push(format!("weight >= {min_weight}"));
+            conditions.push(FilterExpr::MinWeight(min_weight));
         }
 
-        // Since filter (created_at is stored as microseconds since epoch)
-        if let Some(ref since) = self.since {
-            let micros = since.timestamp_micros();
-            conditions.push(format!("created_at >= {micros}"));
+        if let Some(max_weight) = self.max_weight {
+            conditions.push(FilterExpr::MaxWeight(max_weight));
+        }
+
+        if let Some(since) = self.since {
+            conditions.push(FilterExpr::CreatedSince(since));
+        }
+
+        if let Some(until) = self.until {
+            conditions.push(FilterExpr::CreatedUntil(until));
         }
 
-        // Conversation ID filter
         if let Some(ref conv_id) = self.conversation_id {
-            conditions.push(format!("conversation_id = '{conv_id}'"));
+            conditions.push(FilterExpr::ConversationId(conv_id.clone()));
         }
 
         // Session filter with NULL awareness
@@ -127,42 +454,86 @@ impl MemoryFilter {
             match session_opt {
                 Some(session_id) => {
                     // Session specified: match session memories OR global memories
-                    conditions.push(format!(
-                        "(conversation_id = '{session_id}' OR conversation_id IS NULL)"
-                    ));
+                    conditions.push(
+                        FilterExpr::ConversationId(session_id.clone()).or(FilterExpr::Global),
+                    );
                 }
                 None => {
                     // Explicitly None: match only global memories
-                    conditions.push("conversation_id IS NULL".to_string());
+                    conditions.push(FilterExpr::Global);
                 }
             }
         }
 
-        // Tier filter
-        if let Some(ref tier) = self.tier {
-            let tier_str = match tier {
-                StorageTier::Hot => "Hot",
-                StorageTier::Warm => "Warm",
-                StorageTier::Cold => "Cold",
-            };
-            conditions.push(format!("tier = '{tier_str}'"));
+        if let Some(tier) = self.tier {
+            conditions.push(FilterExpr::Tier(tier));
+        }
+
+        if let Some(ref sources) = self.sources {
+            if !sources.is_empty() {
+                conditions.push(FilterExpr::Source(sources.clone()));
+            }
         }
 
-        if conditions.is_empty() {
-            None
-        } else {
-            Some(conditions.join(" AND "))
+        if let Some(compression) = self.compression {
+            conditions.push(FilterExpr::Compression(compression));
         }
+
+        if let Some(count) = self.min_access_count {
+            conditions.push(FilterExpr::MinAccessCount(count));
+        }
+
+        if let Some(count) = self.max_access_count {
+            conditions.push(FilterExpr::MaxAccessCount(count));
+        }
+
+        if let Some(ref entities) = self.entities {
+            conditions.extend(entities.iter().cloned().map(FilterExpr::Entity));
+        }
+
+        if let Some(ref tags) = self.tags {
+            conditions.extend(tags.iter().cloned().map(FilterExpr::Tag));
+        }
+
+        if let Some(ref metadata) = self.metadata {
+            conditions.extend(
+                metadata
+                    .iter()
+                    .map(|(key, value)| FilterExpr::Metadata(key.clone(), value.clone())),
+            );
+        }
+
+        if let Some(ref expr) = self.expr {
+            conditions.push(expr.clone());
+        }
+
+        conditions.into_iter().reduce(FilterExpr::and)
+    }
+
+    /// Build a SQL WHERE clause from this filter.
+    /// Returns `None` if no filters are set.
+    pub fn to_sql_clause(&self) -> Option<String> {
+        self.to_expr().map(|expr| expr.to_sql())
     }
 
     /// Check if this filter is empty (no conditions set)
     pub fn is_empty(&self) -> bool {
         self.memory_types.is_none()
             && self.min_weight.is_none()
+            && self.max_weight.is_none()
             && self.since.is_none()
+            && self.until.is_none()
             && self.conversation_id.is_none()
             && self.session_filter.is_none()
             && self.tier.is_none()
+            && self.sources.is_none()
+            && self.compression.is_none()
+            && self.min_access_count.is_none()
+            && self.max_access_count.is_none()
+            && self.entities.is_none()
+            && self.tags.is_none()
+            && self.metadata.is_none()
+            && self.expr.is_none()
     }
 }
 
@@ -202,6 +573,30 @@ mod tests {
         assert_eq!(sql, "weight >= 0.5");
     }
 
+    #[test]
+    fn test_non_finite_weight_bounds() {
+        let sql = |filter: MemoryFilter| filter.to_sql_clause().unwrap();
+
+        assert_eq!(sql(MemoryFilter::new().with_min_weight(f32::NAN)), "FALSE");
+        assert_eq!(sql(MemoryFilter::new().with_max_weight(f32::NAN)), "FALSE");
+        assert_eq!(
+            sql(MemoryFilter::new().with_min_weight(f32::NEG_INFINITY)),
+            "TRUE"
+        );
+        assert_eq!(
+            sql(MemoryFilter::new().with_min_weight(f32::INFINITY)),
+            "FALSE"
+        );
+        assert_eq!(
+            sql(MemoryFilter::new().with_max_weight(f32::INFINITY)),
+            "TRUE"
+        );
+        assert_eq!(
+            sql(MemoryFilter::new().with_max_weight(f32::NEG_INFINITY)),
+            "FALSE"
+        );
+    }
+
     #[test]
     fn test_conversation_id_filter() {
         let filter = MemoryFilter::new().with_conversation_id("conv-123".to_string());
@@ -252,4 +647,141 @@ mod tests {
         let sql = filter.to_sql_clause().unwrap();
         assert_eq!(sql, "conversation_id IS NULL");
     }
+
+    #[test]
+    fn test_tags_filter() {
+        let filter =
+            MemoryFilter::new().with_tags(vec!["rust".to_string(), "o'reilly".to_string()]);
+        assert!(!filter.is_empty());
+
+        let sql = filter.to_sql_clause().unwrap();
+        assert_eq!(
+            sql,
+            "strpos(',' || tags || ',', ',rust,') > 0 \
+             AND strpos(',' || tags || ',', ',o''reilly,') > 0"
+        );
+    }
+
+    #[test]
+    fn test_metadata_filter() {
+        let filter = MemoryFilter::new()
+            .with_metadata("repo", Value::from("watzon/mnemo"))
+            .with_metadata("stars", Value::from(42));
+        assert!(!filter.is_empty());
+
+        let sql = filter.to_sql_clause().unwrap();
+        assert!(sql.contains("'\n\"repo\":\"watzon/mnemo\"\n'"));
+        assert!(sql.contains("'\n\"stars\":42\n'"));
+        assert!(sql.contains(" AND "));
+    }
+
+    #[test]
+    fn test_metadata_pairs_are_top_level_only() {
+        let metadata = serde_json::json!({"stars": 4, "repo": {"stars": 42}});
+        let pairs = metadata_pairs(metadata.as_object().unwrap());
+
+        assert_eq!(pairs, "\n\"repo\":{\"stars\":42}\n\"stars\":4\n");
+        assert!(!pairs.contains("\n\"stars\":42\n"));
+        assert_eq!(metadata_pairs(&Map::new()), "\n");
+    }
+
+    #[test]
+    fn test_metadata_numbers_are_canonical() {
+        let metadata = serde_json::json!({"stars": 1.0, "repo": {"forks": [2.0, 2.5]}});
+        let pairs = metadata_pairs(metadata.as_object().unwrap());
+        assert_eq!(pairs, "\n\"repo\":{\"forks\":[2,2.5]}\n\"stars\":1\n");
+
+        for stars in [Value::from(1), Value::from(1.0)] {
+            let sql = MemoryFilter::new()
+                .with_metadata("stars", stars)
+                .to_sql_clause()
+                .unwrap();
+            assert_eq!(sql, "strpos(metadata_pairs, '\n\"stars\":1\n') > 0");
+        }
+    }
+
+    #[test]
+    fn test_new_predicates() {
+        use chrono::TimeZone;
+        let until = Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap();
+        let filter = MemoryFilter::new()
+            .with_max_weight(0.9)
+            .until(until)
+            .with_sources(vec![MemorySource::Manual, MemorySource::File])
+            .with_compression(CompressionLevel::Full)
+            .with_min_access_count(2)
+            .with_max_access_count(10)
+            .with_entities(vec!["Rust".to_string()]);
+        assert!(!filter.is_empty());
+
+        let sql = filter.to_sql_clause().unwrap();
+        assert_eq!(
+            sql,
+            format!(
+                "weight <= 0.9 AND created_at <= {} AND source IN ('Manual', 'File') \
+                 AND compression = 'Full' AND access_count >= 2 AND access_count <= 10 \
+                 AND strpos(',' || entities || ',', ',Rust,') > 0",
+                until.timestamp_micros()
+            )
+        );
+    }
+
+    #[test]
+    fn test_expr_or_and_not() {
+        let expr = FilterExpr::MemoryType(vec![MemoryType::Semantic])
+            .or(FilterExpr::Source(vec![MemorySource::Manual]).and(!FilterExpr::Global));
+        let filter = MemoryFilter::new().with_min_weight(0.5).with_expr(expr);
+
+        let sql = filter.to_sql_clause().unwrap();
+        assert_eq!(
+            sql,
+            "weight >= 0.5 AND (memory_type = 'Semantic' \
+             OR source = 'Manual' AND NOT (conversation_id IS NULL))"
+        );
+    }
+
+    #[test]
+    fn test_expr_combinators_flatten() {
+        let expr = FilterExpr::Tag("a".to_string())
+            .or(FilterExpr::Tag("b".to_string()))
+            .or(FilterExpr::Tag("c".to_string()));
+        assert!(matches!(expr, FilterExpr::Or(ref exprs) if exprs.len() == 3));
+
+        let expr =
+            FilterExpr::Global.and(FilterExpr::MinWeight(0.1).and(FilterExpr::MaxWeight(0.9)));
+        assert!(matches!(expr, FilterExpr::And(ref exprs) if exprs.len() == 3));
+
+        assert_eq!(!!FilterExpr::Global, FilterExpr::Global);
+    }
+
+    #[test]
+    fn test_expr_empty_groups() {
+        assert_eq!(FilterExpr::And(Vec::new()).to_sql(), "TRUE");
+        assert_eq!(FilterExpr::Or(Vec::new()).to_sql(), "FALSE");
+        assert_eq!(FilterExpr::Source(Vec::new()).to_sql(), "FALSE");
+    }
+
+    #[test]
+    fn test_repeated_with_expr_combines_with_and() {
+        let filter = MemoryFilter::new()
+            .with_expr(FilterExpr::Tier(StorageTier::Hot))
+            .with_expr(!FilterExpr::Compression(CompressionLevel::Hash));
+
+        let sql = filter.to_sql_clause().unwrap();
+        assert_eq!(sql, "tier = 'Hot' AND NOT (compression = 'Hash')");
+    }
+
+    #[test]
+    fn test_literals_are_escaped() {
+        let filter = MemoryFilter::new()
+            .with_conversation_id("x' OR '1'='1".to_string())
+            .with_entities(vec!["O'Brien".to_string()]);
+
+        let sql = filter.to_sql_clause().unwrap();
+        assert_eq!(
+            sql,
+            "conversation_id = 'x'' OR ''1''=''1' \
+             AND strpos(',' || entities || ',', ',O''Brien,') > 0"
+        );
+    }
 }
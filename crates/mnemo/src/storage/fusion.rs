@@ -0,0 +1,84 @@
+//! Reciprocal rank fusion for Mnemo
+//!
+//! Merges several ranked result lists into one. Used by hybrid search in the
+//! store and by multi-query retrieval in the pipeline.
+
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+
+use uuid::Uuid;
+
+/// Rank constant for reciprocal rank fusion; damps the lead of top ranks
+pub const RRF_K: f32 = 60.0;
+
+/// Fuse weighted rankings with reciprocal rank fusion
+///
+/// An item scores `weight / (RRF_K + rank)` summed over the rankings it
+/// appears in, with `id` identifying items across rankings. When an item
+/// appears again, `merge` folds the new copy into the one already kept.
+///
+/// Returns each item with its fused score, in no particular order; callers
+/// sort with their own tie-breakers.
+pub fn fuse_rankings<T>(
+    rankings: Vec<(Vec<T>, f32)>,
+    id: impl Fn(&T) -> Uuid,
+    mut merge: impl FnMut(&mut T, T),
+) -> Vec<(T, f32)> {
+    let mut fused: HashMap<Uuid, (T, f32)> = HashMap::new();
+
+    for (ranking, weight) in rankings {
+        for (rank, item) in ranking.into_iter().enumerate() {
+            let score = weight / (RRF_K + rank as f32 + 1.0);
+            match fused.entry(id(&item)) {
+                Entry::Occupied(mut entry) => {
+                    let (kept, fused_score) = entry.get_mut();
+                    *fused_score += score;
+                    merge(kept, item);
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert((item, score));
+                }
+            }
+        }
+    }
+
+    fused.into_values().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fuse(rankings: Vec<(Vec<(Uuid, u32)>, f32)>) -> Vec<((Uuid, u32), f32)> {
+        let mut fused = fuse_rankings(rankings, |(id, _)| *id, |kept, (_, n)| kept.1 += n);
+        fused.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        fused
+    }
+
+    #[test]
+    fn test_items_in_several_rankings_come_first() {
+        let shared = Uuid::new_v4();
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+
+        let fused = fuse(vec![
+            (vec![(first, 1), (shared, 1)], 1.0),
+            (vec![(second, 1), (shared, 1)], 1.0),
+        ]);
+
+        assert_eq!(fused.len(), 3);
+        assert_eq!(fused[0].0, (shared, 2));
+        assert!((fused[0].1 - 2.0 / (RRF_K + 2.0)).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_weights_scale_rank_scores() {
+        let light = Uuid::new_v4();
+        let heavy = Uuid::new_v4();
+
+        let fused = fuse(vec![(vec![(light, 1)], 0.25), (vec![(heavy, 1)], 1.0)]);
+
+        assert_eq!(fused[0].0.0, heavy);
+        assert!((fused[1].1 - 0.25 / (RRF_K + 1.0)).abs() < f32::EPSILON);
+    }
+}
@@ -2,8 +2,8 @@ use std::path::Path;
 use std::sync::Arc;
 
 use arrow_array::{
-    Array, FixedSizeListArray, Float32Array, Int32Array, RecordBatch, RecordBatchIterator,
-    StringArray, TimestampMicrosecondArray,
+    Array, BinaryArray, BooleanArray, FixedSizeListArray, Float32Array, Int32Array, RecordBatch,
+    RecordBatchIterator, StringArray, TimestampMicrosecondArray,
 };
 use arrow_schema::{DataType, Field, Schema, TimeUnit};
 use chrono::{TimeZone, Utc};
@@ -11,23 +11,135 @@ use futures::TryStreamExt;
 use lancedb::Table;
 use lancedb::connection::Connection;
 use lancedb::index::Index;
+use lancedb::index::scalar::{FtsIndexBuilder, FullTextSearchQuery};
 use lancedb::index::vector::IvfPqIndexBuilder;
 use lancedb::query::{ExecutableQuery, QueryBase};
+use lancedb::table::NewColumnTransform;
+use sha2::{Digest, Sha256};
 use uuid::Uuid;
 
+use crate::embedding::EmbeddingModel;
 use crate::error::{MnemoError, Result};
 use crate::memory::tombstone::{EvictionReason, Tombstone};
 use crate::memory::types::{CompressionLevel, Memory, MemorySource, MemoryType, StorageTier};
-use crate::storage::filter::MemoryFilter;
+use crate::storage::filter::{self, MemoryFilter};
+use crate::storage::fusion::fuse_rankings;
+use crate::telemetry::OperationTimer;
 
 const EMBEDDING_DIMENSIONS: i32 = 384;
 const MEMORIES_TABLE: &str = "memories";
 const TOMBSTONES_TABLE: &str = "tombstones";
+const ARCHIVE_TABLE: &str = "archive";
+const ARCHIVE_REFS_TABLE: &str = "archive_refs";
+const ARCHIVE_ZSTD_LEVEL: i32 = 3;
+
+const SCHEMA_VERSIONS_TABLE: &str = "schema_versions";
+
+/// Columns covered by the full-text index
+const FTS_COLUMNS: [&str; 2] = ["content", "entities"];
+
+/// Memories written per batch when a migration rewrites the memories table
+const REWRITE_BATCH_SIZE: usize = 256;
+
+/// Schema version of the table layouts written by this build
+pub const SCHEMA_VERSION: u32 = 4;
+
+/// One step of a schema migration
+///
+/// Column steps name their table, so migrations cover the tombstones table as
+/// well as memories; rewriting and re-embedding only touch memories.
+#[derive(Debug, Clone, Copy)]
+pub enum MigrationStep {
+    /// Add a column to a table, filled with a SQL expression
+    AddColumn {
+        table: &'static str,
+        column: &'static str,
+        expression: &'static str,
+    },
+    /// Set a column on every row from a SQL expression
+    Backfill {
+        table: &'static str,
+        column: &'static str,
+        expression: &'static str,
+    },
+    /// Recompute every memory's embedding from its content
+    ///
+    /// Runs once after all other steps of the pending migrations.
+    Reembed,
+    /// Rewrite every memory so columns derived from its fields are filled
+    ///
+    /// Runs once after all other steps of the pending migrations, together
+    /// with re-embedding when both are pending. `pending` is a SQL filter
+    /// matching memories the rewrite has not reached yet, so an unversioned
+    /// store interrupted before the rewrite is not detected as migrated.
+    Rewrite { pending: &'static str },
+}
+
+/// A numbered set of steps upgrading the store from the previous version
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    pub version: u32,
+    pub description: &'static str,
+    pub steps: &'static [MigrationStep],
+}
+
+/// Ordered schema migrations; the last one's version is [`SCHEMA_VERSION`]
+///
+/// Version 1 is the layout of stores created before versioning, which have
+/// no version record and are detected from their columns.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "Initial schema",
+        steps: &[],
+    },
+    Migration {
+        version: 2,
+        description: "Add memory tags and metadata",
+        steps: &[
+            MigrationStep::AddColumn {
+                table: MEMORIES_TABLE,
+                column: "tags",
+                expression: "''",
+            },
+            MigrationStep::AddColumn {
+                table: MEMORIES_TABLE,
+                column: "metadata",
+                expression: "'{}'",
+            },
+        ],
+    },
+    Migration {
+        version: 3,
+        description: "Mark rehydrated memories",
+        steps: &[MigrationStep::AddColumn {
+            table: MEMORIES_TABLE,
+            column: "rehydrated",
+            expression: "false",
+        }],
+    },
+    Migration {
+        version: 4,
+        description: "Index top-level metadata pairs",
+        steps: &[
+            MigrationStep::AddColumn {
+                table: MEMORIES_TABLE,
+                column: "metadata_pairs",
+                expression: "'\n'",
+            },
+            MigrationStep::Rewrite {
+                pending: "metadata_pairs = '\n' AND metadata <> '{}'",
+            },
+        ],
+    },
+];
 
 pub struct LanceStore {
     connection: Connection,
     memories_table: Option<Table>,
     tombstones_table: Option<Table>,
+    archive_table: Option<Table>,
+    archive_refs_table: Option<Table>,
 }
 
 impl LanceStore {
@@ -45,6 +157,8 @@ impl LanceStore {
             connection,
             memories_table: None,
             tombstones_table: None,
+            archive_table: None,
+            archive_refs_table: None,
         })
     }
 
@@ -78,6 +192,10 @@ impl LanceStore {
             Field::new("tier", DataType::Utf8, false),
             Field::new("compression", DataType::Utf8, false),
             Field::new("entities", DataType::Utf8, false),
+            Field::new("tags", DataType::Utf8, false),
+            Field::new("metadata", DataType::Utf8, false),
+            Field::new("rehydrated", DataType::Boolean, false),
+            Field::new("metadata_pairs", DataType::Utf8, false),
         ]))
     }
 
@@ -101,12 +219,32 @@ impl LanceStore {
         ]))
     }
 
+    fn archive_schema() -> Arc<Schema> {
+        Arc::new(Schema::new(vec![
+            Field::new("hash", DataType::Utf8, false),
+            Field::new("content", DataType::Binary, false),
+            Field::new(
+                "archived_at",
+                DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+                false,
+            ),
+        ]))
+    }
+
+    fn archive_refs_schema() -> Arc<Schema> {
+        Arc::new(Schema::new(vec![
+            Field::new("memory_id", DataType::Utf8, false),
+            Field::new("hash", DataType::Utf8, false),
+        ]))
+    }
+
     fn create_empty_batch(schema: Arc<Schema>) -> RecordBatch {
         let empty_strings: Vec<Option<&str>> = vec![];
         let empty_floats: Vec<f32> = vec![];
         let empty_timestamps: Vec<i64> = vec![];
         let empty_ints: Vec<i32> = vec![];
         let empty_embeddings: Vec<Option<Vec<Option<f32>>>> = vec![];
+        let empty_bools: Vec<bool> = vec![];
 
         RecordBatch::try_new(
             schema,
@@ -129,6 +267,10 @@ impl LanceStore {
                 Arc::new(StringArray::from(empty_strings.clone())),
                 Arc::new(StringArray::from(empty_strings.clone())),
                 Arc::new(StringArray::from(empty_strings.clone())),
+                Arc::new(StringArray::from(empty_strings.clone())),
+                Arc::new(StringArray::from(empty_strings.clone())),
+                Arc::new(StringArray::from(empty_strings.clone())),
+                Arc::new(BooleanArray::from(empty_bools)),
                 Arc::new(StringArray::from(empty_strings)),
             ],
         )
@@ -148,6 +290,12 @@ impl LanceStore {
             .map_err(|e| MnemoError::Storage(format!("Failed to create memories table: {e}")))?;
 
         self.memories_table = Some(table);
+
+        if self.schema_version().await?.is_none() {
+            self.record_schema_version(MIGRATIONS[MIGRATIONS.len() - 1])
+                .await?;
+        }
+
         Ok(())
     }
 
@@ -190,6 +338,37 @@ impl LanceStore {
         .expect("Schema matches columns")
     }
 
+    /// Create the content-addressed archive and its memory to hash mapping
+    pub async fn create_archive_table(&mut self) -> Result<()> {
+        let schema = Self::archive_schema();
+        let batch = Self::archive_to_batch(&[], schema.clone())?;
+        let batches = RecordBatchIterator::new(vec![Ok(batch)], schema);
+
+        let table = self
+            .connection
+            .create_table(ARCHIVE_TABLE, Box::new(batches))
+            .execute()
+            .await
+            .map_err(|e| MnemoError::Storage(format!("Failed to create archive table: {e}")))?;
+
+        let schema = Self::archive_refs_schema();
+        let batch = Self::archive_refs_to_batch(&[], schema.clone())?;
+        let batches = RecordBatchIterator::new(vec![Ok(batch)], schema);
+
+        let refs_table = self
+            .connection
+            .create_table(ARCHIVE_REFS_TABLE, Box::new(batches))
+            .execute()
+            .await
+            .map_err(|e| {
+                MnemoError::Storage(format!("Failed to create archive refs table: {e}"))
+            })?;
+
+        self.archive_table = Some(table);
+        self.archive_refs_table = Some(refs_table);
+        Ok(())
+    }
+
     pub async fn create_vector_index(&self) -> Result<()> {
         let table = self
             .memories_table
@@ -219,7 +398,60 @@ impl LanceStore {
         Ok(())
     }
 
+    /// Create full-text (BM25) indices on `content` and `entities`
+    ///
+    /// Columns that already have an index are skipped. Rows added after
+    /// indexing are still found by full-text search, just more slowly.
+    pub async fn create_fts_index(&self) -> Result<()> {
+        let table = self
+            .memories_table
+            .as_ref()
+            .ok_or_else(|| MnemoError::Storage("Memories table not initialized".to_string()))?;
+
+        let indices = table
+            .list_indices()
+            .await
+            .map_err(|e| MnemoError::Storage(format!("Failed to list indices: {e}")))?;
+
+        for column in FTS_COLUMNS {
+            if indices.iter().any(|index| index.columns == [column]) {
+                continue;
+            }
+
+            table
+                .create_index(&[column], Index::FTS(FtsIndexBuilder::default()))
+                .execute()
+                .await
+                .map_err(|e| {
+                    MnemoError::Storage(format!("Failed to create full-text index: {e}"))
+                })?;
+        }
+
+        Ok(())
+    }
+
+    /// Open the memories table, migrating the store to [`SCHEMA_VERSION`]
+    ///
+    /// Migrations cover every table, so open the memories table before the
+    /// tombstones table.
+    ///
+    /// Fails without touching the store when it was written by a newer
+    /// version of mnemo, or when a pending migration re-embeds memories;
+    /// open those stores with
+    /// [`open_memories_table_with_model`](Self::open_memories_table_with_model).
     pub async fn open_memories_table(&mut self) -> Result<()> {
+        self.migrate(None).await?;
+        self.open_migrated_memories_table().await
+    }
+
+    /// Open the memories table, re-embedding with `model` when a pending
+    /// migration requires it
+    pub async fn open_memories_table_with_model(&mut self, model: &EmbeddingModel) -> Result<()> {
+        self.migrate(Some(model)).await?;
+        self.open_migrated_memories_table().await
+    }
+
+    async fn open_migrated_memories_table(&mut self) -> Result<()> {
         let table = self
             .connection
             .open_table(MEMORIES_TABLE)
@@ -231,7 +463,12 @@ impl LanceStore {
         Ok(())
     }
 
+    /// Open the tombstones table, migrating the store to [`SCHEMA_VERSION`]
+    ///
+    /// Usually a no-op after [`open_memories_table`](Self::open_memories_table)
+    /// has migrated the store.
     pub async fn open_tombstones_table(&mut self) -> Result<()> {
+        self.migrate(None).await?;
         let table = self
             .connection
             .open_table(TOMBSTONES_TABLE)
@@ -243,6 +480,26 @@ impl LanceStore {
         Ok(())
     }
 
+    pub async fn open_archive_table(&mut self) -> Result<()> {
+        let table = self
+            .connection
+            .open_table(ARCHIVE_TABLE)
+            .execute()
+            .await
+            .map_err(|e| MnemoError::Storage(format!("Failed to open archive table: {e}")))?;
+
+        let refs_table = self
+            .connection
+            .open_table(ARCHIVE_REFS_TABLE)
+            .execute()
+            .await
+            .map_err(|e| MnemoError::Storage(format!("Failed to open archive refs table: {e}")))?;
+
+        self.archive_table = Some(table);
+        self.archive_refs_table = Some(refs_table);
+        Ok(())
+    }
+
     pub async fn table_exists(&self, name: &str) -> Result<bool> {
         let names = self
             .connection
@@ -254,6 +511,376 @@ impl LanceStore {
         Ok(names.contains(&name.to_string()))
     }
 
+    fn schema_versions_schema() -> Arc<Schema> {
+        Arc::new(Schema::new(vec![
+            Field::new("version", DataType::Int32, false),
+            Field::new("description", DataType::Utf8, false),
+            Field::new(
+                "applied_at",
+                DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+                false,
+            ),
+        ]))
+    }
+
+    /// Schema version recorded for this store, if any
+    pub async fn schema_version(&self) -> Result<Option<u32>> {
+        if !self.table_exists(SCHEMA_VERSIONS_TABLE).await? {
+            return Ok(None);
+        }
+
+        let table = self
+            .connection
+            .open_table(SCHEMA_VERSIONS_TABLE)
+            .execute()
+            .await
+            .map_err(|e| MnemoError::Storage(format!("Failed to open schema versions: {e}")))?;
+
+        let batches: Vec<RecordBatch> = table
+            .query()
+            .execute()
+            .await
+            .map_err(|e| MnemoError::Storage(format!("Failed to query schema versions: {e}")))?
+            .try_collect()
+            .await
+            .map_err(|e| MnemoError::Storage(format!("Failed to collect schema versions: {e}")))?;
+
+        let mut version = None;
+        for batch in &batches {
+            let versions = batch
+                .column(0)
+                .as_any()
+                .downcast_ref::<Int32Array>()
+                .ok_or_else(|| MnemoError::Storage("Failed to get version column".to_string()))?;
+
+            for row in 0..batch.num_rows() {
+                version = version.max(Some(versions.value(row) as u32));
+            }
+        }
+
+        Ok(version)
+    }
+
+    /// Append a migration to the schema version record
+    async fn record_schema_version(&self, migration: Migration) -> Result<()> {
+        let schema = Self::schema_versions_schema();
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![migration.version as i32])),
+                Arc::new(StringArray::from(vec![migration.description])),
+                Arc::new(
+                    TimestampMicrosecondArray::from(vec![Utc::now().timestamp_micros()])
+                        .with_timezone("UTC"),
+                ),
+            ],
+        )
+        .map_err(|e| MnemoError::Storage(format!("Failed to create record batch: {e}")))?;
+        let batches = RecordBatchIterator::new(vec![Ok(batch)], schema);
+
+        if self.table_exists(SCHEMA_VERSIONS_TABLE).await? {
+            let table = self
+                .connection
+                .open_table(SCHEMA_VERSIONS_TABLE)
+                .execute()
+                .await
+                .map_err(|e| MnemoError::Storage(format!("Failed to open schema versions: {e}")))?;
+            table.add(Box::new(batches)).execute().await.map_err(|e| {
+                MnemoError::Storage(format!("Failed to record schema version: {e}"))
+            })?;
+        } else {
+            self.connection
+                .create_table(SCHEMA_VERSIONS_TABLE, Box::new(batches))
+                .execute()
+                .await
+                .map_err(|e| {
+                    MnemoError::Storage(format!("Failed to create schema versions table: {e}"))
+                })?;
+        }
+
+        Ok(())
+    }
+
+    /// Column names of a table, or `None` if it does not exist
+    async fn table_columns(&self, name: &str) -> Result<Option<Vec<String>>> {
+        if !self.table_exists(name).await? {
+            return Ok(None);
+        }
+
+        let table = self
+            .connection
+            .open_table(name)
+            .execute()
+            .await
+            .map_err(|e| MnemoError::Storage(format!("Failed to open table {name}: {e}")))?;
+        let schema = table
+            .schema()
+            .await
+            .map_err(|e| MnemoError::Storage(format!("Failed to read schema of {name}: {e}")))?;
+
+        Ok(Some(
+            schema.fields().iter().map(|f| f.name().clone()).collect(),
+        ))
+    }
+
+    /// Infer the schema version of an unversioned store from its layout
+    ///
+    /// A version matches when every column its migrations add is present and
+    /// no memory still awaits one of its rewrites.
+    async fn detect_schema_version(&self, migrations: &[Migration]) -> Result<u32> {
+        let mut version = migrations[0].version;
+
+        for migration in &migrations[1..] {
+            for step in migration.steps {
+                let applied = match *step {
+                    MigrationStep::AddColumn { table, column, .. } => {
+                        let columns = self.table_columns(table).await?.unwrap_or_default();
+                        columns.iter().any(|c| c == column)
+                    }
+                    MigrationStep::Rewrite { pending } => {
+                        self.count_memories_matching(pending).await? == 0
+                    }
+                    MigrationStep::Backfill { .. } | MigrationStep::Reembed => true,
+                };
+                if !applied {
+                    return Ok(version);
+                }
+            }
+            version = migration.version;
+        }
+
+        Ok(version)
+    }
+
+    /// Count memories matching a SQL filter, before the table is opened
+    async fn count_memories_matching(&self, filter: &str) -> Result<usize> {
+        let table = self
+            .connection
+            .open_table(MEMORIES_TABLE)
+            .execute()
+            .await
+            .map_err(|e| MnemoError::Storage(format!("Failed to open memories table: {e}")))?;
+
+        table
+            .count_rows(Some(filter.to_string()))
+            .await
+            .map_err(|e| MnemoError::Storage(format!("Failed to count memories: {e}")))
+    }
+
+    /// Bring the store up to [`SCHEMA_VERSION`]
+    ///
+    /// Pending migrations run in order; rewriting and re-embedding run once
+    /// after all column changes, and re-embedding needs `model`. Steps are
+    /// idempotent, so an interrupted migration is safely retried on the next
+    /// open. Empty data directories are left untouched.
+    pub async fn migrate(&self, model: Option<&EmbeddingModel>) -> Result<()> {
+        self.migrate_with(MIGRATIONS, model).await
+    }
+
+    /// Bring the store up to the last of `migrations`
+    async fn migrate_with(
+        &self,
+        migrations: &[Migration],
+        model: Option<&EmbeddingModel>,
+    ) -> Result<()> {
+        let _timer = OperationTimer::start("migrate");
+        if !self.table_exists(MEMORIES_TABLE).await? {
+            return Ok(());
+        }
+
+        let latest = migrations[migrations.len() - 1].version;
+        let recorded = self.schema_version().await?;
+        let current = match recorded {
+            Some(version) => version,
+            None => self.detect_schema_version(migrations).await?,
+        };
+
+        if current > latest {
+            return Err(MnemoError::Storage(format!(
+                "Store schema version {current} is newer than the supported version \
+                 {latest}; upgrade mnemo to open it"
+            )));
+        }
+
+        let pending: Vec<Migration> = migrations
+            .iter()
+            .filter(|m| m.version > current)
+            .copied()
+            .collect();
+
+        let reembed = pending.iter().any(|m| {
+            m.steps
+                .iter()
+                .any(|step| matches!(step, MigrationStep::Reembed))
+        });
+        if reembed && model.is_none() {
+            return Err(MnemoError::Storage(
+                "Store migration re-embeds memories and needs an embedding model".to_string(),
+            ));
+        }
+
+        let rewrite = reembed
+            || pending.iter().any(|m| {
+                m.steps
+                    .iter()
+                    .any(|step| matches!(step, MigrationStep::Rewrite { .. }))
+            });
+
+        for migration in &pending {
+            tracing::info!(
+                "Migrating store schema to version {}: {}",
+                migration.version,
+                migration.description
+            );
+            for step in migration.steps {
+                self.apply_migration_step(step).await?;
+            }
+        }
+
+        if rewrite {
+            self.rewrite_memories(model.filter(|_| reembed)).await?;
+        }
+
+        for migration in &pending {
+            self.record_schema_version(*migration).await?;
+        }
+
+        // Stores from before versioning get their detected version recorded
+        if recorded.is_none() && pending.is_empty() {
+            if let Some(migration) = migrations.iter().find(|m| m.version == current) {
+                self.record_schema_version(*migration).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Apply a column step; steps on missing tables or existing columns are skipped
+    async fn apply_migration_step(&self, step: &MigrationStep) -> Result<()> {
+        match *step {
+            MigrationStep::AddColumn {
+                table,
+                column,
+                expression,
+            } => {
+                let Some(columns) = self.table_columns(table).await? else {
+                    return Ok(());
+                };
+                if columns.iter().any(|c| c == column) {
+                    return Ok(());
+                }
+
+                let handle = self
+                    .connection
+                    .open_table(table)
+                    .execute()
+                    .await
+                    .map_err(|e| MnemoError::Storage(format!("Failed to open {table}: {e}")))?;
+                handle
+                    .add_columns(
+                        NewColumnTransform::SqlExpressions(vec![(
+                            column.to_string(),
+                            expression.to_string(),
+                        )]),
+                        None,
+                    )
+                    .await
+                    .map_err(|e| {
+                        MnemoError::Storage(format!("Failed to add column {table}.{column}: {e}"))
+                    })?;
+            }
+            MigrationStep::Backfill {
+                table,
+                column,
+                expression,
+            } => {
+                if !self.table_exists(table).await? {
+                    return Ok(());
+                }
+
+                let handle = self
+                    .connection
+                    .open_table(table)
+                    .execute()
+                    .await
+                    .map_err(|e| MnemoError::Storage(format!("Failed to open {table}: {e}")))?;
+                handle
+                    .update()
+                    .column(column, expression)
+                    .execute()
+                    .await
+                    .map_err(|e| {
+                        MnemoError::Storage(format!("Failed to backfill {table}.{column}: {e}"))
+                    })?;
+            }
+            MigrationStep::Reembed | MigrationStep::Rewrite { .. } => {}
+        }
+
+        Ok(())
+    }
+
+    /// Rewrite every memory, recomputing embeddings with `model` when given
+    ///
+    /// Memories are streamed from the table, so only one scan batch is held
+    /// in memory at a time.
+    async fn rewrite_memories(&self, model: Option<&EmbeddingModel>) -> Result<()> {
+        let table = self
+            .connection
+            .open_table(MEMORIES_TABLE)
+            .execute()
+            .await
+            .map_err(|e| MnemoError::Storage(format!("Failed to open memories table: {e}")))?;
+
+        // The scan reads the table version from when it started, so the
+        // updates below do not feed back into it
+        let mut stream = table
+            .query()
+            .execute()
+            .await
+            .map_err(|e| MnemoError::Storage(format!("Failed to list memories: {e}")))?;
+
+        let schema = Self::memories_schema();
+        let mut rewritten = 0;
+
+        while let Some(batch) = stream
+            .try_next()
+            .await
+            .map_err(|e| MnemoError::Storage(format!("Failed to read memories: {e}")))?
+        {
+            let mut memories = (0..batch.num_rows())
+                .map(|row| Self::batch_to_memory(&batch, row))
+                .collect::<Result<Vec<Memory>>>()?;
+
+            for chunk in memories.chunks_mut(REWRITE_BATCH_SIZE) {
+                if let Some(model) = model {
+                    let contents: Vec<String> = chunk.iter().map(|m| m.content.clone()).collect();
+                    let embeddings = model.embed_batch(&contents)?;
+                    for (memory, embedding) in chunk.iter_mut().zip(embeddings) {
+                        memory.embedding = embedding;
+                    }
+                }
+
+                let batch = Self::memories_to_batch(chunk, schema.clone())?;
+                let batches = RecordBatchIterator::new(vec![Ok(batch)], schema.clone());
+
+                let mut merge = table.merge_insert(&["id"]);
+                merge.when_matched_update_all(None);
+                merge
+                    .execute(Box::new(batches))
+                    .await
+                    .map_err(|e| MnemoError::Storage(format!("Failed to rewrite memories: {e}")))?;
+            }
+
+            rewritten += memories.len();
+        }
+
+        if rewritten > 0 {
+            tracing::info!("Rewrote {rewritten} memories");
+        }
+
+        Ok(())
+    }
+
     pub fn memories_table(&self) -> Option<&Table> {
         self.memories_table.as_ref()
     }
@@ -262,9 +889,13 @@ impl LanceStore {
         self.tombstones_table.as_ref()
     }
 
+    pub fn archive_table(&self) -> Option<&Table> {
+        self.archive_table.as_ref()
+    }
+
     /// Convert a Memory struct to an Arrow RecordBatch
     fn memory_to_batch(memory: &Memory, schema: Arc<Schema>) -> Result<RecordBatch> {
-        Self::memories_to_batch(&[memory.clone()], schema)
+        Self::memories_to_batch(std::slice::from_ref(memory), schema)
     }
 
     /// Convert multiple Memory structs to an Arrow RecordBatch
@@ -339,6 +970,23 @@ impl LanceStore {
         let entities: Vec<String> = memories.iter().map(|m| m.entities.join(",")).collect();
         let entity_refs: Vec<&str> = entities.iter().map(String::as_str).collect();
 
+        let tags: Vec<String> = memories.iter().map(|m| m.tags.join(",")).collect();
+        let tag_refs: Vec<&str> = tags.iter().map(String::as_str).collect();
+
+        let metadata: Vec<String> = memories
+            .iter()
+            .map(|m| serde_json::Value::Object(m.metadata.clone()).to_string())
+            .collect();
+        let metadata_refs: Vec<&str> = metadata.iter().map(String::as_str).collect();
+
+        let rehydrated: Vec<bool> = memories.iter().map(|m| m.rehydrated).collect();
+
+        let metadata_pairs: Vec<String> = memories
+            .iter()
+            .map(|m| filter::metadata_pairs(&m.metadata))
+            .collect();
+        let metadata_pair_refs: Vec<&str> = metadata_pairs.iter().map(String::as_str).collect();
+
         RecordBatch::try_new(
             schema,
             vec![
@@ -359,6 +1007,10 @@ impl LanceStore {
                 Arc::new(StringArray::from(tiers)),
                 Arc::new(StringArray::from(compressions)),
                 Arc::new(StringArray::from(entity_refs)),
+                Arc::new(StringArray::from(tag_refs)),
+                Arc::new(StringArray::from(metadata_refs)),
+                Arc::new(BooleanArray::from(rehydrated)),
+                Arc::new(StringArray::from(metadata_pair_refs)),
             ],
         )
         .map_err(|e| MnemoError::Storage(format!("Failed to create RecordBatch: {e}")))
@@ -446,6 +1098,24 @@ impl LanceStore {
             .downcast_ref::<StringArray>()
             .ok_or_else(|| MnemoError::Storage("Failed to get entities column".to_string()))?;
 
+        let tags_array = batch
+            .column(13)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .ok_or_else(|| MnemoError::Storage("Failed to get tags column".to_string()))?;
+
+        let metadata_array = batch
+            .column(14)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .ok_or_else(|| MnemoError::Storage("Failed to get metadata column".to_string()))?;
+
+        let rehydrated_array = batch
+            .column(15)
+            .as_any()
+            .downcast_ref::<BooleanArray>()
+            .ok_or_else(|| MnemoError::Storage("Failed to get rehydrated column".to_string()))?;
+
         // Parse ID
         let id = Uuid::parse_str(id_array.value(row))
             .map_err(|e| MnemoError::Storage(format!("Failed to parse UUID: {e}")))?;
@@ -550,6 +1220,27 @@ impl LanceStore {
             entities_str.split(',').map(|s| s.to_string()).collect()
         };
 
+        // Parse tags (comma-separated)
+        let tags = if tags_array.is_null(row) || tags_array.value(row).is_empty() {
+            Vec::new()
+        } else {
+            tags_array
+                .value(row)
+                .split(',')
+                .map(|s| s.to_string())
+                .collect()
+        };
+
+        // Parse metadata (JSON object)
+        let metadata = if metadata_array.is_null(row) {
+            serde_json::Map::new()
+        } else {
+            serde_json::from_str(metadata_array.value(row))
+                .map_err(|e| MnemoError::Storage(format!("Failed to parse metadata: {e}")))?
+        };
+
+        let rehydrated = !rehydrated_array.is_null(row) && rehydrated_array.value(row);
+
         Ok(Memory {
             id,
             content,
@@ -564,12 +1255,15 @@ impl LanceStore {
             source,
             tier,
             compression,
+            tags,
+            metadata,
+            rehydrated,
         })
     }
 
     /// Convert a Tombstone struct to an Arrow RecordBatch
     fn tombstone_to_batch(tombstone: &Tombstone, schema: Arc<Schema>) -> Result<RecordBatch> {
-        Self::tombstones_to_batch(&[tombstone.clone()], schema)
+        Self::tombstones_to_batch(std::slice::from_ref(tombstone), schema)
     }
 
     /// Convert multiple Tombstone structs to an Arrow RecordBatch
@@ -760,6 +1454,7 @@ impl LanceStore {
 
     /// Insert a single tombstone into the store
     pub async fn insert_tombstone(&self, tombstone: &Tombstone) -> Result<()> {
+        let _timer = OperationTimer::start("insert_tombstone");
         let table = self
             .tombstones_table
             .as_ref()
@@ -780,6 +1475,7 @@ impl LanceStore {
 
     /// Get a tombstone by original memory ID
     pub async fn get_tombstone(&self, original_id: Uuid) -> Result<Option<Tombstone>> {
+        let _timer = OperationTimer::start("get_tombstone");
         let table = self
             .tombstones_table
             .as_ref()
@@ -812,16 +1508,17 @@ impl LanceStore {
 
     /// Search tombstones by topic (case-insensitive substring match)
     pub async fn search_tombstones_by_topic(&self, topic: &str) -> Result<Vec<Tombstone>> {
+        let _timer = OperationTimer::start("search_tombstones_by_topic");
         let table = self
             .tombstones_table
             .as_ref()
             .ok_or_else(|| MnemoError::Storage("Tombstones table not initialized".to_string()))?;
 
-        // Use SQL LIKE for substring matching (case-insensitive)
-        let pattern = format!("%{}%", topic.to_lowercase());
+        // strpos matches the topic literally, so `%` and `_` are not wildcards
+        let escaped = topic.to_lowercase().replace('\'', "''");
         let stream = table
             .query()
-            .only_if(format!("lower(topics) LIKE '{pattern}'"))
+            .only_if(format!("strpos(lower(topics), '{escaped}') > 0"))
             .execute()
             .await
             .map_err(|e| MnemoError::Storage(format!("Failed to search tombstones: {e}")))?;
@@ -844,6 +1541,7 @@ impl LanceStore {
 
     /// List all tombstones
     pub async fn list_all_tombstones(&self) -> Result<Vec<Tombstone>> {
+        let _timer = OperationTimer::start("list_all_tombstones");
         let table = self
             .tombstones_table
             .as_ref()
@@ -871,16 +1569,269 @@ impl LanceStore {
         Ok(tombstones)
     }
 
-    /// Insert a single memory into the store
-    pub async fn insert(&self, memory: &Memory) -> Result<()> {
-        let table = self
-            .memories_table
-            .as_ref()
-            .ok_or_else(|| MnemoError::Storage("Memories table not initialized".to_string()))?;
+    /// Convert original contents to an Arrow RecordBatch of archive blobs
+    ///
+    /// Content is stored zstd-compressed under its SHA-256 hash.
+    fn archive_to_batch(contents: &[&str], schema: Arc<Schema>) -> Result<RecordBatch> {
+        let hashes: Vec<String> = contents
+            .iter()
+            .map(|content| content_hash(content))
+            .collect();
+        let hash_refs: Vec<&str> = hashes.iter().map(String::as_str).collect();
 
-        let schema = Self::memories_schema();
-        let batch = Self::memory_to_batch(memory, schema.clone())?;
-        let batches = RecordBatchIterator::new(vec![Ok(batch)], schema);
+        let compressed = contents
+            .iter()
+            .map(|content| zstd::encode_all(content.as_bytes(), ARCHIVE_ZSTD_LEVEL))
+            .collect::<std::io::Result<Vec<Vec<u8>>>>()
+            .map_err(|e| MnemoError::Storage(format!("Failed to compress content: {e}")))?;
+        let compressed_refs: Vec<&[u8]> = compressed.iter().map(Vec::as_slice).collect();
+
+        let now = Utc::now().timestamp_micros();
+        let archived_at: Vec<i64> = vec![now; contents.len()];
+
+        RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(StringArray::from(hash_refs)),
+                Arc::new(BinaryArray::from(compressed_refs)),
+                Arc::new(TimestampMicrosecondArray::from(archived_at).with_timezone("UTC")),
+            ],
+        )
+        .map_err(|e| MnemoError::Storage(format!("Failed to create record batch: {e}")))
+    }
+
+    /// Convert (memory ID, content hash) pairs to an Arrow RecordBatch
+    fn archive_refs_to_batch(entries: &[(Uuid, &str)], schema: Arc<Schema>) -> Result<RecordBatch> {
+        let memory_ids: Vec<String> = entries.iter().map(|(id, _)| id.to_string()).collect();
+        let id_refs: Vec<&str> = memory_ids.iter().map(String::as_str).collect();
+        let hash_refs: Vec<&str> = entries.iter().map(|(_, hash)| *hash).collect();
+
+        RecordBatch::try_new(
+            schema,
+            vec![
+                Arc::new(StringArray::from(id_refs)),
+                Arc::new(StringArray::from(hash_refs)),
+            ],
+        )
+        .map_err(|e| MnemoError::Storage(format!("Failed to create record batch: {e}")))
+    }
+
+    /// Archive the original content of a memory before it is compressed
+    ///
+    /// Blobs are keyed by SHA-256 hash, so memories with identical content
+    /// share one copy. Only the first archived version of a memory is kept,
+    /// so the full text survives every later compaction stage. Returns the
+    /// hash of the archived content.
+    pub async fn archive_content(&self, memory_id: Uuid, content: &str) -> Result<String> {
+        let _timer = OperationTimer::start("archive_content");
+        let table = self
+            .archive_table
+            .as_ref()
+            .ok_or_else(|| MnemoError::Storage("Archive table not initialized".to_string()))?;
+        let refs_table = self
+            .archive_refs_table
+            .as_ref()
+            .ok_or_else(|| MnemoError::Storage("Archive table not initialized".to_string()))?;
+
+        if let Some(hash) = self.archived_hash(memory_id).await? {
+            return Ok(hash);
+        }
+
+        let hash = content_hash(content);
+
+        if self.archived_blob(&hash).await?.is_none() {
+            let schema = Self::archive_schema();
+            let batch = Self::archive_to_batch(&[content], schema.clone())?;
+            let batches = RecordBatchIterator::new(vec![Ok(batch)], schema);
+
+            table
+                .add(Box::new(batches))
+                .execute()
+                .await
+                .map_err(|e| MnemoError::Storage(format!("Failed to archive content: {e}")))?;
+        }
+
+        let schema = Self::archive_refs_schema();
+        let batch = Self::archive_refs_to_batch(&[(memory_id, &hash)], schema.clone())?;
+        let batches = RecordBatchIterator::new(vec![Ok(batch)], schema);
+
+        refs_table
+            .add(Box::new(batches))
+            .execute()
+            .await
+            .map_err(|e| MnemoError::Storage(format!("Failed to archive content: {e}")))?;
+
+        Ok(hash)
+    }
+
+    /// Get the archived original content of a memory
+    ///
+    /// The content is verified against its hash before it is returned.
+    pub async fn get_archived(&self, memory_id: Uuid) -> Result<Option<String>> {
+        let _timer = OperationTimer::start("get_archived");
+        let Some(hash) = self.archived_hash(memory_id).await? else {
+            return Ok(None);
+        };
+        let compressed = self.archived_blob(&hash).await?.ok_or_else(|| {
+            MnemoError::Storage(format!("Archived content for {memory_id} is missing"))
+        })?;
+
+        let bytes = zstd::decode_all(compressed.as_slice())
+            .map_err(|e| MnemoError::Storage(format!("Failed to decompress content: {e}")))?;
+        let content = String::from_utf8(bytes)
+            .map_err(|e| MnemoError::Storage(format!("Archived content is not UTF-8: {e}")))?;
+
+        if content_hash(&content) != hash {
+            return Err(MnemoError::Storage(format!(
+                "Archived content for {memory_id} does not match its hash"
+            )));
+        }
+
+        Ok(Some(content))
+    }
+
+    /// Hash of the content archived for a memory
+    async fn archived_hash(&self, memory_id: Uuid) -> Result<Option<String>> {
+        let table = self
+            .archive_refs_table
+            .as_ref()
+            .ok_or_else(|| MnemoError::Storage("Archive table not initialized".to_string()))?;
+
+        let Some(batch) =
+            Self::first_archive_row(table, format!("memory_id = '{memory_id}'")).await?
+        else {
+            return Ok(None);
+        };
+
+        let hash_array = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .ok_or_else(|| MnemoError::Storage("Failed to get hash column".to_string()))?;
+
+        Ok(Some(hash_array.value(0).to_string()))
+    }
+
+    /// Compressed content stored under a hash
+    async fn archived_blob(&self, hash: &str) -> Result<Option<Vec<u8>>> {
+        let table = self
+            .archive_table
+            .as_ref()
+            .ok_or_else(|| MnemoError::Storage("Archive table not initialized".to_string()))?;
+
+        let Some(batch) = Self::first_archive_row(table, format!("hash = '{hash}'")).await? else {
+            return Ok(None);
+        };
+
+        let content_array = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<BinaryArray>()
+            .ok_or_else(|| MnemoError::Storage("Failed to get content column".to_string()))?;
+
+        Ok(Some(content_array.value(0).to_vec()))
+    }
+
+    /// First non-empty batch of an archive table query
+    async fn first_archive_row(table: &Table, filter: String) -> Result<Option<RecordBatch>> {
+        let stream = table
+            .query()
+            .only_if(filter)
+            .limit(1)
+            .execute()
+            .await
+            .map_err(|e| MnemoError::Storage(format!("Failed to query archive: {e}")))?;
+
+        let batches: Vec<RecordBatch> = stream
+            .try_collect()
+            .await
+            .map_err(|e| MnemoError::Storage(format!("Failed to collect query results: {e}")))?;
+
+        Ok(batches.into_iter().find(|b| b.num_rows() > 0))
+    }
+
+    /// Remove a memory's archive mapping, dropping the blob once unreferenced
+    async fn delete_archived(&self, memory_id: Uuid) -> Result<()> {
+        let (Some(table), Some(refs_table)) = (
+            self.archive_table.as_ref(),
+            self.archive_refs_table.as_ref(),
+        ) else {
+            return Ok(());
+        };
+
+        let Some(hash) = self.archived_hash(memory_id).await? else {
+            return Ok(());
+        };
+
+        refs_table
+            .delete(&format!("memory_id = '{memory_id}'"))
+            .await
+            .map_err(|e| MnemoError::Storage(format!("Failed to delete archive: {e}")))?;
+
+        let remaining = refs_table
+            .count_rows(Some(format!("hash = '{hash}'")))
+            .await
+            .map_err(|e| MnemoError::Storage(format!("Failed to count archive refs: {e}")))?;
+
+        if remaining == 0 {
+            table
+                .delete(&format!("hash = '{hash}'"))
+                .await
+                .map_err(|e| MnemoError::Storage(format!("Failed to delete archive: {e}")))?;
+        }
+
+        Ok(())
+    }
+
+    /// Restore a compressed memory to its full archived content
+    ///
+    /// The memory is marked as rehydrated so compaction does not compress it
+    /// again. Returns `Ok(false)` when the memory is already at `Full` or
+    /// nothing was archived for it.
+    pub async fn rehydrate(&self, id: Uuid) -> Result<bool> {
+        let _timer = OperationTimer::start("rehydrate");
+        let memory = self
+            .get(id)
+            .await?
+            .ok_or_else(|| MnemoError::Memory(format!("Memory not found: {id}")))?;
+
+        if memory.compression == CompressionLevel::Full {
+            return Ok(false);
+        }
+
+        let Some(content) = self.get_archived(id).await? else {
+            return Ok(false);
+        };
+
+        let table = self
+            .memories_table
+            .as_ref()
+            .ok_or_else(|| MnemoError::Storage("Memories table not initialized".to_string()))?;
+
+        table
+            .update()
+            .only_if(format!("id = '{id}'"))
+            .column("content", format!("'{}'", content.replace('\'', "''")))
+            .column("compression", "'Full'")
+            .column("rehydrated", "true")
+            .execute()
+            .await
+            .map_err(|e| MnemoError::Storage(format!("Failed to rehydrate memory: {e}")))?;
+
+        Ok(true)
+    }
+
+    /// Insert a single memory into the store
+    pub async fn insert(&self, memory: &Memory) -> Result<()> {
+        let _timer = OperationTimer::start("insert");
+        let table = self
+            .memories_table
+            .as_ref()
+            .ok_or_else(|| MnemoError::Storage("Memories table not initialized".to_string()))?;
+
+        let schema = Self::memories_schema();
+        let batch = Self::memory_to_batch(memory, schema.clone())?;
+        let batches = RecordBatchIterator::new(vec![Ok(batch)], schema);
 
         table
             .add(Box::new(batches))
@@ -893,6 +1844,7 @@ impl LanceStore {
 
     /// Insert multiple memories in batch
     pub async fn insert_batch(&self, memories: &[Memory]) -> Result<()> {
+        let _timer = OperationTimer::start("insert_batch");
         if memories.is_empty() {
             return Ok(());
         }
@@ -917,6 +1869,7 @@ impl LanceStore {
 
     /// Get a memory by ID
     pub async fn get(&self, id: Uuid) -> Result<Option<Memory>> {
+        let _timer = OperationTimer::start("get");
         let table = self
             .memories_table
             .as_ref()
@@ -950,6 +1903,7 @@ impl LanceStore {
     /// Delete a memory by ID
     /// Returns true if a memory was deleted, false if not found
     pub async fn delete(&self, id: Uuid) -> Result<bool> {
+        let _timer = OperationTimer::start("delete");
         let table = self
             .memories_table
             .as_ref()
@@ -963,6 +1917,8 @@ impl LanceStore {
                 .delete(&format!("id = '{id}'"))
                 .await
                 .map_err(|e| MnemoError::Storage(format!("Failed to delete memory: {e}")))?;
+
+            self.delete_archived(id).await?;
         }
 
         Ok(exists)
@@ -970,6 +1926,7 @@ impl LanceStore {
 
     /// Update access stats (increment count, update timestamp)
     pub async fn update_access(&self, id: Uuid) -> Result<()> {
+        let _timer = OperationTimer::start("update_access");
         let table = self
             .memories_table
             .as_ref()
@@ -989,8 +1946,79 @@ impl LanceStore {
         Ok(())
     }
 
+    /// Persist the result of merging a near-duplicate into an existing memory
+    ///
+    /// Writes the memory's weight, entities, tags, metadata, access count and
+    /// last access time.
+    pub async fn update_merged(&self, memory: &Memory) -> Result<()> {
+        let _timer = OperationTimer::start("update_merged");
+        let table = self
+            .memories_table
+            .as_ref()
+            .ok_or_else(|| MnemoError::Storage("Memories table not initialized".to_string()))?;
+
+        let escaped_entities = memory.entities.join(",").replace('\'', "''");
+        let escaped_tags = memory.tags.join(",").replace('\'', "''");
+        let escaped_metadata = serde_json::Value::Object(memory.metadata.clone())
+            .to_string()
+            .replace('\'', "''");
+        let escaped_pairs = filter::metadata_pairs(&memory.metadata).replace('\'', "''");
+
+        table
+            .update()
+            .only_if(format!("id = '{}'", memory.id))
+            .column("weight", format!("{}", memory.weight))
+            .column("entities", format!("'{escaped_entities}'"))
+            .column("tags", format!("'{escaped_tags}'"))
+            .column("metadata", format!("'{escaped_metadata}'"))
+            .column("metadata_pairs", format!("'{escaped_pairs}'"))
+            .column("access_count", format!("{}", memory.access_count))
+            .column(
+                "last_accessed",
+                format!("{}", memory.last_accessed.timestamp_micros()),
+            )
+            .execute()
+            .await
+            .map_err(|e| MnemoError::Storage(format!("Failed to update merged memory: {e}")))?;
+
+        Ok(())
+    }
+
+    /// Replace the tags and metadata of a memory
+    pub async fn update_labels(
+        &self,
+        id: Uuid,
+        tags: &[String],
+        metadata: &serde_json::Map<String, serde_json::Value>,
+    ) -> Result<()> {
+        let _timer = OperationTimer::start("update_labels");
+        let table = self
+            .memories_table
+            .as_ref()
+            .ok_or_else(|| MnemoError::Storage("Memories table not initialized".to_string()))?;
+
+        let escaped_tags = tags.join(",").replace('\'', "''");
+        let escaped_metadata = serde_json::Value::Object(metadata.clone())
+            .to_string()
+            .replace('\'', "''");
+        let escaped_pairs = filter::metadata_pairs(metadata).replace('\'', "''");
+
+        table
+            .update()
+            .only_if(format!("id = '{id}'"))
+            .column("tags", format!("'{escaped_tags}'"))
+            .column("metadata", format!("'{escaped_metadata}'"))
+            .column("metadata_pairs", format!("'{escaped_pairs}'"))
+            .execute()
+            .await
+            .map_err(|e| MnemoError::Storage(format!("Failed to update labels: {e}")))?;
+
+        Ok(())
+    }
+
     /// Update the storage tier of a memory
     pub async fn update_tier(&self, id: Uuid, tier: StorageTier) -> Result<()> {
+        let _timer = OperationTimer::start("update_tier");
         let table = self
             .memories_table
             .as_ref()
@@ -1019,6 +2047,7 @@ impl LanceStore {
         id: Uuid,
         conversation_id: Option<String>,
     ) -> Result<bool> {
+        let _timer = OperationTimer::start("update_conversation_id");
         let table = self
             .memories_table
             .as_ref()
@@ -1053,6 +2082,7 @@ impl LanceStore {
         filter: &MemoryFilter,
         limit: usize,
     ) -> Result<Vec<Memory>> {
+        let _timer = OperationTimer::start("search_filtered");
         let table = self
             .memories_table
             .as_ref()
@@ -1089,8 +2119,125 @@ impl LanceStore {
         Ok(memories)
     }
 
+    /// Full-text search over memory content and entities with filter criteria
+    ///
+    /// Returns memories with their BM25 scores, best match first.
+    pub async fn search_text(
+        &self,
+        query: &str,
+        filter: &MemoryFilter,
+        limit: usize,
+    ) -> Result<Vec<(Memory, f32)>> {
+        let _timer = OperationTimer::start("search_text");
+        let table = self
+            .memories_table
+            .as_ref()
+            .ok_or_else(|| MnemoError::Storage("Memories table not initialized".to_string()))?;
+
+        if query.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let columns: Vec<String> = FTS_COLUMNS.iter().map(|c| c.to_string()).collect();
+        let fts_query = FullTextSearchQuery::new(query.to_string())
+            .with_columns(&columns)
+            .map_err(|e| MnemoError::Storage(format!("Failed to create text query: {e}")))?;
+
+        let mut query = table.query().full_text_search(fts_query).limit(limit);
+
+        if let Some(sql_filter) = filter.to_sql_clause() {
+            query = query.only_if(sql_filter);
+        }
+
+        let stream = query
+            .execute()
+            .await
+            .map_err(|e| MnemoError::Storage(format!("Failed to execute text search: {e}")))?;
+
+        let batches: Vec<RecordBatch> = stream
+            .try_collect()
+            .await
+            .map_err(|e| MnemoError::Storage(format!("Failed to collect search results: {e}")))?;
+
+        let mut matches = Vec::new();
+        for batch in &batches {
+            let scores = batch
+                .column_by_name("_score")
+                .and_then(|column| column.as_any().downcast_ref::<Float32Array>())
+                .ok_or_else(|| MnemoError::Storage("Failed to get _score column".to_string()))?;
+
+            for row in 0..batch.num_rows() {
+                let memory = Self::batch_to_memory(batch, row)?;
+                matches.push((memory, scores.value(row)));
+            }
+        }
+
+        matches.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+        Ok(matches)
+    }
+
+    /// Hybrid search fusing vector similarity and BM25 full-text relevance
+    ///
+    /// Both searches return up to `limit` memories and are merged with
+    /// reciprocal rank fusion, the text ranking weighted by `text_weight`
+    /// (0.0-1.0) and the vector ranking by the remainder. Each memory is
+    /// returned with its BM25 score normalized against the best text match,
+    /// or 0.0 when only the vector search found it.
+    pub async fn search_hybrid(
+        &self,
+        embedding: &[f32],
+        query: &str,
+        filter: &MemoryFilter,
+        limit: usize,
+        text_weight: f32,
+    ) -> Result<Vec<(Memory, f32)>> {
+        let _timer = OperationTimer::start("search_hybrid");
+        let text_weight = text_weight.clamp(0.0, 1.0);
+
+        let vector_matches = self.search_filtered(embedding, filter, limit).await?;
+        let text_matches = self.search_text(query, filter, limit).await?;
+
+        let max_text_score = text_matches
+            .iter()
+            .map(|(_, score)| *score)
+            .fold(0.0f32, f32::max);
+
+        let vector_ranking: Vec<(Memory, f32)> = vector_matches
+            .into_iter()
+            .map(|memory| (memory, 0.0))
+            .collect();
+        let text_ranking: Vec<(Memory, f32)> = text_matches
+            .into_iter()
+            .map(|(memory, text_score)| {
+                let normalized = if max_text_score > 0.0 {
+                    text_score / max_text_score
+                } else {
+                    0.0
+                };
+                (memory, normalized)
+            })
+            .collect();
+
+        let mut results = fuse_rankings(
+            vec![
+                (vector_ranking, 1.0 - text_weight),
+                (text_ranking, text_weight),
+            ],
+            |(memory, _)| memory.id,
+            |(_, text_score), (_, other)| *text_score = text_score.max(other),
+        );
+        results.sort_by(|((a, _), a_score), ((b, _), b_score)| {
+            b_score.total_cmp(a_score).then_with(|| a.id.cmp(&b.id))
+        });
+        results.truncate(limit);
+
+        Ok(results.into_iter().map(|(matched, _)| matched).collect())
+    }
+
     /// List all memories in a specific storage tier
     pub async fn list_by_tier(&self, tier: StorageTier) -> Result<Vec<Memory>> {
+        let _timer = OperationTimer::start("list_by_tier");
         let table = self
             .memories_table
             .as_ref()
@@ -1127,6 +2274,7 @@ impl LanceStore {
 
     /// Count memories in a specific storage tier
     pub async fn count_by_tier(&self, tier: StorageTier) -> Result<usize> {
+        let _timer = OperationTimer::start("count_by_tier");
         let table = self
             .memories_table
             .as_ref()
@@ -1148,6 +2296,7 @@ impl LanceStore {
 
     /// Get the total number of memories across all tiers
     pub async fn total_count(&self) -> Result<usize> {
+        let _timer = OperationTimer::start("total_count");
         let table = self
             .memories_table
             .as_ref()
@@ -1168,6 +2317,7 @@ impl LanceStore {
         content: &str,
         compression: CompressionLevel,
     ) -> Result<()> {
+        let _timer = OperationTimer::start("update_compression");
         let table = self
             .memories_table
             .as_ref()
@@ -1195,6 +2345,7 @@ impl LanceStore {
     }
 
     pub async fn count_filtered(&self, filter: &MemoryFilter) -> Result<usize> {
+        let _timer = OperationTimer::start("count_filtered");
         let table = self
             .memories_table
             .as_ref()
@@ -1215,6 +2366,7 @@ impl LanceStore {
         limit: usize,
         offset: usize,
     ) -> Result<Vec<Memory>> {
+        let _timer = OperationTimer::start("list_filtered");
         let table = self
             .memories_table
             .as_ref()
@@ -1248,6 +2400,11 @@ impl LanceStore {
     }
 }
 
+/// Hex-encoded SHA-256 hash of content
+pub fn content_hash(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1284,7 +2441,7 @@ mod tests {
     async fn test_schema_has_correct_fields() {
         let schema = LanceStore::memories_schema();
 
-        assert_eq!(schema.fields().len(), 13);
+        assert_eq!(schema.fields().len(), 15);
 
         let field_names: Vec<&str> = schema.fields().iter().map(|f| f.name().as_str()).collect();
         assert!(field_names.contains(&"id"));
@@ -1300,6 +2457,8 @@ mod tests {
         assert!(field_names.contains(&"tier"));
         assert!(field_names.contains(&"compression"));
         assert!(field_names.contains(&"entities"));
+        assert!(field_names.contains(&"tags"));
+        assert!(field_names.contains(&"metadata"));
     }
 
     #[tokio::test]
@@ -1433,6 +2592,31 @@ mod tests {
             assert!(updated.last_accessed > original_last_accessed);
         }
 
+        #[tokio::test]
+        async fn test_update_merged() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+            store.create_memories_table().await.unwrap();
+
+            let mut memory = create_test_memory("Merge test");
+            memory.weight = 0.5;
+            store.insert(&memory).await.unwrap();
+
+            memory.weight = 0.8;
+            memory.entities = vec!["O'Brien".to_string(), "Rust".to_string()];
+            memory.mark_accessed();
+            store.update_merged(&memory).await.unwrap();
+
+            let updated = store.get(memory.id).await.unwrap().unwrap();
+            assert!((updated.weight - 0.8).abs() < 1e-6);
+            assert_eq!(updated.entities, memory.entities);
+            assert_eq!(updated.access_count, 1);
+            assert_eq!(
+                updated.last_accessed.timestamp_micros(),
+                memory.last_accessed.timestamp_micros()
+            );
+        }
+
         #[tokio::test]
         async fn test_roundtrip_preserves_all_fields() {
             let temp_dir = tempfile::tempdir().unwrap();
@@ -1449,6 +2633,13 @@ mod tests {
             memory.conversation_id = Some("conv-123".to_string());
             memory.tier = StorageTier::Warm;
             memory.compression = CompressionLevel::Summary;
+            memory.tags = vec!["mnemo".to_string(), "rust".to_string()];
+            memory
+                .metadata
+                .insert("repo".to_string(), serde_json::json!("watzon/mnemo"));
+            memory
+                .metadata
+                .insert("stars".to_string(), serde_json::json!(42));
 
             let id = memory.id;
 
@@ -1464,6 +2655,30 @@ mod tests {
             assert_eq!(retrieved.source, memory.source);
             assert_eq!(retrieved.tier, memory.tier);
             assert_eq!(retrieved.compression, memory.compression);
+            assert_eq!(retrieved.tags, memory.tags);
+            assert_eq!(retrieved.metadata, memory.metadata);
+        }
+
+        #[tokio::test]
+        async fn test_update_labels() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+            store.create_memories_table().await.unwrap();
+
+            let memory = create_test_memory("Label test");
+            store.insert(&memory).await.unwrap();
+
+            let tags = vec!["client:zed".to_string(), "it's".to_string()];
+            let mut metadata = serde_json::Map::new();
+            metadata.insert("author".to_string(), serde_json::json!("O'Brien"));
+            store
+                .update_labels(memory.id, &tags, &metadata)
+                .await
+                .unwrap();
+
+            let updated = store.get(memory.id).await.unwrap().unwrap();
+            assert_eq!(updated.tags, tags);
+            assert_eq!(updated.metadata, metadata);
         }
     }
 
@@ -1517,27 +2732,119 @@ mod tests {
         }
 
         #[tokio::test]
-        async fn test_search_respects_limit() {
+        async fn test_search_text_finds_exact_identifiers() {
             let temp_dir = tempfile::tempdir().unwrap();
             let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
             store.create_memories_table().await.unwrap();
 
-            let base_embedding: Vec<f32> = vec![0.5; 384];
-            let memories: Vec<Memory> = (0..5)
-                .map(|i| {
-                    create_memory_with_embedding(
-                        &format!("Memory {i}"),
-                        similar_embedding(&base_embedding, i as f32 * 0.01),
-                        MemoryType::Semantic,
-                    )
-                })
-                .collect();
-            store.insert_batch(&memories).await.unwrap();
-
-            let results = store.search(&base_embedding, 2).await.unwrap();
-
-            assert_eq!(results.len(), 2);
-        }
+            let memories = vec![
+                create_memory_with_embedding(
+                    "Deploys fail with error E4031 when the cache is cold",
+                    vec![0.5; 384],
+                    MemoryType::Semantic,
+                ),
+                create_memory_with_embedding(
+                    "The user prefers short answers",
+                    vec![0.5; 384],
+                    MemoryType::Semantic,
+                ),
+            ];
+            store.insert_batch(&memories).await.unwrap();
+            store.create_fts_index().await.unwrap();
+
+            let results = store
+                .search_text("E4031", &MemoryFilter::default(), 10)
+                .await
+                .unwrap();
+
+            assert_eq!(results.len(), 1);
+            assert!(results[0].0.content.contains("E4031"));
+            assert!(results[0].1 > 0.0);
+        }
+
+        #[tokio::test]
+        async fn test_search_text_without_index() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+            store.create_memories_table().await.unwrap();
+
+            let mut memory =
+                create_memory_with_embedding("Ticket filed", vec![0.5; 384], MemoryType::Episodic);
+            memory.entities = vec!["PROJ-1234".to_string()];
+            store.insert(&memory).await.unwrap();
+
+            let results = store
+                .search_text("PROJ-1234", &MemoryFilter::default(), 10)
+                .await
+                .unwrap();
+
+            assert_eq!(results.len(), 1);
+            assert_eq!(results[0].0.id, memory.id);
+        }
+
+        #[tokio::test]
+        async fn test_search_hybrid_fuses_text_and_vector_matches() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+            store.create_memories_table().await.unwrap();
+
+            let base_embedding: Vec<f32> = vec![0.5; 384];
+            let semantic = create_memory_with_embedding(
+                "Close in embedding space",
+                similar_embedding(&base_embedding, 0.01),
+                MemoryType::Semantic,
+            );
+            let keyword = create_memory_with_embedding(
+                "Function parse_manifest panics on empty input",
+                vec![-0.5; 384],
+                MemoryType::Semantic,
+            );
+            store
+                .insert_batch(&[semantic.clone(), keyword.clone()])
+                .await
+                .unwrap();
+            store.create_fts_index().await.unwrap();
+
+            let results = store
+                .search_hybrid(
+                    &base_embedding,
+                    "parse_manifest",
+                    &MemoryFilter::default(),
+                    10,
+                    0.5,
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(results.len(), 2);
+            let keyword_match = results.iter().find(|(m, _)| m.id == keyword.id).unwrap();
+            assert!((keyword_match.1 - 1.0).abs() < f32::EPSILON);
+            let semantic_match = results.iter().find(|(m, _)| m.id == semantic.id).unwrap();
+            assert_eq!(semantic_match.1, 0.0);
+        }
+
+        #[tokio::test]
+        async fn test_search_respects_limit() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+            store.create_memories_table().await.unwrap();
+
+            let base_embedding: Vec<f32> = vec![0.5; 384];
+            let memories: Vec<Memory> = (0..5)
+                .map(|i| {
+                    create_memory_with_embedding(
+                        &format!("Memory {i}"),
+                        similar_embedding(&base_embedding, i as f32 * 0.01),
+                        MemoryType::Semantic,
+                    )
+                })
+                .collect();
+            store.insert_batch(&memories).await.unwrap();
+
+            let results = store.search(&base_embedding, 2).await.unwrap();
+
+            assert_eq!(results.len(), 2);
+        }
 
         #[tokio::test]
         async fn test_search_filtered_by_memory_type() {
@@ -1647,6 +2954,127 @@ mod tests {
             assert_eq!(results[0].conversation_id, Some("conv-a".to_string()));
         }
 
+        #[tokio::test]
+        async fn test_search_filtered_by_tags_and_metadata() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+            store.create_memories_table().await.unwrap();
+
+            let base_embedding: Vec<f32> = vec![0.5; 384];
+
+            let mut tagged = create_memory_with_embedding(
+                "Tagged",
+                base_embedding.clone(),
+                MemoryType::Semantic,
+            );
+            tagged.tags = vec!["rust".to_string(), "mnemo".to_string()];
+            tagged
+                .metadata
+                .insert("stars".to_string(), serde_json::json!(4));
+
+            let mut prefixed = create_memory_with_embedding(
+                "Prefixed",
+                base_embedding.clone(),
+                MemoryType::Semantic,
+            );
+            prefixed.tags = vec!["rustacean".to_string()];
+            prefixed
+                .metadata
+                .insert("stars".to_string(), serde_json::json!(42));
+
+            // Same pair, but nested under another key
+            let mut nested = create_memory_with_embedding(
+                "Nested",
+                base_embedding.clone(),
+                MemoryType::Semantic,
+            );
+            nested.metadata.insert(
+                "repo".to_string(),
+                serde_json::json!({"name": "mnemo", "stars": 4, "tags": []}),
+            );
+
+            store
+                .insert_batch(&[tagged.clone(), prefixed, nested])
+                .await
+                .unwrap();
+
+            let filter = MemoryFilter::new().with_tags(vec!["rust".to_string()]);
+            let results = store
+                .search_filtered(&base_embedding, &filter, 10)
+                .await
+                .unwrap();
+            assert_eq!(results.len(), 1);
+            assert_eq!(results[0].id, tagged.id);
+
+            let filter = MemoryFilter::new().with_metadata("stars", serde_json::json!(4));
+            let results = store
+                .search_filtered(&base_embedding, &filter, 10)
+                .await
+                .unwrap();
+            assert_eq!(results.len(), 1);
+            assert_eq!(results[0].id, tagged.id);
+        }
+
+        #[tokio::test]
+        async fn test_search_filtered_by_expression() {
+            use crate::storage::filter::FilterExpr;
+
+            let temp_dir = tempfile::tempdir().unwrap();
+            let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+            store.create_memories_table().await.unwrap();
+
+            let base_embedding: Vec<f32> = vec![0.5; 384];
+
+            let mut manual = create_memory_with_embedding(
+                "Manual",
+                base_embedding.clone(),
+                MemoryType::Semantic,
+            );
+            manual.entities = vec!["O'Brien".to_string()];
+
+            let mut web =
+                create_memory_with_embedding("Web", base_embedding.clone(), MemoryType::Semantic);
+            web.source = MemorySource::Web;
+            web.access_count = 5;
+
+            let mut old =
+                create_memory_with_embedding("Old", base_embedding.clone(), MemoryType::Episodic);
+            old.source = MemorySource::Web;
+            old.created_at = Utc::now() - chrono::Duration::days(30);
+
+            store
+                .insert_batch(&[manual.clone(), web.clone(), old.clone()])
+                .await
+                .unwrap();
+
+            let filter = MemoryFilter::new().with_expr(
+                FilterExpr::Entity("O'Brien".to_string())
+                    .or(FilterExpr::MinAccessCount(5))
+                    .and(!FilterExpr::MemoryType(vec![MemoryType::Episodic])),
+            );
+            let mut ids: Vec<Uuid> = store
+                .search_filtered(&base_embedding, &filter, 10)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|m| m.id)
+                .collect();
+            ids.sort();
+            let mut expected = vec![manual.id, web.id];
+            expected.sort();
+            assert_eq!(ids, expected);
+
+            let filter = MemoryFilter::new()
+                .with_sources(vec![MemorySource::Web])
+                .until(Utc::now() - chrono::Duration::days(1));
+            let results = store
+                .search_filtered(&base_embedding, &filter, 10)
+                .await
+                .unwrap();
+            assert_eq!(results.len(), 1);
+            assert_eq!(results[0].id, old.id);
+        }
+
         #[tokio::test]
         async fn test_search_with_combined_filters() {
             let temp_dir = tempfile::tempdir().unwrap();
@@ -1831,6 +3259,15 @@ mod tests {
                 .await
                 .unwrap();
             assert!(results.is_empty());
+
+            // Wildcard characters are matched literally
+            for pattern in ["%", "pyth_n", "_"] {
+                let results = store.search_tombstones_by_topic(pattern).await.unwrap();
+                assert!(results.is_empty(), "{pattern} should not match");
+            }
+
+            let results = store.search_tombstones_by_topic("PYTHON").await.unwrap();
+            assert_eq!(results.len(), 2);
         }
 
         #[tokio::test]
@@ -1893,4 +3330,498 @@ mod tests {
             }
         }
     }
+
+    mod archive {
+        use super::*;
+
+        fn create_test_memory(content: &str) -> Memory {
+            Memory::new(
+                content.to_string(),
+                vec![0.1; 384],
+                MemoryType::Semantic,
+                MemorySource::Manual,
+            )
+        }
+
+        async fn create_store(path: &Path) -> LanceStore {
+            let mut store = LanceStore::connect(path).await.unwrap();
+            store.create_memories_table().await.unwrap();
+            store.create_archive_table().await.unwrap();
+            store
+        }
+
+        #[tokio::test]
+        async fn test_create_and_open_archive_table() {
+            let temp_dir = tempfile::tempdir().unwrap();
+
+            {
+                let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+                assert!(store.archive_table().is_none());
+                store.create_archive_table().await.unwrap();
+                assert!(store.table_exists(ARCHIVE_TABLE).await.unwrap());
+            }
+
+            let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+            store.open_archive_table().await.unwrap();
+            assert!(store.archive_table().is_some());
+            assert!(store.table_exists(ARCHIVE_REFS_TABLE).await.unwrap());
+        }
+
+        #[tokio::test]
+        async fn test_archive_and_get_roundtrip() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let store = create_store(temp_dir.path()).await;
+
+            let id = Uuid::new_v4();
+            let content = "The user's deploy key lives in the team's vault.";
+            let hash = store.archive_content(id, content).await.unwrap();
+
+            assert_eq!(hash, content_hash(content));
+            assert_eq!(
+                store.get_archived(id).await.unwrap().as_deref(),
+                Some(content)
+            );
+            assert!(store.get_archived(Uuid::new_v4()).await.unwrap().is_none());
+        }
+
+        #[tokio::test]
+        async fn test_archive_keeps_first_version() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let store = create_store(temp_dir.path()).await;
+
+            let id = Uuid::new_v4();
+            store
+                .archive_content(id, "Original full text.")
+                .await
+                .unwrap();
+            let hash = store.archive_content(id, "Summary.").await.unwrap();
+
+            assert_eq!(hash, content_hash("Original full text."));
+            assert_eq!(
+                store.get_archived(id).await.unwrap().as_deref(),
+                Some("Original full text.")
+            );
+        }
+
+        #[tokio::test]
+        async fn test_rehydrate_restores_full_content() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let store = create_store(temp_dir.path()).await;
+
+            let memory = create_test_memory("First sentence. Second sentence. Third sentence.");
+            store.insert(&memory).await.unwrap();
+            store
+                .archive_content(memory.id, &memory.content)
+                .await
+                .unwrap();
+            store
+                .update_compression(memory.id, "first sentence", CompressionLevel::Keywords)
+                .await
+                .unwrap();
+
+            assert!(store.rehydrate(memory.id).await.unwrap());
+
+            let restored = store.get(memory.id).await.unwrap().unwrap();
+            assert_eq!(restored.content, memory.content);
+            assert_eq!(restored.compression, CompressionLevel::Full);
+            assert!(restored.rehydrated);
+
+            // Already full
+            assert!(!store.rehydrate(memory.id).await.unwrap());
+        }
+
+        #[tokio::test]
+        async fn test_rehydrate_without_archive() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let store = create_store(temp_dir.path()).await;
+
+            let memory = create_test_memory("Some content");
+            store.insert(&memory).await.unwrap();
+            store
+                .update_compression(memory.id, "content", CompressionLevel::Keywords)
+                .await
+                .unwrap();
+
+            assert!(!store.rehydrate(memory.id).await.unwrap());
+            assert!(store.rehydrate(Uuid::new_v4()).await.is_err());
+        }
+
+        #[tokio::test]
+        async fn test_delete_removes_archive_entry() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let store = create_store(temp_dir.path()).await;
+
+            let memory = create_test_memory("Some content");
+            store.insert(&memory).await.unwrap();
+            store
+                .archive_content(memory.id, &memory.content)
+                .await
+                .unwrap();
+
+            store.delete(memory.id).await.unwrap();
+
+            assert!(store.get_archived(memory.id).await.unwrap().is_none());
+            assert!(
+                store
+                    .archived_blob(&content_hash(&memory.content))
+                    .await
+                    .unwrap()
+                    .is_none()
+            );
+        }
+
+        #[tokio::test]
+        async fn test_identical_content_shares_one_blob() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let store = create_store(temp_dir.path()).await;
+
+            let first = create_test_memory("Shared content");
+            let second = create_test_memory("Shared content");
+            store.insert(&first).await.unwrap();
+            store.insert(&second).await.unwrap();
+
+            let first_hash = store
+                .archive_content(first.id, &first.content)
+                .await
+                .unwrap();
+            let second_hash = store
+                .archive_content(second.id, &second.content)
+                .await
+                .unwrap();
+
+            assert_eq!(first_hash, second_hash);
+            assert_eq!(
+                store
+                    .archive_table()
+                    .unwrap()
+                    .count_rows(None)
+                    .await
+                    .unwrap(),
+                1
+            );
+
+            // The blob outlives the first memory while the second still refers to it
+            store.delete(first.id).await.unwrap();
+
+            assert!(store.get_archived(first.id).await.unwrap().is_none());
+            assert_eq!(
+                store.get_archived(second.id).await.unwrap().as_deref(),
+                Some("Shared content")
+            );
+        }
+    }
+
+    mod migrations {
+        use super::*;
+
+        #[tokio::test]
+        async fn test_new_store_records_current_version() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+
+            assert_eq!(store.schema_version().await.unwrap(), None);
+
+            store.create_memories_table().await.unwrap();
+
+            assert_eq!(store.schema_version().await.unwrap(), Some(SCHEMA_VERSION));
+        }
+
+        #[tokio::test]
+        async fn test_unversioned_store_is_detected_and_recorded() {
+            let temp_dir = tempfile::tempdir().unwrap();
+
+            {
+                let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+                store.create_memories_table().await.unwrap();
+                store
+                    .connection
+                    .drop_table(SCHEMA_VERSIONS_TABLE, &[])
+                    .await
+                    .unwrap();
+                assert_eq!(store.schema_version().await.unwrap(), None);
+            }
+
+            let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+            store.open_memories_table().await.unwrap();
+
+            assert_eq!(store.schema_version().await.unwrap(), Some(SCHEMA_VERSION));
+        }
+
+        #[tokio::test]
+        async fn test_refuses_newer_schema_version() {
+            let temp_dir = tempfile::tempdir().unwrap();
+
+            {
+                let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+                store.create_memories_table().await.unwrap();
+                store
+                    .record_schema_version(Migration {
+                        version: SCHEMA_VERSION + 1,
+                        description: "From the future",
+                        steps: &[],
+                    })
+                    .await
+                    .unwrap();
+            }
+
+            let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+            let err = store.open_memories_table().await.unwrap_err();
+
+            assert!(err.to_string().contains("newer"));
+            assert!(store.memories_table().is_none());
+        }
+
+        #[tokio::test]
+        async fn test_reembed_recomputes_embeddings_from_content() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+            store.create_memories_table().await.unwrap();
+
+            let memories: Vec<Memory> = (0..3)
+                .map(|i| {
+                    Memory::new(
+                        format!("Memory number {i}"),
+                        vec![0.1; 384],
+                        MemoryType::Semantic,
+                        MemorySource::Manual,
+                    )
+                })
+                .collect();
+            store.insert_batch(&memories).await.unwrap();
+
+            let mut migrations = MIGRATIONS.to_vec();
+            migrations.push(Migration {
+                version: SCHEMA_VERSION + 1,
+                description: "Re-embed memories",
+                steps: &[MigrationStep::Reembed],
+            });
+
+            let err = store.migrate_with(&migrations, None).await.unwrap_err();
+            assert!(err.to_string().contains("embedding model"));
+            assert_eq!(store.schema_version().await.unwrap(), Some(SCHEMA_VERSION));
+
+            let model = EmbeddingModel::new().unwrap();
+            store.migrate_with(&migrations, Some(&model)).await.unwrap();
+
+            assert_eq!(
+                store.schema_version().await.unwrap(),
+                Some(SCHEMA_VERSION + 1)
+            );
+            for memory in &memories {
+                let stored = store.get(memory.id).await.unwrap().unwrap();
+                assert_eq!(stored.embedding, model.embed(&memory.content).unwrap());
+            }
+        }
+
+        #[tokio::test]
+        async fn test_interrupted_rewrite_is_detected_and_resumed() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let mut memory = Memory::new(
+                "Stored before metadata pairs existed".to_string(),
+                vec![0.1; 384],
+                MemoryType::Semantic,
+                MemorySource::Manual,
+            );
+            memory
+                .metadata
+                .insert("stars".to_string(), serde_json::json!(4));
+
+            {
+                let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+                store.create_memories_table().await.unwrap();
+                store.insert(&memory).await.unwrap();
+                // The metadata_pairs column was added but the rewrite never ran
+                store
+                    .memories_table()
+                    .unwrap()
+                    .update()
+                    .column("metadata_pairs", "'\n'")
+                    .execute()
+                    .await
+                    .unwrap();
+                store
+                    .connection
+                    .drop_table(SCHEMA_VERSIONS_TABLE, &[])
+                    .await
+                    .unwrap();
+                assert_eq!(
+                    store.detect_schema_version(MIGRATIONS).await.unwrap(),
+                    SCHEMA_VERSION - 1
+                );
+            }
+
+            let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+            store.open_memories_table().await.unwrap();
+
+            assert_eq!(store.schema_version().await.unwrap(), Some(SCHEMA_VERSION));
+            let filter = MemoryFilter::new().with_metadata("stars", serde_json::json!(4));
+            let results = store
+                .search_filtered(&[0.1; 384], &filter, 10)
+                .await
+                .unwrap();
+            assert_eq!(results.len(), 1);
+            assert_eq!(results[0].id, memory.id);
+        }
+
+        #[tokio::test]
+        async fn test_migrations_add_tombstone_columns() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+            store.create_memories_table().await.unwrap();
+            store.create_tombstones_table().await.unwrap();
+
+            let mut migrations = MIGRATIONS.to_vec();
+            migrations.push(Migration {
+                version: SCHEMA_VERSION + 1,
+                description: "Add tombstone notes",
+                steps: &[MigrationStep::AddColumn {
+                    table: TOMBSTONES_TABLE,
+                    column: "notes",
+                    expression: "''",
+                }],
+            });
+            store.migrate_with(&migrations, None).await.unwrap();
+
+            let columns = store
+                .table_columns(TOMBSTONES_TABLE)
+                .await
+                .unwrap()
+                .unwrap();
+            assert!(columns.iter().any(|c| c == "notes"));
+            assert_eq!(
+                store.schema_version().await.unwrap(),
+                Some(SCHEMA_VERSION + 1)
+            );
+        }
+
+        #[tokio::test]
+        async fn test_add_column_and_backfill_steps() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+            store.create_memories_table().await.unwrap();
+            store
+                .insert(&Memory::new(
+                    "Some content".to_string(),
+                    vec![0.1; 384],
+                    MemoryType::Semantic,
+                    MemorySource::Manual,
+                ))
+                .await
+                .unwrap();
+
+            let add = MigrationStep::AddColumn {
+                table: MEMORIES_TABLE,
+                column: "valence",
+                expression: "CAST(0.0 AS FLOAT)",
+            };
+            store.apply_migration_step(&add).await.unwrap();
+            // Idempotent
+            store.apply_migration_step(&add).await.unwrap();
+
+            let columns = store.table_columns(MEMORIES_TABLE).await.unwrap().unwrap();
+            assert_eq!(columns.iter().filter(|c| *c == "valence").count(), 1);
+
+            store
+                .apply_migration_step(&MigrationStep::Backfill {
+                    table: MEMORIES_TABLE,
+                    column: "valence",
+                    expression: "CAST(0.5 AS FLOAT)",
+                })
+                .await
+                .unwrap();
+
+            let table = store
+                .connection
+                .open_table(MEMORIES_TABLE)
+                .execute()
+                .await
+                .unwrap();
+            let count = table
+                .count_rows(Some("valence > 0.4".to_string()))
+                .await
+                .unwrap();
+            assert_eq!(count, 1);
+
+            // Steps on missing tables are skipped
+            store
+                .apply_migration_step(&MigrationStep::AddColumn {
+                    table: "missing",
+                    column: "valence",
+                    expression: "0",
+                })
+                .await
+                .unwrap();
+        }
+
+        #[tokio::test]
+        async fn test_version_one_store_gains_tags_and_metadata() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let memory = Memory::new(
+                "Stored before tags existed".to_string(),
+                vec![0.1; 384],
+                MemoryType::Semantic,
+                MemorySource::Manual,
+            );
+
+            {
+                let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+                store.create_memories_table().await.unwrap();
+                store.insert(&memory).await.unwrap();
+                store
+                    .memories_table()
+                    .unwrap()
+                    .drop_columns(&["tags", "metadata", "rehydrated", "metadata_pairs"])
+                    .await
+                    .unwrap();
+                store
+                    .connection
+                    .drop_table(SCHEMA_VERSIONS_TABLE, &[])
+                    .await
+                    .unwrap();
+                assert_eq!(store.detect_schema_version(MIGRATIONS).await.unwrap(), 1);
+            }
+
+            let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+            store.open_memories_table().await.unwrap();
+
+            assert_eq!(store.schema_version().await.unwrap(), Some(SCHEMA_VERSION));
+            let loaded = store.get(memory.id).await.unwrap().unwrap();
+            assert!(loaded.tags.is_empty());
+            assert!(loaded.metadata.is_empty());
+
+            // Migrated columns line up with the layout new rows are written in
+            let mut added = Memory::new(
+                "Stored after the migration".to_string(),
+                vec![0.1; 384],
+                MemoryType::Semantic,
+                MemorySource::Manual,
+            );
+            added.tags = vec!["rust".to_string()];
+            added
+                .metadata
+                .insert("stars".to_string(), serde_json::json!(4));
+            store.insert(&added).await.unwrap();
+
+            let loaded = store.get(added.id).await.unwrap().unwrap();
+            assert_eq!(loaded.tags, added.tags);
+            assert_eq!(loaded.metadata, added.metadata);
+            assert!(!loaded.rehydrated);
+
+            let filter = MemoryFilter::new().with_metadata("stars", serde_json::json!(4));
+            let results = store
+                .search_filtered(&[0.1; 384], &filter, 10)
+                .await
+                .unwrap();
+            assert_eq!(results.len(), 1);
+            assert_eq!(results[0].id, added.id);
+        }
+
+        #[test]
+        fn test_migrations_are_ordered() {
+            assert_eq!(MIGRATIONS[MIGRATIONS.len() - 1].version, SCHEMA_VERSION);
+            for pair in MIGRATIONS.windows(2) {
+                assert_eq!(pair[1].version, pair[0].version + 1);
+            }
+        }
+    }
 }
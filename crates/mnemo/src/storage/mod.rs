@@ -1,11 +1,13 @@
 pub mod compaction;
 pub mod eviction;
 pub mod filter;
+pub mod fusion;
 pub mod lance;
 pub mod tiers;
 
 pub use compaction::{CompactionConfig, CompactionResult, Compactor};
 pub use eviction::{CapacityStatus, EvictionConfig, Evictor};
-pub use filter::MemoryFilter;
+pub use filter::{FilterExpr, MemoryFilter};
+pub use fusion::fuse_rankings;
 pub use lance::LanceStore;
 pub use tiers::{TierConfig, TierManager};
@@ -3,6 +3,7 @@
 //! Implements automatic migration of memories between storage tiers (Hot, Warm, Cold)
 //! based on access patterns and storage thresholds.
 
+use chrono::{DateTime, Duration, Utc};
 use uuid::Uuid;
 
 use crate::error::{MnemoError, Result};
@@ -205,6 +206,61 @@ impl<'a> TierManager<'a> {
             Ok(false)
         }
     }
+
+    /// Demote every memory in a tier that has not been accessed recently
+    ///
+    /// Memories whose `last_accessed` is older than `max_idle` are moved one
+    /// tier cooler (Hot → Warm, Warm → Cold). Cold memories are left as is.
+    ///
+    /// # Returns
+    /// * The UUIDs of the demoted memories
+    pub async fn demote_idle(&self, tier: StorageTier, max_idle: Duration) -> Result<Vec<Uuid>> {
+        let new_tier = match tier {
+            StorageTier::Hot => StorageTier::Warm,
+            StorageTier::Warm => StorageTier::Cold,
+            StorageTier::Cold => return Ok(Vec::new()),
+        };
+
+        let cutoff = Utc::now() - max_idle;
+        let mut demoted = Vec::new();
+
+        for memory in self.store.list_by_tier(tier).await? {
+            if memory.last_accessed < cutoff {
+                self.store.update_tier(memory.id, new_tier).await?;
+                demoted.push(memory.id);
+            }
+        }
+
+        Ok(demoted)
+    }
+
+    /// Demote a single memory one tier cooler if it is still in `tier` and
+    /// has not been accessed since `cutoff`
+    ///
+    /// Returns `Ok(false)` if the memory no longer exists, has moved, or was
+    /// accessed since it was selected.
+    pub async fn demote_if_idle(
+        &self,
+        memory_id: Uuid,
+        tier: StorageTier,
+        cutoff: DateTime<Utc>,
+    ) -> Result<bool> {
+        let new_tier = match tier {
+            StorageTier::Hot => StorageTier::Warm,
+            StorageTier::Warm => StorageTier::Cold,
+            StorageTier::Cold => return Ok(false),
+        };
+
+        let Some(memory) = self.store.get(memory_id).await? else {
+            return Ok(false);
+        };
+        if memory.tier != tier || memory.last_accessed >= cutoff {
+            return Ok(false);
+        }
+
+        self.store.update_tier(memory_id, new_tier).await?;
+        Ok(true)
+    }
 }
 
 #[cfg(test)]
@@ -618,5 +674,31 @@ mod tests {
             let manager = TierManager::with_config(&store, custom_config);
             assert!(manager.should_promote(id).await.unwrap());
         }
+
+        #[tokio::test]
+        async fn test_demote_idle() {
+            let temp_dir = tempfile::tempdir().unwrap();
+            let mut store = LanceStore::connect(temp_dir.path()).await.unwrap();
+            store.create_memories_table().await.unwrap();
+
+            let mut idle = create_test_memory(StorageTier::Hot);
+            idle.last_accessed = Utc::now() - Duration::days(10);
+            let idle_id = idle.id;
+            store.insert(&idle).await.unwrap();
+
+            let recent = create_test_memory(StorageTier::Hot);
+            let recent_id = recent.id;
+            store.insert(&recent).await.unwrap();
+
+            let manager = TierManager::new(&store);
+            let demoted = manager
+                .demote_idle(StorageTier::Hot, Duration::days(7))
+                .await
+                .unwrap();
+
+            assert_eq!(demoted, vec![idle_id]);
+            assert_eq!(manager.get_tier(idle_id).await.unwrap(), StorageTier::Warm);
+            assert_eq!(manager.get_tier(recent_id).await.unwrap(), StorageTier::Hot);
+        }
     }
 }
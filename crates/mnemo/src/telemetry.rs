@@ -0,0 +1,161 @@
+//! Prometheus metrics
+//!
+//! Metrics are recorded through the `metrics` facade and rendered in the
+//! Prometheus text exposition format by the `/metrics` route. Recording is a
+//! no-op until [`install`] has registered the recorder.
+
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use metrics::{counter, gauge, histogram};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder, PrometheusHandle};
+
+use crate::memory::types::StorageTier;
+
+pub const REQUESTS_TOTAL: &str = "mnemo_requests_total";
+pub const REQUEST_DURATION_SECONDS: &str = "mnemo_request_duration_seconds";
+pub const MEMORIES_INJECTED: &str = "mnemo_memories_injected";
+pub const INJECTION_TOKENS: &str = "mnemo_injection_tokens";
+pub const EMBEDDING_DURATION_SECONDS: &str = "mnemo_embedding_duration_seconds";
+pub const NER_DURATION_SECONDS: &str = "mnemo_ner_duration_seconds";
+pub const LANCEDB_OPERATION_DURATION_SECONDS: &str = "mnemo_lancedb_operation_duration_seconds";
+pub const MEMORIES: &str = "mnemo_memories";
+
+/// Buckets for request durations, in seconds
+const REQUEST_BUCKETS: &[f64] = &[0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0];
+
+/// Buckets for in-process inference and storage durations, in seconds
+const OPERATION_BUCKETS: &[f64] = &[0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5];
+
+/// Buckets for the number of memories injected into a request
+const MEMORY_COUNT_BUCKETS: &[f64] = &[0.0, 1.0, 2.0, 3.0, 5.0, 8.0, 13.0, 21.0];
+
+/// Buckets for injected token counts
+const TOKEN_BUCKETS: &[f64] = &[0.0, 100.0, 250.0, 500.0, 1000.0, 2000.0, 4000.0, 8000.0];
+
+static HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+
+/// Install the Prometheus recorder, returning the handle used to render metrics
+///
+/// Safe to call more than once; the recorder is only installed the first time.
+/// If another global recorder is already registered, metrics are not recorded
+/// and rendering returns an empty exposition.
+pub fn install() -> &'static PrometheusHandle {
+    HANDLE.get_or_init(|| {
+        let recorder = builder().build_recorder();
+        let handle = recorder.handle();
+        if let Err(e) = metrics::set_global_recorder(recorder) {
+            tracing::warn!("Failed to install Prometheus recorder: {}", e);
+        }
+        handle
+    })
+}
+
+fn builder() -> PrometheusBuilder {
+    let buckets = [
+        (REQUEST_DURATION_SECONDS, REQUEST_BUCKETS),
+        (EMBEDDING_DURATION_SECONDS, OPERATION_BUCKETS),
+        (NER_DURATION_SECONDS, OPERATION_BUCKETS),
+        (LANCEDB_OPERATION_DURATION_SECONDS, OPERATION_BUCKETS),
+        (MEMORIES_INJECTED, MEMORY_COUNT_BUCKETS),
+        (INJECTION_TOKENS, TOKEN_BUCKETS),
+    ];
+
+    buckets
+        .into_iter()
+        .fold(PrometheusBuilder::new(), |builder, (name, values)| {
+            builder
+                .set_buckets_for_metric(Matcher::Full(name.to_string()), values)
+                .expect("bucket values are non-empty")
+        })
+}
+
+/// Render all metrics in the Prometheus text format
+pub fn render() -> String {
+    install().render()
+}
+
+/// Record a completed upstream request
+pub fn record_request(host: &str, provider: &str, status: u16, duration: Duration) {
+    let labels = [
+        ("host", host.to_string()),
+        ("provider", provider.to_string()),
+    ];
+    counter!(
+        REQUESTS_TOTAL,
+        "host" => host.to_string(),
+        "provider" => provider.to_string(),
+        "status" => status.to_string()
+    )
+    .increment(1);
+    histogram!(REQUEST_DURATION_SECONDS, &labels).record(duration.as_secs_f64());
+}
+
+/// Record the memories and tokens injected into a request
+pub fn record_injection(provider: &str, memories: usize, tokens: usize) {
+    let labels = [("provider", provider.to_string())];
+    histogram!(MEMORIES_INJECTED, &labels).record(memories as f64);
+    histogram!(INJECTION_TOKENS, &labels).record(tokens as f64);
+}
+
+/// Record the duration of an embedding inference
+pub fn record_embedding(duration: Duration) {
+    histogram!(EMBEDDING_DURATION_SECONDS).record(duration.as_secs_f64());
+}
+
+/// Record the duration of an NER inference
+pub fn record_ner(duration: Duration) {
+    histogram!(NER_DURATION_SECONDS).record(duration.as_secs_f64());
+}
+
+/// Set the memory count gauge for a storage tier
+pub fn set_tier_count(tier: StorageTier, count: usize) {
+    let tier = format!("{tier:?}").to_lowercase();
+    gauge!(MEMORIES, "tier" => tier).set(count as f64);
+}
+
+/// Records the duration of a LanceDB operation when dropped
+pub struct OperationTimer {
+    operation: &'static str,
+    start: Instant,
+}
+
+impl OperationTimer {
+    pub fn start(operation: &'static str) -> Self {
+        Self {
+            operation,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for OperationTimer {
+    fn drop(&mut self) {
+        histogram!(LANCEDB_OPERATION_DURATION_SECONDS, "operation" => self.operation)
+            .record(self.start.elapsed().as_secs_f64());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_exports_recorded_metrics() {
+        install();
+        record_request("api.openai.com", "OpenAI", 200, Duration::from_millis(120));
+        record_injection("OpenAI", 3, 450);
+        set_tier_count(StorageTier::Hot, 7);
+        drop(OperationTimer::start("search"));
+
+        let output = render();
+
+        assert!(output.contains("mnemo_requests_total{"));
+        assert!(output.contains("host=\"api.openai.com\""));
+        assert!(output.contains("mnemo_request_duration_seconds_bucket{"));
+        assert!(output.contains("mnemo_memories_injected_bucket{"));
+        assert!(output.contains("mnemo_injection_tokens_sum{"));
+        assert!(output.contains("mnemo_memories{tier=\"hot\"} 7"));
+        assert!(output.contains("operation=\"search\""));
+    }
+}
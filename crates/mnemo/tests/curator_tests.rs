@@ -357,6 +357,7 @@ async fn test_buffer_token_limit_eviction() {
     let config = BufferConfig {
         max_turns: 100,    // High turn limit
         max_tokens: 10,    // Very low token limit (~40 chars of content)
+        session_ttl_secs: 1800,
     };
     let mut buffer = ConversationBuffer::new(&config);
 
@@ -420,6 +421,7 @@ async fn test_role_and_turn() {
     assert_eq!(Role::User.as_str(), "user");
     assert_eq!(Role::Assistant.as_str(), "assistant");
     assert_eq!(Role::System.as_str(), "system");
+    assert_eq!(Role::Tool.as_str(), "tool");
 
     // Test conversation turn creation
     let turn = ConversationTurn::new(Role::User, "Hello world".to_string());
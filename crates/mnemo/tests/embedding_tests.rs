@@ -16,7 +16,7 @@ fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
 
 /// Get reference to the shared embedding model
 fn get_model() -> &'static mnemo_server::embedding::EmbeddingModel {
-    &*SHARED_EMBEDDING_MODEL
+    &SHARED_EMBEDDING_MODEL
 }
 
 mod model_loading_tests {
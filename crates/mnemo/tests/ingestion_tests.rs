@@ -5,6 +5,7 @@
 
 use mnemo_server::memory::ingestion::IngestionPipeline;
 use mnemo_server::memory::types::{CompressionLevel, MemorySource, MemoryType, StorageTier};
+use mnemo_server::proxy::ToolCall;
 use mnemo_server::storage::LanceStore;
 use tempfile::tempdir;
 
@@ -353,6 +354,30 @@ mod memory_type_assignment_tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_tool_call_assigns_procedural_with_tool_entity() {
+        let (store, _dir) = create_test_store().await;
+        let mut pipeline = IngestionPipeline::new_owned(store).expect("Failed to create pipeline");
+
+        let call = ToolCall {
+            id: Some("call_1".to_string()),
+            name: "get_weather".to_string(),
+            arguments: r#"{"city":"Paris"}"#.to_string(),
+            result: Some("18C and sunny".to_string()),
+        };
+
+        let memory = pipeline
+            .ingest_tool_call(&call, Some("test-conv".to_string()))
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(memory.memory_type, MemoryType::Procedural);
+        assert_eq!(memory.entities[0], "get_weather");
+        assert!(memory.content.contains("Result: 18C and sunny"));
+        assert_eq!(memory.conversation_id, Some("test-conv".to_string()));
+    }
+
     #[tokio::test]
     async fn test_conversation_id_preserved() {
         let (store, _dir) = create_test_store().await;
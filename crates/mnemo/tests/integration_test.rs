@@ -21,6 +21,7 @@ use serde_json::json;
 use tempfile::TempDir;
 use tower::ServiceExt;
 
+use mnemo_server::config::IngestionConfig;
 use mnemo_server::memory::ingestion::IngestionPipeline;
 use mnemo_server::memory::retrieval::{RetrievalPipeline, RetrievedMemory};
 use mnemo_server::memory::types::{CompressionLevel, Memory, MemorySource, MemoryType, StorageTier};
@@ -204,7 +205,7 @@ mod full_proxy_flow_tests {
         let count = store.count_by_tier(StorageTier::Hot).await.unwrap();
         assert!(count >= 3, "Should have at least 3 memories ingested");
 
-        let mut retrieval = RetrievalPipeline::with_defaults(&store, &*SHARED_EMBEDDING_MODEL);
+        let mut retrieval = RetrievalPipeline::with_defaults(&store, &SHARED_EMBEDDING_MODEL);
 
         let results = retrieval
             .retrieve("What programming language does the user prefer?", 5)
@@ -536,7 +537,13 @@ mod ingestion_tests {
     #[tokio::test]
     async fn test_compression_levels() {
         let (store, _temp_dir) = create_test_store().await;
-        let mut pipeline = IngestionPipeline::new_owned(store).expect("Failed to create pipeline");
+        // Repeated-character inputs embed almost identically, so keep them as separate memories
+        let mut pipeline = IngestionPipeline::new_owned(store)
+            .expect("Failed to create pipeline")
+            .with_config(IngestionConfig {
+                dedup_enabled: false,
+                ..IngestionConfig::default()
+            });
 
         // Short content -> Full compression
         let short_mem = pipeline
@@ -948,7 +955,7 @@ mod full_pipeline_tests {
         let count = store.count_by_tier(StorageTier::Hot).await.unwrap();
         assert_eq!(count, 5);
 
-        let mut retrieval = RetrievalPipeline::with_defaults(&store, &*SHARED_EMBEDDING_MODEL);
+        let mut retrieval = RetrievalPipeline::with_defaults(&store, &SHARED_EMBEDDING_MODEL);
 
         let results = retrieval
             .retrieve("What editor does the user prefer?", 3)
@@ -6,7 +6,8 @@ use axum::http::HeaderMap;
 use serde_json::json;
 use url::Url;
 
-use mnemo_server::proxy::Provider;
+use mnemo_server::config::RouteRule;
+use mnemo_server::proxy::{Provider, Route};
 
 // =============================================================================
 // URL-based Detection Tests
@@ -52,6 +53,65 @@ fn test_detect_anthropic_from_subdomain() {
     assert_eq!(provider, Provider::Anthropic);
 }
 
+#[test]
+fn test_detect_gemini_from_url() {
+    let url = Url::parse(
+        "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.0-flash:generateContent",
+    )
+    .unwrap();
+    let headers = HeaderMap::new();
+    let body = json!({});
+
+    let provider = Provider::detect(&url, &headers, &body);
+    assert_eq!(provider, Provider::Gemini);
+}
+
+#[test]
+fn test_detect_gemini_from_method_suffix() {
+    // Vertex AI hosts share the generateContent method names
+    let url = Url::parse(
+        "https://us-central1-aiplatform.googleapis.com/v1/projects/p/locations/us-central1/publishers/google/models/gemini-2.0-flash:streamGenerateContent",
+    )
+    .unwrap();
+    let mut headers = HeaderMap::new();
+    headers.insert("authorization", "Bearer ya29.xxx".parse().unwrap());
+    let body = json!({});
+
+    let provider = Provider::detect(&url, &headers, &body);
+    assert_eq!(provider, Provider::Gemini);
+}
+
+#[test]
+fn test_detect_ollama_from_path() {
+    let headers = HeaderMap::new();
+    let body = json!({"model": "llama3.2", "messages": [{"role": "system", "content": "Hi"}]});
+
+    for path in ["/api/chat", "/api/generate"] {
+        let url = Url::parse(&format!("http://localhost:11434{path}")).unwrap();
+        assert_eq!(Provider::detect(&url, &headers, &body), Provider::Ollama);
+    }
+}
+
+#[test]
+fn test_detect_llamacpp_from_path() {
+    let url = Url::parse("http://127.0.0.1:8080/completion").unwrap();
+    let headers = HeaderMap::new();
+    let body = json!({"prompt": "Hello", "n_predict": 128});
+
+    let provider = Provider::detect(&url, &headers, &body);
+    assert_eq!(provider, Provider::LlamaCpp);
+}
+
+#[test]
+fn test_openai_completions_path_not_llamacpp() {
+    let url = Url::parse("http://localhost:8080/v1/completions").unwrap();
+    let headers = HeaderMap::new();
+    let body = json!({"prompt": "Hello"});
+
+    let provider = Provider::detect(&url, &headers, &body);
+    assert_ne!(provider, Provider::LlamaCpp);
+}
+
 // =============================================================================
 // Body Structure Detection Tests
 // =============================================================================
@@ -88,6 +148,21 @@ fn test_detect_openai_from_body_structure() {
     assert_eq!(provider, Provider::OpenAI);
 }
 
+#[test]
+fn test_detect_openai_responses_from_body_structure() {
+    // Responses API bodies carry `input` and optional `instructions`
+    let url = Url::parse("https://unknown-api.example.com/v1/respond").unwrap();
+    let headers = HeaderMap::new();
+    let body = json!({
+        "model": "gpt-4o",
+        "instructions": "You are a helpful assistant",
+        "input": [{"role": "user", "content": "Hello"}]
+    });
+
+    let provider = Provider::detect(&url, &headers, &body);
+    assert_eq!(provider, Provider::OpenAI);
+}
+
 #[test]
 fn test_detect_anthropic_from_max_tokens_required() {
     // Anthropic requires max_tokens, OpenAI doesn't
@@ -119,6 +194,17 @@ fn test_detect_anthropic_from_header_hint() {
     assert_eq!(provider, Provider::Anthropic);
 }
 
+#[test]
+fn test_detect_gemini_from_header() {
+    let url = Url::parse("https://unknown-api.example.com/v1/generate").unwrap();
+    let mut headers = HeaderMap::new();
+    headers.insert("x-goog-api-key", "AIza-xxx".parse().unwrap());
+    let body = json!({});
+
+    let provider = Provider::detect(&url, &headers, &body);
+    assert_eq!(provider, Provider::Gemini);
+}
+
 #[test]
 fn test_openai_from_authorization_header() {
     // Authorization header with Bearer token (common for OpenAI)
@@ -229,3 +315,146 @@ fn test_anthropic_messages_format() {
     let provider = Provider::detect(&url, &headers, &body);
     assert_eq!(provider, Provider::Anthropic);
 }
+
+#[test]
+fn test_gemini_contents_format() {
+    let url = Url::parse("https://unknown-api.example.com/v1/generate").unwrap();
+    let headers = HeaderMap::new();
+    let body = json!({
+        "contents": [{"role": "user", "parts": [{"text": "Hello"}]}]
+    });
+
+    let provider = Provider::detect(&url, &headers, &body);
+    assert_eq!(provider, Provider::Gemini);
+}
+
+// =============================================================================
+// Route Rule Tests
+// =============================================================================
+
+fn route_rule(host: Option<&str>, provider: Option<Provider>) -> RouteRule {
+    RouteRule {
+        host: host.map(String::from),
+        path_prefix: None,
+        header: None,
+        header_value: None,
+        provider,
+        inject: true,
+        capture: true,
+    }
+}
+
+#[test]
+fn test_route_without_rules_falls_back_to_detection() {
+    let url = Url::parse("https://api.anthropic.com/v1/messages").unwrap();
+    let body = json!({"model": "claude-3", "messages": []});
+
+    let route = Route::resolve(&[], &url, &HeaderMap::new(), Some(&body));
+    assert_eq!(route.provider, Provider::Anthropic);
+    assert!(route.inject);
+    assert!(route.capture);
+}
+
+#[test]
+fn test_route_rule_overrides_detection() {
+    let url = Url::parse("https://gateway.example.com/v1/chat/completions").unwrap();
+    let body = json!({"model": "claude-3", "messages": []});
+    let rules = vec![route_rule(
+        Some("gateway.example.com"),
+        Some(Provider::Anthropic),
+    )];
+
+    let route = Route::resolve(&rules, &url, &HeaderMap::new(), Some(&body));
+    assert_eq!(route.provider, Provider::Anthropic);
+}
+
+#[test]
+fn test_route_rule_wildcard_host() {
+    let url = Url::parse("https://my-resource.openai.azure.com/openai/deployments/gpt").unwrap();
+    let body = json!({"messages": []});
+    let rules = vec![route_rule(
+        Some("*.openai.azure.com"),
+        Some(Provider::OpenAI),
+    )];
+
+    let route = Route::resolve(&rules, &url, &HeaderMap::new(), Some(&body));
+    assert_eq!(route.provider, Provider::OpenAI);
+}
+
+#[test]
+fn test_route_first_matching_rule_wins() {
+    let url = Url::parse("https://gateway.example.com/anthropic/v1/messages").unwrap();
+    let body = json!({"messages": []});
+    let mut by_path = route_rule(None, Some(Provider::Anthropic));
+    by_path.path_prefix = Some("/anthropic".to_string());
+    by_path.capture = false;
+    let rules = vec![
+        by_path,
+        route_rule(Some("gateway.example.com"), Some(Provider::OpenAI)),
+    ];
+
+    let route = Route::resolve(&rules, &url, &HeaderMap::new(), Some(&body));
+    assert_eq!(route.provider, Provider::Anthropic);
+    assert!(route.inject);
+    assert!(!route.capture);
+
+    let other = Url::parse("https://gateway.example.com/openai/v1/chat/completions").unwrap();
+    let route = Route::resolve(&rules, &other, &HeaderMap::new(), Some(&body));
+    assert_eq!(route.provider, Provider::OpenAI);
+    assert!(route.capture);
+}
+
+#[test]
+fn test_route_rule_header_match() {
+    let url = Url::parse("https://gateway.example.com/v1/chat").unwrap();
+    let body = json!({"messages": []});
+    let mut rule = route_rule(None, Some(Provider::Gemini));
+    rule.header = Some("x-upstream".to_string());
+    rule.header_value = Some("gemini".to_string());
+    let rules = vec![rule];
+
+    let mut headers = HeaderMap::new();
+    headers.insert("x-upstream", "gemini".parse().unwrap());
+    assert_eq!(
+        Route::resolve(&rules, &url, &headers, Some(&body)).provider,
+        Provider::Gemini
+    );
+
+    headers.insert("x-upstream", "openai".parse().unwrap());
+    assert_eq!(
+        Route::resolve(&rules, &url, &headers, Some(&body)).provider,
+        Provider::Unknown
+    );
+}
+
+#[test]
+fn test_route_rule_without_provider_keeps_detection() {
+    let url = Url::parse("https://api.openai.com/v1/chat/completions").unwrap();
+    let body = json!({"messages": []});
+    let mut rule = route_rule(Some("api.openai.com"), None);
+    rule.inject = false;
+
+    let route = Route::resolve(&[rule], &url, &HeaderMap::new(), Some(&body));
+    assert_eq!(route.provider, Provider::OpenAI);
+    assert!(!route.inject);
+    assert!(route.capture);
+}
+
+#[test]
+fn test_route_rules_apply_to_non_json_bodies() {
+    let url = Url::parse("https://gateway.example.com/v1/chat/completions").unwrap();
+    let rules = vec![route_rule(
+        Some("gateway.example.com"),
+        Some(Provider::Anthropic),
+    )];
+
+    let route = Route::resolve(&rules, &url, &HeaderMap::new(), None);
+    assert_eq!(route.provider, Provider::Anthropic);
+    assert!(!route.inject);
+    assert!(route.capture);
+
+    let url = Url::parse("https://api.openai.com/v1/audio/transcriptions").unwrap();
+    let route = Route::resolve(&[], &url, &HeaderMap::new(), None);
+    assert_eq!(route.provider, Provider::OpenAI);
+    assert!(!route.inject);
+}
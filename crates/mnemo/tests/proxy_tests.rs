@@ -14,18 +14,19 @@ use tower::ServiceExt;
 use wiremock::{Mock, MockServer, ResponseTemplate, matchers};
 
 use dashmap::DashMap;
-use mnemo_server::config::{ProxyConfig, RouterConfig};
-use mnemo_server::curator::ConversationBuffer;
+use mnemo_server::admin::StatsCollector;
+use mnemo_server::config::{CaptureConfig, InjectionTrackingConfig, ProxyConfig, RouterConfig};
 use mnemo_server::embedding::EmbeddingModel;
 use mnemo_server::memory::retrieval::RetrievedMemory;
 use mnemo_server::memory::types::{Memory, MemorySource, MemoryType};
+use mnemo_server::memory::{RollingQueryEmbeddings, SessionInjectionTrackers};
 use mnemo_server::proxy::{AppState, create_router};
 use mnemo_server::proxy::{
     estimate_tokens, extract_user_query, format_memory_block, inject_memories, truncate_to_budget,
 };
 use mnemo_server::router::MemoryRouter;
 use mnemo_server::storage::LanceStore;
-use tokio::sync::{broadcast, Mutex as TokioMutex};
+use tokio::sync::{Mutex as TokioMutex, broadcast};
 
 // =============================================================================
 // Test Fixtures
@@ -569,7 +570,9 @@ mod passthrough_tests {
             upstream_url: None,
             timeout_secs: 10,
             max_injection_tokens: 2000,
+            tombstone_budget_share: 0.2,
             allowed_hosts,
+            routes: Vec::new(),
         };
         let store = Arc::new(TokioMutex::new(store));
         let embedding_model = Arc::new(EmbeddingModel::new().unwrap());
@@ -597,6 +600,12 @@ mod passthrough_tests {
             curator: None,
             conversation_buffers: Arc::new(DashMap::new()),
             curator_config: None,
+            capture_config: CaptureConfig::default(),
+            stats: Arc::new(StatsCollector::new()),
+            injection_trackers: Arc::new(SessionInjectionTrackers::new(
+                InjectionTrackingConfig::default(),
+            )),
+            rolling_queries: Arc::new(RollingQueryEmbeddings::default()),
         });
         create_router(state)
     }
@@ -1039,4 +1048,46 @@ mod passthrough_tests {
 
         assert_eq!(response.status(), StatusCode::OK);
     }
+
+    #[tokio::test]
+    async fn test_passthrough_sse_response_streamed() {
+        let mock_server = MockServer::start().await;
+
+        let sse_body = "data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\"Hello\"}}]}\n\n\
+                        data: {\"choices\":[{\"index\":0,\"delta\":{\"content\":\" there\"}}]}\n\n\
+                        data: [DONE]\n\n";
+
+        Mock::given(matchers::method("POST"))
+            .and(matchers::path("/v1/chat/completions"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("Content-Type", "text/event-stream")
+                    .set_body_string(sse_body),
+            )
+            .mount(&mock_server)
+            .await;
+
+        let router = create_test_router_with_allowed(vec!["127.0.0.1".to_string()]).await;
+
+        let target = format!("{}/v1/chat/completions", mock_server.uri());
+        let request = Request::builder()
+            .method("POST")
+            .uri(format!("/p/{}", target))
+            .header("Content-Type", "application/json")
+            .body(Body::from(r#"{"model": "gpt-4", "stream": true}"#))
+            .unwrap();
+
+        let response = router.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers().get("Content-Type").unwrap(),
+            "text/event-stream"
+        );
+
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&body_bytes), sse_body);
+    }
 }
@@ -9,7 +9,7 @@ use mnemo_server::testing::SHARED_MEMORY_ROUTER;
 
 /// Test helper to get the shared router instance
 fn get_router() -> &'static MemoryRouter {
-    &*SHARED_MEMORY_ROUTER
+    &SHARED_MEMORY_ROUTER
 }
 
 mod router_creation_tests {